@@ -0,0 +1,144 @@
+use std::{borrow::Cow, collections::HashMap, fs::File, io, path::Path, time::Duration};
+
+use crate::{Recorder, Visualize};
+
+/// Records frames into an animated GIF that loops forever, drawing each character as a square
+/// of pixels in the frame's colors. Captions aren't drawn, and every frame has to be the same
+/// size as the first.
+pub struct GifRecorder<W: io::Write> {
+    /// Where the GIF goes, until the first frame gives its size and it's handed to `encoder`
+    out: Option<W>,
+    /// The encoder and the width and height in pixels of every frame
+    encoder: Option<(gif::Encoder<W>, u16, u16)>,
+    /// Width and height in pixels of each character
+    scale: u16,
+    /// How long each frame is shown for, in hundredths of a second
+    delay: u16,
+}
+
+impl GifRecorder<File> {
+    pub fn create(path: &Path, scale: u16, delay: Duration) -> io::Result<Self> {
+        Ok(Self::new(File::create(path)?, scale, delay))
+    }
+}
+
+impl<W: io::Write> GifRecorder<W> {
+    pub fn new(out: W, scale: u16, delay: Duration) -> Self {
+        Self {
+            out: Some(out),
+            encoder: None,
+            scale: scale.max(1),
+            delay: (delay.as_millis() / 10).try_into().unwrap_or(u16::MAX),
+        }
+    }
+
+    /// Finishes off the GIF, which is only valid once this is done.
+    pub fn finish(self) -> io::Result<W> {
+        match self.encoder {
+            Some((encoder, _, _)) => encoder.into_inner(),
+            None => Err(io::Error::other("an animation needs at least one frame")),
+        }
+    }
+}
+
+fn io_error(e: gif::EncodingError) -> io::Error {
+    match e {
+        gif::EncodingError::Io(e) => e,
+        e => io::Error::other(e),
+    }
+}
+
+impl<W: io::Write> Recorder for GifRecorder<W> {
+    fn record(&mut self, frame: &impl Visualize, _caption: &str) -> io::Result<()> {
+        let grid = frame.frame();
+        let scale = usize::from(self.scale);
+        let too_big = || io::Error::other("the frame is too big for a GIF");
+        let width: u16 = (grid.width() * scale).try_into().map_err(|_| too_big())?;
+        let height: u16 = (grid.height() * scale).try_into().map_err(|_| too_big())?;
+
+        // Each frame gets its own palette of just the colors in it
+        let mut palette = vec![];
+        let mut indices: HashMap<char, u8> = HashMap::new();
+        let mut pixels = Vec::with_capacity(usize::from(width) * usize::from(height));
+        for row in grid.rows() {
+            let mut line = Vec::with_capacity(usize::from(width));
+            for &ch in row {
+                let index = match indices.get(&ch) {
+                    Some(&index) => index,
+                    None => {
+                        let index = u8::try_from(indices.len()).map_err(|_| {
+                            io::Error::other("a GIF frame can only have 256 colors")
+                        })?;
+                        palette.extend(frame.color(ch));
+                        indices.insert(ch, index);
+                        index
+                    }
+                };
+                line.extend(std::iter::repeat_n(index, scale));
+            }
+            for _ in 0..scale {
+                pixels.extend(&line);
+            }
+        }
+
+        let (encoder, first_width, first_height) = match &mut self.encoder {
+            Some(encoder) => encoder,
+            None => {
+                let out = self
+                    .out
+                    .take()
+                    .expect("only the first frame starts the encoder");
+                let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(io_error)?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(io_error)?;
+                self.encoder.insert((encoder, width, height))
+            }
+        };
+        if (width, height) != (*first_width, *first_height) {
+            return Err(io::Error::other(format!(
+                "a {width}x{height} frame doesn't fit a {first_width}x{first_height} animation"
+            )));
+        }
+        encoder
+            .write_frame(&gif::Frame {
+                width,
+                height,
+                buffer: Cow::Owned(pixels),
+                palette: Some(palette),
+                delay: self.delay,
+                ..Default::default()
+            })
+            .map_err(io_error)
+    }
+}
+
+#[test]
+fn test_gif_recorder() {
+    use crate::Grid;
+
+    let mut gif = GifRecorder::new(vec![], 2, Duration::from_millis(100));
+    let grid: Grid<char> = "#.\n.#\n".parse().unwrap();
+    gif.record(&grid, "first").unwrap();
+    gif.record(&grid.map(|_| '.'), "second").unwrap();
+    assert!(gif.record(&Grid::new(1, 1, '#'), "too small").is_err());
+    let bytes = gif.finish().unwrap();
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(&bytes[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (4, 4));
+    let first = decoder.read_next_frame().unwrap().unwrap();
+    assert_eq!(first.delay, 10);
+    // The top left character is white, drawn as 2x2 pixels, and the one right of it is black
+    let pixel = |x: usize, y: usize| &first.buffer[(y * 4 + x) * 4..][..3];
+    assert_eq!(pixel(1, 1), [255, 255, 255]);
+    assert_eq!(pixel(2, 1), [0, 0, 0]);
+    assert_eq!(pixel(3, 3), [255, 255, 255]);
+    assert!(decoder.read_next_frame().unwrap().is_some());
+    assert!(decoder.read_next_frame().unwrap().is_none());
+
+    assert!(GifRecorder::new(vec![], 1, Duration::ZERO)
+        .finish()
+        .is_err());
+}
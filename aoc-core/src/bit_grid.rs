@@ -0,0 +1,166 @@
+use crate::Coord;
+
+/// One bit per cell of a `width` by `height` area, for marking which cells have been seen or
+/// reached. Stored row by row with each row padded out to a whole number of `u64` words, so
+/// whole rows can be worked on a word at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An area with nothing set.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    /// An area with the cells `f` says yes to set.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> bool) -> Self {
+        let mut bits = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let coord = Coord::new(x as i64, y as i64);
+                if f(coord) {
+                    bits.set(coord);
+                }
+            }
+        }
+        bits
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        (0..self.width as i64).contains(&coord.x) && (0..self.height as i64).contains(&coord.y)
+    }
+
+    /// Whether `coord` is set, which it never is outside the area.
+    pub fn get(&self, coord: Coord) -> bool {
+        if !self.contains(coord) {
+            return false;
+        }
+        let x = coord.x as usize;
+        self.row(coord.y as usize)[x / 64] & (1 << (x % 64)) != 0
+    }
+
+    /// Sets `coord`, returning whether it wasn't set already.
+    pub fn set(&mut self, coord: Coord) -> bool {
+        assert!(self.contains(coord), "{coord} is outside the grid");
+        let x = coord.x as usize;
+        let word = &mut self.row_mut(coord.y as usize)[x / 64];
+        let bit = 1 << (x % 64);
+        let was_set = *word & bit != 0;
+        *word |= bit;
+        !was_set
+    }
+
+    /// The words row `y` is stored in, with x = 0 the lowest bit of the first. The bits past
+    /// the last column have to be left clear.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Whether nothing is set.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// How many cells are set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Keeps only the cells that are set in `other` too, which has to be the same size.
+    pub fn and(&mut self, other: &BitGrid) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    /// Clears the cells that are set in `other`, which has to be the same size.
+    pub fn and_not(&mut self, other: &BitGrid) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+    }
+
+    /// Sets the cells that are set in `other`, which has to be the same size.
+    pub fn or(&mut self, other: &BitGrid) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Every set cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.height).flat_map(move |y| {
+            self.row(y).iter().enumerate().flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(Coord::new((i * 64 + bit) as i64, y as i64))
+                })
+            })
+        })
+    }
+}
+
+#[test]
+fn test_bit_grid() {
+    let mut bits = BitGrid::new(70, 3);
+    assert!(bits.is_empty());
+    assert!(bits.set(Coord::new(0, 0)));
+    assert!(bits.set(Coord::new(69, 1)));
+    assert!(bits.set(Coord::new(64, 2)));
+    assert!(!bits.set(Coord::new(69, 1)));
+    assert!(bits.get(Coord::new(69, 1)));
+    assert!(!bits.get(Coord::new(68, 1)));
+    assert!(!bits.get(Coord::new(70, 1)));
+    assert!(!bits.get(Coord::new(-1, 0)));
+    assert_eq!(bits.count(), 3);
+    assert_eq!(
+        bits.iter().collect::<Vec<_>>(),
+        [Coord::new(0, 0), Coord::new(69, 1), Coord::new(64, 2)]
+    );
+}
+
+#[test]
+fn test_combine() {
+    let left = BitGrid::from_fn(5, 2, |c| c.x < 3);
+    let even = BitGrid::from_fn(5, 2, |c| (c.x + c.y) % 2 == 0);
+    let mut both = left.clone();
+    both.and(&even);
+    assert_eq!(both.count(), 3);
+    let mut either = left.clone();
+    either.or(&even);
+    assert_eq!(either.count(), 8);
+    let mut only_left = left;
+    only_left.and_not(&even);
+    assert_eq!(
+        only_left.iter().collect::<Vec<_>>(),
+        [Coord::new(1, 0), Coord::new(0, 1), Coord::new(2, 1)]
+    );
+}
@@ -0,0 +1,34 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Lets whoever is waiting on a part tell it to give up, so a part that's been abandoned stops
+/// instead of going on with nobody to answer. Clones share the one flag, so a part that
+/// searches on several threads can check it from any of them.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[test]
+fn test_cancel() {
+    let cancel = Cancel::new();
+    let shared = cancel.clone();
+    assert!(!shared.is_cancelled());
+    cancel.cancel();
+    assert!(shared.is_cancelled());
+    assert!(!Cancel::new().is_cancelled());
+}
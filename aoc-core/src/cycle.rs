@@ -0,0 +1,180 @@
+//! Finding where a sequence of states starts repeating, for simulations that have to be run
+//! for far more steps than is possible one at a time. Only works for states that are
+//! guaranteed to repeat eventually, or else these never return.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the sequence `start`, `step(start)`, `step(step(start))`, ... starts repeating, as
+/// the number of steps before the first state that repeats and the number of steps between
+/// repeats, using Brent's algorithm so that only a couple of states are kept at a time.
+pub fn find_cycle<S: Clone + Eq>(start: S, mut step: impl FnMut(S) -> S) -> (usize, usize) {
+    // Find the period: the hare goes ahead in powers of two, with the tortoise waiting at the
+    // start of each until the hare comes back around to it
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start.clone());
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(hare);
+        period += 1;
+    }
+
+    // Find the offset: with the hare a period ahead, they first meet where the cycle starts
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(hare);
+    }
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        offset += 1;
+    }
+    (offset, period)
+}
+
+/// The fewest steps that end up in the same state as `n` steps do, for a sequence with the
+/// given offset and period from [`find_cycle`].
+pub fn shortcut(n: usize, (offset, period): (usize, usize)) -> usize {
+    if n < offset {
+        n
+    } else {
+        offset + (n - offset) % period
+    }
+}
+
+/// The state after `n` steps from `start`, skipping over as many repeats as it can.
+pub fn nth<S: Clone + Eq>(start: S, mut step: impl FnMut(S) -> S, n: usize) -> S {
+    let cycle = find_cycle(start.clone(), &mut step);
+    (0..shortcut(n, cycle)).fold(start, |state, _| step(state))
+}
+
+/// Every state of a simulation up to where it starts repeating, from [`simulate`], so that
+/// the state after any number of steps can be looked up without stepping again.
+#[derive(Debug, Clone)]
+pub struct Simulation<S> {
+    /// The states in order, with the one after the last being `states[offset]` again, or with
+    /// `offset` just past the end if it stopped before anything repeated
+    states: Vec<S>,
+    offset: usize,
+}
+
+/// Steps from `start` until a state comes around again, keeping every state on the way. Takes
+/// as few steps as possible, unlike [`find_cycle`], at the cost of keeping them all.
+pub fn simulate<S: Clone + Eq + Hash>(start: S, step: impl FnMut(S) -> S) -> Simulation<S> {
+    simulate_up_to(start, step, usize::MAX)
+}
+
+/// Like [`simulate`], but stops after `limit` steps even if nothing has repeated yet, for
+/// states that might take far longer than that to come around again.
+pub fn simulate_up_to<S: Clone + Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(S) -> S,
+    limit: usize,
+) -> Simulation<S> {
+    let mut seen = HashMap::new();
+    let mut state = start;
+    let offset = loop {
+        if let Some(&first) = seen.get(&state) {
+            break first;
+        }
+        seen.insert(state.clone(), seen.len());
+        if seen.len() > limit {
+            break seen.len();
+        }
+        state = step(state);
+    };
+    let mut states: Vec<(S, usize)> = seen.into_iter().collect();
+    states.sort_by_key(|&(_, steps)| steps);
+    Simulation {
+        states: states.into_iter().map(|(state, _)| state).collect(),
+        offset,
+    }
+}
+
+impl<S> Simulation<S> {
+    /// Whether it got as far as a state coming around again.
+    pub fn repeats(&self) -> bool {
+        self.offset < self.states.len()
+    }
+
+    /// Steps before the first state that repeats, or every step taken if none did.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Steps between repeats, or 0 if none did.
+    pub fn period(&self) -> usize {
+        self.states.len() - self.offset
+    }
+
+    /// Every state before the first repeat, starting with the start.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The state after `n` steps, which has to be one of [`Self::states`] if nothing repeated.
+    pub fn nth(&self, n: usize) -> &S {
+        if self.repeats() {
+            &self.states[shortcut(n, (self.offset, self.period()))]
+        } else {
+            &self.states[n]
+        }
+    }
+}
+
+#[test]
+fn test_find_cycle() {
+    // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+    let collatz = |n: u64| {
+        if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    };
+    assert_eq!(find_cycle(3, collatz), (5, 3));
+    assert_eq!(find_cycle(1, collatz), (0, 3));
+    assert_eq!(find_cycle(7, |_| 7), (0, 1));
+    assert_eq!(shortcut(4, (5, 3)), 4);
+    assert_eq!(shortcut(9, (5, 3)), 6);
+    assert_eq!(nth(3, collatz, 4), 8);
+    assert_eq!(nth(3, collatz, 1_000_000_000), 1);
+}
+
+#[test]
+fn test_simulate() {
+    // 0, 1, 2, ..., 9, 5, 6, ...
+    let simulation = simulate(0, |n| if n == 9 { 5 } else { n + 1 });
+    assert_eq!((simulation.offset(), simulation.period()), (5, 5));
+    assert_eq!(simulation.states(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(*simulation.nth(3), 3);
+    assert_eq!(*simulation.nth(12), 7);
+    assert_eq!(*simulation.nth(1_000_000_001), 6);
+
+    let mut steps = 0;
+    let simulation = simulate(3u64, |n| {
+        steps += 1;
+        if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    });
+    assert_eq!((simulation.offset(), simulation.period(), steps), (5, 3, 8));
+
+    let simulation = simulate_up_to(0, |n| if n == 9 { 5 } else { n + 1 }, 10);
+    assert!(simulation.repeats());
+    assert_eq!(*simulation.nth(12), 7);
+    let simulation = simulate_up_to(0, |n| if n == 9 { 5 } else { n + 1 }, 7);
+    assert!(!simulation.repeats());
+    assert_eq!((simulation.offset(), simulation.period()), (8, 0));
+    assert_eq!(simulation.states(), [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(*simulation.nth(7), 7);
+}
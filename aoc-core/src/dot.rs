@@ -0,0 +1,175 @@
+use std::{fmt, io, path::Path};
+
+use crate::{Graph, NodeId};
+
+/// A graph written in Graphviz's DOT language, to look at with `dot -Tsvg` or similar. Nodes
+/// and edges can be given any attributes Graphviz knows, like `label`, `color` or `shape`.
+#[derive(Debug)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    statements: Vec<String>,
+}
+
+/// `text` in quotes, with any quotes or newlines in it escaped.
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Words that mean something in DOT, whatever their case, so can't be used as plain names.
+const KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+
+/// `id` as it has to be written in DOT: as it is if it's a plain name or number, and otherwise
+/// quoted.
+fn quote(id: &str) -> String {
+    let name = id.starts_with(|c: char| !c.is_ascii_digit())
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(id));
+    let number = !id.is_empty() && id.chars().all(|c| c.is_ascii_digit());
+    if name || number {
+        id.to_string()
+    } else {
+        quoted(id)
+    }
+}
+
+/// Attributes in brackets after a node or edge, or nothing if there aren't any. Labels are
+/// always quoted, since they're text to show rather than names.
+fn attributes(attrs: &[(&str, &str)]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    let attrs: Vec<String> = attrs
+        .iter()
+        .map(|&(name, value)| match name {
+            "label" => format!("{name}={}", quoted(value)),
+            _ => format!("{name}={}", quote(value)),
+        })
+        .collect();
+    format!(" [{}]", attrs.join(", "))
+}
+
+impl Dot {
+    /// An empty graph whose edges go one way.
+    pub fn digraph(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            directed: true,
+            statements: vec![],
+        }
+    }
+
+    /// An empty graph whose edges go both ways.
+    pub fn graph(name: &str) -> Self {
+        Self {
+            directed: false,
+            ..Self::digraph(name)
+        }
+    }
+
+    pub fn node(&mut self, id: &str, attrs: &[(&str, &str)]) {
+        self.statements
+            .push(format!("{}{}", quote(id), attributes(attrs)));
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attrs: &[(&str, &str)]) {
+        let op = if self.directed { "->" } else { "--" };
+        self.statements.push(format!(
+            "{} {op} {}{}",
+            quote(from),
+            quote(to),
+            attributes(attrs)
+        ));
+    }
+
+    /// Adds every node of `graph`, identified by its [`NodeId`] and labelled by `node_label`,
+    /// then an edge to each of its successors. Edges are labelled by `edge_label` if it gives a
+    /// label, from the node they start at and which of its successors they go to, so that
+    /// edges between the same nodes can be told apart.
+    pub fn add_graph<N>(
+        &mut self,
+        graph: &Graph<N>,
+        mut node_label: impl FnMut(NodeId) -> String,
+        mut edge_label: impl FnMut(NodeId, usize) -> Option<String>,
+    ) {
+        for node in graph.nodes() {
+            self.node(&node.to_string(), &[("label", &node_label(node))]);
+        }
+        for from in graph.nodes() {
+            for (nth, &to) in graph.successors(from).iter().enumerate() {
+                let label = edge_label(from, nth);
+                let attrs: Vec<_> = label
+                    .iter()
+                    .map(|label| ("label", label.as_str()))
+                    .collect();
+                self.edge(&from.to_string(), &to.to_string(), &attrs);
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.directed { "digraph" } else { "graph" };
+        writeln!(f, "{kind} {} {{", quote(&self.name))?;
+        for statement in &self.statements {
+            writeln!(f, "    {statement};")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[test]
+fn test_quote() {
+    assert_eq!(quote("abc_1"), "abc_1");
+    assert_eq!(quote("42"), "42");
+    assert_eq!(quote("4a"), "\"4a\"");
+    assert_eq!(quote(""), "\"\"");
+    assert_eq!(quote("Node"), "\"Node\"");
+    assert_eq!(quote("a<2006"), "\"a<2006\"");
+    assert_eq!(quote("say \"hi\"\n"), "\"say \\\"hi\\\"\\n\"");
+}
+
+#[test]
+fn test_dot() {
+    let mut dot = Dot::graph("cut");
+    dot.node("bvb", &[("style", "filled"), ("fillcolor", "lightblue")]);
+    dot.edge("bvb", "cmg", &[("color", "red"), ("penwidth", "3")]);
+    dot.edge("cmg", "xhk", &[]);
+    assert_eq!(
+        dot.to_string(),
+        "graph cut {
+    bvb [style=filled, fillcolor=lightblue];
+    bvb -- cmg [color=red, penwidth=3];
+    cmg -- xhk;
+}
+"
+    );
+
+    let mut graph: Graph<String> = Graph::new();
+    let [a, b] = ["a", "b"].map(|name| graph.add_node(name));
+    graph.add_edge(a, b);
+    graph.add_edge(b, b);
+    let mut dot = Dot::digraph("two nodes");
+    dot.add_graph(
+        &graph,
+        |node| format!("%{}", graph.label(node)),
+        |from, nth| (graph.successors(from)[nth] != from).then(|| "1".to_string()),
+    );
+    assert_eq!(
+        dot.to_string(),
+        r#"digraph "two nodes" {
+    0 [label="%a"];
+    1 [label="%b"];
+    0 -> 1 [label="1"];
+    1 -> 1;
+}
+"#
+    );
+}
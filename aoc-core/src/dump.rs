@@ -0,0 +1,47 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::SolveError;
+
+/// A directory days write state from partway through solving to, as one JSON file per thing
+/// worth looking at, for analysis outside the program.
+#[derive(Debug, Clone)]
+pub struct StateDump {
+    dir: PathBuf,
+}
+
+impl StateDump {
+    /// Dumps into `dir`, which is created when the first thing is written.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes `value` as indented JSON to `{name}.json`, returning where it went.
+    pub fn write(&self, name: &str, value: &impl Serialize) -> Result<PathBuf, SolveError> {
+        let path = self.dir.join(format!("{name}.json"));
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| SolveError::failed(format!("couldn't dump {name}: {e}")))?;
+        std::fs::create_dir_all(&self.dir)
+            .and_then(|()| std::fs::write(&path, json + "\n"))
+            .map_err(|e| SolveError::failed(format!("couldn't write {}: {e}", path.display())))?;
+        Ok(path)
+    }
+}
+
+#[test]
+fn test_write() {
+    let dir = std::env::temp_dir().join(format!("aoc-dump-test-{}", std::process::id()));
+    let dump = StateDump::new(dir.join("day5"));
+    let path = dump.write("seeds", &[79, 14]).unwrap();
+    assert_eq!(path, dir.join("day5/seeds.json"));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "[\n  79,\n  14\n]\n"
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
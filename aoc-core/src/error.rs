@@ -0,0 +1,44 @@
+use thiserror::Error;
+
+use crate::SolveError;
+
+/// Why a puzzle input couldn't be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AocError {
+    #[error("the input is missing {0}")]
+    Missing(&'static str),
+    #[error("malformed {text:?}, expected {expected}")]
+    Malformed {
+        text: String,
+        expected: &'static str,
+    },
+    #[error("{0}")]
+    Invalid(String),
+    #[error("couldn't read the input: {0}")]
+    Read(String),
+    #[error("line {line}: {source}")]
+    OnLine { line: usize, source: Box<AocError> },
+}
+
+impl AocError {
+    pub fn malformed(text: &str, expected: &'static str) -> Self {
+        AocError::Malformed {
+            text: text.to_string(),
+            expected,
+        }
+    }
+
+    /// The same error, saying it's on `line`, counting from 1.
+    pub fn on_line(self, line: usize) -> Self {
+        AocError::OnLine {
+            line,
+            source: Box::new(self),
+        }
+    }
+}
+
+impl From<AocError> for SolveError {
+    fn from(e: AocError) -> Self {
+        SolveError::failed(e)
+    }
+}
@@ -0,0 +1,111 @@
+//! The example inputs from the puzzle descriptions, with the answers the descriptions give for
+//! them, so the days' tests, the runner's `--example` and anything checking every day at once
+//! all use the same text.
+
+/// One of a day's examples, numbered from 1 in the order the puzzle description gives them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub n: u8,
+    pub input: &'static str,
+    /// The answer to each part the description gives for this example, if it gives one
+    pub answers: [Option<&'static str>; 2],
+}
+
+macro_rules! example {
+    ($day:literal, $n:literal, [$part1:expr, $part2:expr]) => {
+        Example {
+            day: $day,
+            n: $n,
+            input: include_str!(concat!(
+                "examples/day",
+                stringify!($day),
+                "-",
+                stringify!($n),
+                ".txt"
+            )),
+            answers: [$part1, $part2],
+        }
+    };
+}
+
+/// Every example, in order of day and then number.
+pub const EXAMPLES: &[Example] = &[
+    example!(1, 1, [Some("142"), None]),
+    example!(1, 2, [None, Some("281")]),
+    example!(2, 1, [Some("8"), Some("2286")]),
+    example!(3, 1, [Some("4361"), Some("467835")]),
+    example!(4, 1, [Some("13"), Some("30")]),
+    example!(5, 1, [Some("35"), Some("46")]),
+    example!(6, 1, [Some("288"), Some("71503")]),
+    example!(7, 1, [Some("6440"), Some("5905")]),
+    example!(8, 1, [Some("2"), None]),
+    example!(8, 2, [Some("6"), None]),
+    example!(8, 3, [None, Some("6")]),
+    example!(9, 1, [Some("114"), Some("2")]),
+    example!(10, 1, [Some("4"), None]),
+    example!(10, 2, [Some("8"), None]),
+    example!(10, 3, [None, Some("4")]),
+    example!(10, 4, [None, Some("8")]),
+    example!(11, 1, [Some("374"), Some("82000210")]),
+    example!(12, 1, [Some("21"), Some("525152")]),
+    example!(13, 1, [Some("405"), Some("400")]),
+    example!(14, 1, [Some("136"), Some("64")]),
+    example!(15, 1, [Some("1320"), Some("145")]),
+    example!(16, 1, [Some("46"), Some("51")]),
+    example!(17, 1, [Some("102"), Some("94")]),
+    example!(17, 2, [None, Some("71")]),
+    example!(18, 1, [Some("62"), Some("952408144115")]),
+    example!(19, 1, [Some("19114"), Some("167409079868000")]),
+    example!(20, 1, [Some("32000000"), None]),
+    example!(20, 2, [Some("11687500"), None]),
+    // The description only gives answers after other numbers of steps than the puzzle asks for
+    example!(21, 1, [None, None]),
+    example!(22, 1, [Some("5"), Some("7")]),
+    example!(23, 1, [Some("94"), Some("154")]),
+    // Part 1 asks about a test area that only makes sense for the real input
+    example!(24, 1, [None, Some("47")]),
+    example!(25, 1, [Some("54"), None]),
+];
+
+/// The example for `day` numbered `n`, if there is one.
+pub fn find(day: u8, n: u8) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|e| e.day == day && e.n == n)
+}
+
+/// The input of the example for `day` numbered `n`. Usable in a `const`, where a missing
+/// example stops the build.
+pub const fn get(day: u8, n: u8) -> &'static str {
+    let mut i = 0;
+    while i < EXAMPLES.len() {
+        if EXAMPLES[i].day == day && EXAMPLES[i].n == n {
+            return EXAMPLES[i].input;
+        }
+        i += 1;
+    }
+    panic!("there's no such example")
+}
+
+/// How many examples `day` has.
+pub fn count(day: u8) -> u8 {
+    EXAMPLES.iter().filter(|e| e.day == day).count() as u8
+}
+
+#[test]
+fn test_examples() {
+    assert!(get(9, 1).starts_with("0 3 6 9 12 15\n"));
+    assert_eq!(find(10, 4).unwrap().answers, [None, Some("8")]);
+    assert_eq!(find(10, 5), None);
+    assert_eq!(count(8), 3);
+    for (i, example) in EXAMPLES.iter().enumerate() {
+        let (day, n) = (example.day, example.n);
+        assert!(example.input.ends_with('\n'), "day {day} example {n}");
+        assert!((1..=25).contains(&day));
+        // Numbered from 1 with no gaps, in order
+        let previous = i.checked_sub(1).map(|i| (EXAMPLES[i].day, EXAMPLES[i].n));
+        assert!(
+            n == 1 && previous.is_none_or(|p| p.0 < day) || previous == Some((day, n - 1)),
+            "day {day} example {n}"
+        );
+    }
+}
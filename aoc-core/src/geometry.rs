@@ -0,0 +1,189 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A position on a grid, with x increasing to the east and y increasing to the south.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub struct Coord {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Coord {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The four orthogonally adjacent positions, in the order of `Dir::ALL`.
+    pub fn neighbors4(self) -> impl Iterator<Item = Coord> {
+        Dir::ALL.into_iter().map(move |dir| self + dir)
+    }
+
+    /// The eight positions touching this one, diagonally too, row by row.
+    pub fn neighbors8(self) -> impl Iterator<Item = Coord> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Coord::new(dx, dy)))
+            .filter(|&offset| offset != Coord::new(0, 0))
+            .map(move |offset| self + offset)
+    }
+
+    /// Steps from the origin moving only orthogonally.
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Coord) -> Coord {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Coord {
+        Coord::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: i64) -> Coord {
+        Coord::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Add<Dir> for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Dir) -> Coord {
+        self + rhs.offset()
+    }
+}
+
+impl AddAssign<Dir> for Coord {
+    fn add_assign(&mut self, rhs: Dir) {
+        *self = *self + rhs;
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+/// One of the four directions on a grid. North is towards y = 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::South, Dir::West, Dir::East];
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Dir::North => Dir::South,
+            Dir::South => Dir::North,
+            Dir::East => Dir::West,
+            Dir::West => Dir::East,
+        }
+    }
+
+    /// The direction after turning left while facing this way.
+    pub fn left(self) -> Self {
+        match self {
+            Dir::North => Dir::West,
+            Dir::West => Dir::South,
+            Dir::South => Dir::East,
+            Dir::East => Dir::North,
+        }
+    }
+
+    /// The direction after turning right while facing this way.
+    pub fn right(self) -> Self {
+        self.left().reverse()
+    }
+
+    /// One step this way.
+    pub fn offset(self) -> Coord {
+        match self {
+            Dir::North => Coord::new(0, -1),
+            Dir::South => Coord::new(0, 1),
+            Dir::East => Coord::new(1, 0),
+            Dir::West => Coord::new(-1, 0),
+        }
+    }
+}
+
+impl Mul<i64> for Dir {
+    type Output = Coord;
+
+    fn mul(self, rhs: i64) -> Coord {
+        self.offset() * rhs
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("expected a direction like N or U but got {0:?}")]
+pub struct ParseDirError(pub String);
+
+impl FromStr for Dir {
+    type Err = ParseDirError;
+
+    /// Accepts compass letters (N, S, E, W) as well as up, down, left and right (U, D, L, R).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "N" | "U" => Dir::North,
+            "S" | "D" => Dir::South,
+            "E" | "R" => Dir::East,
+            "W" | "L" => Dir::West,
+            _ => return Err(ParseDirError(s.to_string())),
+        })
+    }
+}
+
+#[test]
+fn test_dir() {
+    assert_eq!("U".parse(), Ok(Dir::North));
+    assert_eq!("W".parse(), Ok(Dir::West));
+    assert_eq!("L".parse(), Ok(Dir::West));
+    assert_eq!("x".parse::<Dir>(), Err(ParseDirError("x".to_string())));
+    for dir in Dir::ALL {
+        assert_eq!(dir.left().right(), dir);
+        assert_eq!(dir.left().left(), dir.reverse());
+        assert_eq!(dir.offset() + dir.reverse().offset(), Coord::default());
+    }
+    assert_eq!(Dir::North.right(), Dir::East);
+    assert_eq!(Coord::new(1, 2) + Dir::South * 3, Coord::new(1, 5));
+    assert_eq!(Coord::new(1, 2) * 2 - Coord::new(1, 1), Coord::new(1, 3));
+}
@@ -0,0 +1,217 @@
+use std::{borrow::Borrow, collections::VecDeque, hash::Hash, ops::Range};
+
+use crate::Interner;
+
+/// Index of a node in a [`Graph`], in the order the nodes were added.
+pub type NodeId = u32;
+
+/// A directed graph of nodes labelled with `N`s, like names from the input, stored as a list
+/// of each node's successors. An undirected graph has each edge added both ways.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    /// Each node's label, numbered with its id
+    labels: Interner<N>,
+    /// Each node's successors, in the order their edges were added
+    successors: Vec<Vec<NodeId>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            labels: Interner::default(),
+            successors: vec![],
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The node labelled `label`, added without any edges if it isn't in the graph yet.
+    ///
+    /// Panics if the graph already has as many nodes as a [`NodeId`] can number; see
+    /// [`Graph::try_add_node`].
+    pub fn add_node<Q>(&mut self, label: &Q) -> NodeId
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = N> + ?Sized,
+    {
+        self.try_add_node(label)
+            .expect("added more nodes than a NodeId can number")
+    }
+
+    /// The node labelled `label`, added without any edges if it isn't in the graph yet, or
+    /// None if it would need adding and the graph already has as many nodes as a [`NodeId`]
+    /// can number.
+    pub fn try_add_node<Q>(&mut self, label: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = N> + ?Sized,
+    {
+        let id = self.labels.try_intern(label)?;
+        if id as usize == self.successors.len() {
+            self.successors.push(vec![]);
+        }
+        Some(id)
+    }
+
+    /// Adds an edge from `from` to `to`, even if there's one already.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.successors[from as usize].push(to);
+    }
+
+    pub fn id<Q>(&self, label: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.labels.get(label)
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn label(&self, node: NodeId) -> &N {
+        self.labels.resolve(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn nodes(&self) -> Range<NodeId> {
+        0..self.len() as NodeId
+    }
+
+    /// Nodes with an edge from `node`, in the order the edges were added.
+    pub fn successors(&self, node: NodeId) -> &[NodeId] {
+        &self.successors[node as usize]
+    }
+
+    /// The node each node was first reached from in a breadth-first search from `start`,
+    /// which for `start` is itself, or None if it can't be reached. Following these back from
+    /// any node gives a shortest path to it.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<NodeId>> {
+        let mut came_from = vec![None; self.len()];
+        came_from[start as usize] = Some(start);
+        let mut to_examine = VecDeque::from([start]);
+        while let Some(here) = to_examine.pop_front() {
+            for &next in self.successors(here) {
+                if came_from[next as usize].is_none() {
+                    came_from[next as usize] = Some(here);
+                    to_examine.push_back(next);
+                }
+            }
+        }
+        came_from
+    }
+
+    /// Every node reachable from `start`, in the order a depth-first search first visits
+    /// them.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        self.dfs_unseen(start, &mut seen)
+    }
+
+    /// The nodes a depth-first search from `start` reaches that aren't already `seen`, marking
+    /// them seen.
+    fn dfs_unseen(&self, start: NodeId, seen: &mut [bool]) -> Vec<NodeId> {
+        let mut order = vec![];
+        let mut to_examine = vec![start];
+        while let Some(here) = to_examine.pop() {
+            if std::mem::replace(&mut seen[here as usize], true) {
+                continue;
+            }
+            order.push(here);
+            // Reversed so the first successor is the first one examined
+            to_examine.extend(self.successors(here).iter().rev());
+        }
+        order
+    }
+
+    /// The groups of nodes connected to each other, biggest first, each in order. Only makes
+    /// sense for undirected graphs.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut seen = vec![false; self.len()];
+        let mut components = vec![];
+        for start in self.nodes() {
+            if !seen[start as usize] {
+                let mut component = self.dfs_unseen(start, &mut seen);
+                component.sort();
+                components.push(component);
+            }
+        }
+        components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+        components
+    }
+
+    /// Every node, ordered so that each edge goes from an earlier node to a later one, or None
+    /// if there's a cycle so that no order works.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut in_degrees = vec![0; self.len()];
+        for node in self.nodes() {
+            for &next in self.successors(node) {
+                in_degrees[next as usize] += 1;
+            }
+        }
+        let mut ready: VecDeque<NodeId> = self
+            .nodes()
+            .filter(|&node| in_degrees[node as usize] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(here) = ready.pop_front() {
+            order.push(here);
+            for &next in self.successors(here) {
+                in_degrees[next as usize] -= 1;
+                if in_degrees[next as usize] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+}
+
+#[test]
+fn test_graph() {
+    // a -> b -> d, a -> c -> d, and e on its own
+    let mut graph: Graph<String> = Graph::new();
+    let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|name| graph.add_node(name));
+    assert_eq!(graph.add_node("c"), c);
+    assert_eq!(graph.try_add_node("c"), Some(c));
+    for (from, to) in [(a, b), (a, c), (b, d), (c, d)] {
+        graph.add_edge(from, to);
+    }
+    assert_eq!(graph.len(), 5);
+    assert_eq!(graph.id("d"), Some(d));
+    assert_eq!(graph.id("f"), None);
+    assert_eq!(graph.label(e), "e");
+    assert_eq!(graph.successors(a), [b, c]);
+
+    assert_eq!(graph.bfs(a), [Some(a), Some(a), Some(a), Some(b), None]);
+    assert_eq!(graph.dfs(a), [a, b, d, c]);
+    assert_eq!(graph.dfs(c), [c, d]);
+    assert_eq!(graph.topological_sort(), Some(vec![a, e, b, c, d]));
+
+    graph.add_edge(d, a);
+    assert_eq!(graph.topological_sort(), None);
+}
+
+#[test]
+fn test_connected_components() {
+    let mut graph: Graph<char> = Graph::new();
+    for (from, to) in [('a', 'b'), ('c', 'd'), ('d', 'e'), ('f', 'f')] {
+        let (from, to) = (graph.add_node(&from), graph.add_node(&to));
+        graph.add_edge(from, to);
+        graph.add_edge(to, from);
+    }
+    assert_eq!(
+        graph.connected_components(),
+        [vec![2, 3, 4], vec![0, 1], vec![5]]
+    );
+}
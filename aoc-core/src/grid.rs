@@ -0,0 +1,387 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Coord;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseGridError<E> {
+    #[error("unexpected {ch:?} at {at}")]
+    InvalidCell { at: Coord, ch: char, error: E },
+    #[error("row {row} is {len} cells wide but the first row is {width}")]
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+/// A rectangular grid of cells, stored row by row and indexed by `Coord` or `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "SavedGrid<T>")]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// A grid as it's deserialized, before checking there are the right number of cells.
+#[derive(Deserialize)]
+struct SavedGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> TryFrom<SavedGrid<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(saved: SavedGrid<T>) -> Result<Self, Self::Error> {
+        let SavedGrid {
+            width,
+            height,
+            cells,
+        } = saved;
+        if cells.len() != width * height {
+            return Err(format!(
+                "{} cells don't fill a {width}x{height} grid",
+                cells.len()
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coord::new(x as i64, y as i64)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character, one row per line. Trailing blank lines are ignored.
+    pub fn parse<E>(
+        s: &str,
+        mut parse_cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseGridError<E>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (y, line) in s.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let row_start = cells.len();
+            for (x, ch) in line.chars().enumerate() {
+                let at = Coord::new(x as i64, y as i64);
+                cells.push(parse_cell(ch).map_err(|error| ParseGridError::InvalidCell {
+                    at,
+                    ch,
+                    error,
+                })?);
+            }
+            let len = cells.len() - row_start;
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(ParseGridError::Ragged { row: y, len, width })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.offset(coord).is_some()
+    }
+
+    fn offset(&self, coord: Coord) -> Option<usize> {
+        let (x, y) = (
+            usize::try_from(coord.x).ok()?,
+            usize::try_from(coord.y).ok()?,
+        );
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.offset(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.offset(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// The cell at `coord` with the grid repeated forever in every direction, as the
+    /// [`Tiled`](crate::Tiled) plane made of copies of it would have it.
+    pub fn get_wrapped(&self, coord: Coord) -> &T {
+        &self[(
+            coord.x.rem_euclid(self.width as i64) as usize,
+            coord.y.rem_euclid(self.height as i64) as usize,
+        )]
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord::new(x as i64, y as i64)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    /// Position of the first cell, row by row, that matches `pred`.
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.cells().find(|(_, cell)| pred(cell)).map(|(at, _)| at)
+    }
+
+    /// The orthogonal neighbors of `coord` that are inside the grid.
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord.neighbors4().filter(|&n| self.contains(n))
+    }
+
+    /// The neighbors of `coord`, diagonal ones too, that are inside the grid.
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord.neighbors8().filter(|&n| self.contains(n))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid flipped over its main diagonal, so its rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |c| {
+            self[(c.y as usize, c.x as usize)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn clockwise, so its west side faces north.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |c| {
+            self[(c.y as usize, self.height - 1 - c.x as usize)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn anticlockwise, so its east side faces north.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |c| {
+            self[(self.width - 1 - c.y as usize, c.x as usize)].clone()
+        })
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |c| {
+            self[(self.width - 1 - c.x as usize, c.y as usize)].clone()
+        })
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |c| {
+            self[(c.x as usize, self.height - 1 - c.y as usize)].clone()
+        })
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord} is outside the grid"))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "x {x} is outside the grid");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "x {x} is outside the grid");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseGridError<T::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, T::try_from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parse() {
+    let grid: Grid<char> = "#..\n.#.\n".parse().unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Coord::new(1, 1)], '#');
+    assert_eq!(grid[(2, 0)], '.');
+    assert_eq!(grid.get(Coord::new(3, 0)), None);
+    assert_eq!(grid.get(Coord::new(0, -1)), None);
+    assert_eq!(*grid.get_wrapped(Coord::new(4, 3)), '#');
+    assert_eq!(*grid.get_wrapped(Coord::new(-3, -2)), '#');
+    assert_eq!(grid.position(|&ch| ch == '#'), Some(Coord::new(0, 0)));
+    assert_eq!(grid.to_string(), "#..\n.#.\n");
+
+    assert_eq!(
+        "#..\n.#".parse::<Grid<char>>(),
+        Err(ParseGridError::Ragged {
+            row: 1,
+            len: 2,
+            width: 3
+        })
+    );
+    let digits = Grid::parse("12\n3x", |ch| ch.to_digit(10).ok_or(()));
+    assert_eq!(
+        digits,
+        Err(ParseGridError::InvalidCell {
+            at: Coord::new(1, 1),
+            ch: 'x',
+            error: ()
+        })
+    );
+}
+
+#[test]
+fn test_neighbors() {
+    let grid = Grid::from_fn(3, 2, |c| c.x + 10 * c.y);
+    assert_eq!(grid[(2, 1)], 12);
+    assert_eq!(
+        grid.neighbors4(Coord::new(0, 0)).collect::<Vec<_>>(),
+        [Coord::new(0, 1), Coord::new(1, 0)]
+    );
+    assert_eq!(grid.neighbors4(Coord::new(1, 1)).count(), 3);
+    assert_eq!(
+        grid.neighbors8(Coord::new(0, 0)).collect::<Vec<_>>(),
+        [Coord::new(1, 0), Coord::new(0, 1), Coord::new(1, 1)]
+    );
+    assert_eq!(grid.neighbors8(Coord::new(1, 1)).count(), 5);
+    assert_eq!(grid.map(|v| v % 10).row(1), [0, 1, 2]);
+}
+
+#[test]
+fn test_transforms() {
+    let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+    assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+    assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+    assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    assert_eq!(grid.transpose().transpose(), grid);
+    assert_eq!(
+        grid.rotate_cw().rotate_cw(),
+        grid.flip_horizontal().flip_vertical()
+    );
+}
+
+#[test]
+fn test_serde() {
+    let grid: Grid<char> = "#..\n.#.\n".parse().unwrap();
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(
+        json,
+        r##"{"width":3,"height":2,"cells":["#",".",".",".","#","."]}"##
+    );
+    assert_eq!(serde_json::from_str::<Grid<char>>(&json).unwrap(), grid);
+    let error =
+        serde_json::from_str::<Grid<u8>>(r#"{"width":2,"height":2,"cells":[1,2,3]}"#).unwrap_err();
+    assert_eq!(error.to_string(), "3 cells don't fill a 2x2 grid");
+}
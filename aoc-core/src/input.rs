@@ -0,0 +1,208 @@
+use std::{
+    convert::Infallible,
+    ffi::OsString,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use clap::{CommandFactory, FromArgMatches};
+
+use crate::init_logging;
+
+/// Where to read a puzzle input from: a file, or stdin when given as `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(path.into()),
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// What every day's binary can be told on the command line, for a day with other arguments of
+/// its own to `#[command(flatten)]` into them.
+#[derive(Debug, PartialEq, Eq, clap::Args)]
+#[command(about = None, long_about = None)]
+pub struct CommonArgs {
+    /// Puzzle input to read instead of `input.txt`, or - for stdin
+    #[arg(long, value_name = "FILE", default_value = "input.txt")]
+    pub input: InputSource,
+
+    /// Log what's going on to stderr; repeat for more detail
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+/// The arguments of a binary that takes nothing but [`CommonArgs`].
+#[derive(clap::Parser)]
+#[command(about = None, long_about = None)]
+struct OnlyCommonArgs {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+/// Parses `args`, starting with the program name, as [`CommonArgs`] with the input defaulting
+/// to the file `default`.
+fn parse_args<I, T>(args: I, default: &'static str) -> Result<CommonArgs, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let matches = OnlyCommonArgs::command()
+        .mut_arg("input", |input| {
+            input.default_value(default).help(format!(
+                "Puzzle input to read instead of `{default}`, or - for stdin"
+            ))
+        })
+        .try_get_matches_from(args)?;
+    Ok(OnlyCommonArgs::from_arg_matches(&matches)?.common)
+}
+
+impl InputSource {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::File(path) => std::fs::read_to_string(path),
+        }
+    }
+
+    /// Opens the input to be read a bit at a time, for inputs too big to read all at once.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            InputSource::Stdin => Box::new(io::stdin().lock()),
+            InputSource::File(path) => Box::new(BufReader::new(File::open(path)?)),
+        })
+    }
+
+    /// Reads the input, exiting with an error if it can't be read.
+    pub fn read_or_exit(&self) -> String {
+        self.read().unwrap_or_else(|e| {
+            eprintln!("error: couldn't read {self}: {e}");
+            std::process::exit(1);
+        })
+    }
+}
+
+/// The input given with `--input` on the command line, or the file `default` without one,
+/// after setting up logging as verbose as the `-v`s given ask for. Exits with usage if there's
+/// anything else, or with help if asked for it.
+fn input_from_args(default: &'static str) -> InputSource {
+    let args = parse_args(std::env::args_os(), default).unwrap_or_else(|e| e.exit());
+    init_logging(args.verbose);
+    args.input
+}
+
+/// Reads the input given with `--input` on the command line, or the file `default` without one,
+/// after setting up logging as verbose as the `-v`s given ask for. For binaries that take no
+/// other arguments; exits with usage if there's anything else.
+pub fn read_input(default: &'static str) -> String {
+    input_from_args(default).read_or_exit()
+}
+
+/// Like [`read_input`], but opens the input to be read a line at a time instead.
+pub fn open_input(default: &'static str) -> Box<dyn BufRead> {
+    let input = input_from_args(default);
+    input.open().unwrap_or_else(|e| {
+        eprintln!("error: couldn't read {input}: {e}");
+        std::process::exit(1);
+    })
+}
+
+#[test]
+fn test_parse_args() {
+    use clap::error::ErrorKind;
+
+    let parse = |args: &[&str]| parse_args(["day"].iter().chain(args), "input1.txt");
+    let file = |path: &str, verbose| CommonArgs {
+        input: InputSource::File(path.into()),
+        verbose,
+    };
+    assert_eq!(parse(&[]).unwrap(), file("input1.txt", 0));
+    assert_eq!(
+        parse(&["--input", "example.txt"]).unwrap(),
+        file("example.txt", 0)
+    );
+    assert_eq!(
+        parse(&["--input=../day3/input.txt"]).unwrap(),
+        file("../day3/input.txt", 0)
+    );
+    assert_eq!(
+        parse(&["--input", "-"]).unwrap(),
+        CommonArgs {
+            input: InputSource::Stdin,
+            verbose: 0
+        }
+    );
+    assert_eq!(parse(&["-vv", "--input", "x"]).unwrap(), file("x", 2));
+    assert_eq!(parse(&["-v", "--verbose"]).unwrap(), file("input1.txt", 2));
+    let many_vs = format!("-{}", "v".repeat(300));
+    assert_eq!(parse(&[&many_vs]).unwrap(), file("input1.txt", u8::MAX));
+    assert!(parse(&["--input"]).is_err());
+    assert!(parse(&["--inputs=x"]).is_err());
+    assert!(parse(&["--input", "a", "--input", "b"]).is_err());
+    assert!(parse(&["input.txt"]).is_err());
+    assert!(parse(&["-vx"]).is_err());
+
+    let help = parse(&["--help"]).unwrap_err();
+    assert_eq!(help.kind(), ErrorKind::DisplayHelp);
+    assert!(help
+        .to_string()
+        .contains("Puzzle input to read instead of `input1.txt`"));
+}
+
+#[test]
+fn test_common_args() {
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Args {
+        #[command(flatten)]
+        common: CommonArgs,
+    }
+
+    let parse = |args: &[&str]| {
+        Args::try_parse_from(["day"].iter().chain(args))
+            .map(|args| args.common)
+            .ok()
+    };
+    let file = |path: &str, verbose| {
+        Some(CommonArgs {
+            input: InputSource::File(path.into()),
+            verbose,
+        })
+    };
+    assert_eq!(parse(&[]), file("input.txt", 0));
+    assert_eq!(parse(&["--input=x", "-vv"]), file("x", 2));
+    assert_eq!(
+        parse(&["--input", "-", "--verbose"]),
+        Some(CommonArgs {
+            input: InputSource::Stdin,
+            verbose: 1
+        })
+    );
+    assert_eq!(parse(&["--input"]), None);
+}
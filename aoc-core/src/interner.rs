@@ -0,0 +1,95 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+/// Numbers things like names from the input in the order they're first seen, so that they
+/// can be kept and compared as `u32`s and only looked up again to show them.
+#[derive(Debug, Clone)]
+pub struct Interner<N> {
+    values: Vec<N>,
+    ids: HashMap<N, u32>,
+}
+
+impl<N> Default for Interner<N> {
+    fn default() -> Self {
+        Self {
+            values: vec![],
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Interner<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number for `value`, giving it the next one if it doesn't have one yet.
+    ///
+    /// Panics if every `u32` is already taken; see [`Interner::try_intern`].
+    pub fn intern<Q>(&mut self, value: &Q) -> u32
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = N> + ?Sized,
+    {
+        self.try_intern(value)
+            .expect("interned more values than there are u32s")
+    }
+
+    /// The number for `value`, giving it the next one if it doesn't have one yet, or None if
+    /// it needs a new one and every `u32` is already taken.
+    pub fn try_intern<Q>(&mut self, value: &Q) -> Option<u32>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = N> + ?Sized,
+    {
+        if let Some(id) = self.get(value) {
+            return Some(id);
+        }
+        let id = u32::try_from(self.values.len()).ok()?;
+        self.values.push(value.to_owned());
+        self.ids.insert(value.to_owned(), id);
+        Some(id)
+    }
+
+    /// The number for `value`, if it has one.
+    pub fn get<Q>(&self, value: &Q) -> Option<u32>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(value).copied()
+    }
+}
+
+impl<N> Interner<N> {
+    /// What was given the number `id`.
+    pub fn resolve(&self, id: u32) -> &N {
+        &self.values[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Everything interned, in order of number.
+    pub fn values(&self) -> &[N] {
+        &self.values
+    }
+}
+
+#[test]
+fn test_interner() {
+    let mut names: Interner<String> = Interner::new();
+    assert_eq!(names.intern("broadcaster"), 0);
+    assert_eq!(names.intern("rx"), 1);
+    assert_eq!(names.intern("broadcaster"), 0);
+    assert_eq!(names.try_intern("kh"), Some(2));
+    assert_eq!(names.get("rx"), Some(1));
+    assert_eq!(names.get("lf"), None);
+    assert_eq!(names.resolve(1), "rx");
+    assert_eq!(names.len(), 3);
+    assert_eq!(names.values(), ["broadcaster", "rx", "kh"]);
+}
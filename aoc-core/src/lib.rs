@@ -0,0 +1,49 @@
+//! Pieces shared between the days' solutions.
+
+mod animation;
+mod bit_grid;
+mod cancel;
+pub mod cycle;
+mod dot;
+mod dump;
+mod error;
+pub mod examples;
+mod geometry;
+mod graph;
+mod grid;
+mod input;
+mod interner;
+mod logging;
+mod memo;
+pub mod number;
+mod parse;
+pub mod pathfinding;
+pub mod polygon;
+mod probe;
+pub mod snapshot;
+mod solver;
+mod svg;
+mod tiled;
+mod union_find;
+mod visualize;
+
+pub use animation::GifRecorder;
+pub use bit_grid::BitGrid;
+pub use cancel::Cancel;
+pub use dot::Dot;
+pub use dump::StateDump;
+pub use error::AocError;
+pub use geometry::{Coord, Dir, ParseDirError};
+pub use graph::{Graph, NodeId};
+pub use grid::{Grid, ParseGridError};
+pub use input::{open_input, read_input, CommonArgs, InputSource};
+pub use interner::Interner;
+pub use logging::{init_logging, init_quiet_logging};
+pub use memo::Memo;
+pub use parse::{blocks, for_each_line, parse_grid, parse_lines, parse_numbers};
+pub use probe::{Probe, ProbeArgs};
+pub use solver::{Params, SolveError, Solver};
+pub use svg::Svg;
+pub use tiled::{Tile, Tiled};
+pub use union_find::UnionFind;
+pub use visualize::{Recorder, Terminal, Visualize};
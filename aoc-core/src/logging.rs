@@ -0,0 +1,24 @@
+use tracing_subscriber::filter::LevelFilter;
+
+/// Sends log events to stderr: only warnings by default, debug events with one `-v` and
+/// everything with more.
+pub fn init_logging(verbose: u8) {
+    init(match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    });
+}
+
+/// Drops every log event, even warnings, so that the only output is what's printed on purpose.
+pub fn init_quiet_logging() {
+    init(LevelFilter::OFF);
+}
+
+fn init(level: LevelFilter) {
+    // Someone else setting up logging first is fine.
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .try_init();
+}
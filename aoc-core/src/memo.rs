@@ -0,0 +1,85 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Remembers answers worked out for inputs of type `I`, filed under a key `K` taken from each
+/// input, so that recursive solutions only work out each answer once. The key can be the input
+/// itself, or something smaller that's still enough to tell apart every input that comes up.
+pub struct Memo<I: ?Sized, K, V> {
+    key: fn(&I) -> K,
+    answers: HashMap<K, V>,
+}
+
+impl<I: Clone + Hash + Eq, V: Clone> Memo<I, I, V> {
+    /// A memo keyed by a copy of each whole input.
+    pub fn new() -> Self {
+        Self::keyed_by(I::clone)
+    }
+}
+
+impl<I: Clone + Hash + Eq, V: Clone> Default for Memo<I, I, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: ?Sized, K: Hash + Eq, V: Clone> Memo<I, K, V> {
+    /// A memo keyed by what `key` takes from each input.
+    pub fn keyed_by(key: fn(&I) -> K) -> Self {
+        Self {
+            key,
+            answers: HashMap::new(),
+        }
+    }
+
+    /// The answer for `input`, worked out with `compute` only if there isn't one for its key
+    /// yet. `compute` is given the memo so it can look up smaller inputs through it too.
+    pub fn get(&mut self, input: &I, compute: impl FnOnce(&mut Self) -> V) -> V {
+        let key = (self.key)(input);
+        if let Some(answer) = self.answers.get(&key) {
+            return answer.clone();
+        }
+        let answer = compute(self);
+        self.answers.insert(key, answer.clone());
+        answer
+    }
+
+    /// How many answers have been remembered.
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.answers.clear();
+    }
+}
+
+#[test]
+fn test_recursive() {
+    fn fib(n: u64, memo: &mut Memo<u64, u64, u64>) -> u64 {
+        memo.get(&n, |memo| match n {
+            0 | 1 => n,
+            _ => fib(n - 1, memo) + fib(n - 2, memo),
+        })
+    }
+    let mut memo = Memo::new();
+    assert_eq!(fib(90, &mut memo), 2_880_067_194_370_816_120);
+    assert_eq!(memo.len(), 91);
+}
+
+#[test]
+fn test_keyed_by() {
+    let mut memo: Memo<str, usize, usize> = Memo::keyed_by(str::len);
+    let mut computed = 0;
+    for word in ["one", "two", "three", "six"] {
+        memo.get(word, |_| {
+            computed += 1;
+            word.len()
+        });
+    }
+    assert_eq!((computed, memo.len()), (2, 2));
+    memo.clear();
+    assert!(memo.is_empty());
+}
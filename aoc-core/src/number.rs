@@ -0,0 +1,124 @@
+//! Number theory for puzzles about things that repeat with different periods: greatest common
+//! divisors, least common multiples and solving simultaneous congruences.
+
+use std::ops::{Div, Mul, Rem};
+
+/// The greatest common divisor of `a` and `b`, which is `a` if `b` is 0.
+pub fn gcd<T: Copy + Eq + Rem<Output = T> + From<u8>>(mut a: T, mut b: T) -> T {
+    while b != T::from(0) {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, or 0 if either is 0.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + Eq + Rem<Output = T> + Div<Output = T> + Mul<Output = T> + From<u8>,
+{
+    if a == T::from(0) || b == T::from(0) {
+        return T::from(0);
+    }
+    a / gcd(a, b) * b
+}
+
+/// The greatest common divisor `g` of `a` and `b`, which is never negative, along with `x` and
+/// `y` such that `a * x + b * y == g`, by the extended Euclidean algorithm.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a * b % m` without overflowing, for `a` and `b` below `m`, which must be below 2^127.
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (mut a, mut b, mut product) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            product = (product + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    product
+}
+
+/// The smallest `x` that leaves each remainder when divided by its modulus, given as
+/// `(remainder, modulus)` pairs, along with the least common multiple of the moduli, which
+/// every other such `x` differs from it by a multiple of. The moduli needn't be coprime, but
+/// there's no answer if two of them can't agree, or if the least common multiple doesn't fit
+/// below 2^127. Solving nothing at all gives 0 modulo 1.
+pub fn crt(congruences: impl IntoIterator<Item = (u128, u128)>) -> Option<(u128, u128)> {
+    let limit: u128 = 1 << 127;
+    let (mut x, mut modulus): (u128, u128) = (0, 1);
+    for (remainder, m) in congruences {
+        assert!(m > 0, "the moduli have to be positive");
+        if m >= limit {
+            return None;
+        }
+        let remainder = remainder % m;
+        let (g, inverse, _) = extended_gcd(modulus as i128, m as i128);
+        let g = g as u128;
+        // x + modulus * k = remainder (mod m) has a solution for k if g divides the difference
+        let difference = (remainder + m - x % m) % m;
+        if difference % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let inverse = inverse.rem_euclid(step as i128) as u128;
+        let k = mul_mod(difference / g, inverse, step);
+        let combined = modulus.checked_mul(step).filter(|&c| c < limit)?;
+        x = (x + modulus * k) % combined;
+        modulus = combined;
+    }
+    Some((x, modulus))
+}
+
+#[test]
+fn test_gcd_lcm() {
+    assert_eq!(gcd(12u64, 18), 6);
+    assert_eq!(gcd(0u32, 7), 7);
+    assert_eq!(gcd(7usize, 0), 7);
+    assert_eq!(lcm(4u64, 6), 12);
+    assert_eq!(lcm(0u8, 6), 0);
+    assert_eq!([2usize, 3, 4, 5].into_iter().fold(1, lcm), 60);
+    assert_eq!(lcm(1u128 << 100, 3), 3 << 100);
+}
+
+#[test]
+fn test_extended_gcd() {
+    for (a, b) in [(240, 46), (46, 240), (-12, 18), (7, 0), (0, 0), (17, 5)] {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(a * x + b * y, g, "{a} {b}");
+        assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+    }
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    // Not coprime, but agreeing, and then not
+    assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+    assert_eq!(crt([(0, 4), (1, 6)]), None);
+    assert_eq!(crt([]), Some((0, 1)));
+    assert_eq!(crt([(10, 3)]), Some((1, 3)));
+    // Big enough that the steps overflow if they're multiplied directly
+    let (p, q) = ((1 << 31) - 1, (1 << 89) - 1);
+    let (x, m) = crt([(5, p), (q - 1, q)]).unwrap();
+    assert_eq!((x % p, x % q, m), (5, q - 1, p * q));
+    assert_eq!(crt([(0, 1 << 100), (0, 3u128.pow(30))]), None);
+}
@@ -0,0 +1,163 @@
+use std::{io::BufRead, str::FromStr};
+
+use crate::{AocError, Grid, ParseGridError};
+
+/// Parses each line of `input` with `parse`, saying which line any error was on.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, AocError>,
+) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Calls `f` with each line read from `reader`, without its line ending, reusing one buffer so
+/// that memory use doesn't grow with the input. Errors from `f` say which line they were on.
+pub fn for_each_line(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), AocError>,
+) -> Result<(), AocError> {
+    let mut line = String::new();
+    for number in 1.. {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|e| AocError::Read(e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        let text = line.strip_suffix('\n').unwrap_or(&line);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        f(text).map_err(|e| e.on_line(number))?;
+    }
+    Ok(())
+}
+
+/// Parses a list of numbers separated by any amount of whitespace. Anything else in the list
+/// is an error, rather than skipped.
+pub fn parse_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, AocError> {
+    text.split_whitespace()
+        .map(|n| n.parse().map_err(|_| AocError::malformed(n, "a number")))
+        .collect()
+}
+
+/// Splits `input` into the blocks of lines between blank lines, each without its trailing
+/// newline. Any number of blank lines can separate blocks, or come before or after them.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    // Where the block being read started, if there is one yet
+    let mut start = None;
+    let mut at = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(input[start..at].trim_end_matches(['\r', '\n']));
+            }
+        } else if start.is_none() {
+            start = Some(at);
+        }
+        at += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(input[start..].trim_end_matches(['\r', '\n']));
+    }
+    blocks
+}
+
+/// Parses a grid of one character per cell with `parse_cell`, where any character it gives
+/// None for is reported as not being `expected`.
+pub fn parse_grid<T>(
+    input: &str,
+    expected: &'static str,
+    mut parse_cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, AocError> {
+    Grid::parse(input, |ch| parse_cell(ch).ok_or(())).map_err(|e| match e {
+        ParseGridError::InvalidCell { at, ch, .. } => {
+            AocError::malformed(&ch.to_string(), expected).on_line(at.y as usize + 1)
+        }
+        ParseGridError::Ragged { row, len, width } => AocError::Invalid(format!(
+            "the line is {len} characters long but the first is {width}"
+        ))
+        .on_line(row + 1),
+    })
+}
+
+#[test]
+fn test_parse_lines() {
+    let parse = |line: &str| {
+        line.parse::<u8>()
+            .map_err(|_| AocError::malformed(line, "a byte"))
+    };
+    assert_eq!(parse_lines("1\n2\n3", parse), Ok(vec![1, 2, 3]));
+    let err = parse_lines("1\n256", parse).unwrap_err();
+    assert_eq!(err, AocError::malformed("256", "a byte").on_line(2));
+    assert_eq!(
+        err.to_string(),
+        "line 2: malformed \"256\", expected a byte"
+    );
+}
+
+#[test]
+fn test_for_each_line() {
+    let mut lines = vec![];
+    let read = for_each_line("a\r\n\nb".as_bytes(), |line| {
+        lines.push(line.to_string());
+        Ok(())
+    });
+    assert_eq!(read, Ok(()));
+    assert_eq!(lines, ["a", "", "b"]);
+
+    let err = for_each_line("1\n2\nx\n".as_bytes(), |line| {
+        parse_numbers::<u8>(line).map(|_| ())
+    });
+    assert_eq!(err, Err(AocError::malformed("x", "a number").on_line(3)));
+
+    let err = for_each_line(&[b'a', 0xff, b'\n'][..], |_| Ok(())).unwrap_err();
+    assert!(matches!(err, AocError::Read(_)));
+}
+
+#[test]
+fn test_parse_numbers() {
+    assert_eq!(parse_numbers(" 1  -2\t3 "), Ok(vec![1, -2, 3]));
+    assert_eq!(parse_numbers::<u8>(""), Ok(vec![]));
+    assert_eq!(
+        parse_numbers::<u8>("1 2 x"),
+        Err(AocError::malformed("x", "a number"))
+    );
+    assert_eq!(
+        parse_numbers::<u8>("1 -2"),
+        Err(AocError::malformed("-2", "a number"))
+    );
+}
+
+#[test]
+fn test_blocks() {
+    assert_eq!(blocks("a\nb\n\nc\n"), ["a\nb", "c"]);
+    assert_eq!(blocks("\n\na\r\n \r\n\r\nb\r\nc"), ["a", "b\r\nc"]);
+    assert_eq!(blocks("\n\n"), [""; 0]);
+}
+
+#[test]
+fn test_parse_grid() {
+    let parse = |input| {
+        parse_grid(input, "# or .", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    };
+    let grid = parse("#.\n.#\n").unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    assert!(grid[(1, 1)]);
+    assert_eq!(
+        parse("#.\n.x"),
+        Err(AocError::malformed("x", "# or .").on_line(2))
+    );
+    assert_eq!(
+        parse("#.\n.").unwrap_err().to_string(),
+        "line 2: the line is 1 characters long but the first is 2"
+    );
+}
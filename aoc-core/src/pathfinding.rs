@@ -0,0 +1,199 @@
+//! Searches over any kind of state, given a closure that lists the states reachable in one move
+//! from each, and for the weighted searches what each move costs.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Fewest moves from `start` to every state reachable from it.
+pub fn bfs<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, distance)) = queue.pop_front() {
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// A heap entry ordered only by its priority, lowest first, so states don't need to be `Ord`.
+struct Queued<C, S> {
+    priority: C,
+    state: S,
+}
+
+impl<C: Ord, S> Ord for Queued<C, S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<C: Ord, S> PartialOrd for Queued<C, S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord, S> PartialEq for Queued<C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<C: Ord, S> Eq for Queued<C, S> {}
+
+/// Lowest total cost from `start` to every state reachable from it, where `successors` gives
+/// each state's next states along with the cost of moving to them. Costs can't be negative.
+pub fn dijkstra<S, C, I>(start: S, successors: impl FnMut(&S) -> I) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::new();
+    search(start, successors, |_| C::default(), |_| false, &mut costs);
+    costs
+        .into_iter()
+        .map(|(state, (cost, _))| (state, cost))
+        .collect()
+}
+
+/// The cheapest way from `start` to a state `is_goal` accepts, as the states along the way
+/// (`start` and the goal included) and the total cost, or None if there's no way there.
+/// `heuristic` guesses the cost from a state to the nearest goal, and the path is only
+/// guaranteed cheapest if it never guesses too high; always guessing 0 is plain Dijkstra.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::new();
+    let goal = search(start, successors, heuristic, is_goal, &mut costs)?;
+    let cost = costs[&goal].0;
+    let mut path = vec![goal];
+    while let Some(previous) = &costs[path.last().unwrap()].1 {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    Some((path, cost))
+}
+
+/// Best-first search filling in `costs` with the cheapest cost found to each state and the
+/// state it was reached from, until a goal is taken off the queue. Returns that goal.
+fn search<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+    costs: &mut HashMap<S, (C, Option<S>)>,
+) -> Option<S>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        state: start.clone(),
+    }]);
+    costs.insert(start, (C::default(), None));
+    while let Some(Queued { priority, state }) = queue.pop() {
+        let cost = costs[&state].0;
+        if priority > cost + heuristic(&state) {
+            // A cheaper way here was found after this one was queued
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(state);
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            match costs.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((next_cost, Some(state.clone())));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((next_cost, Some(state.clone())));
+                }
+            }
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                state: next,
+            });
+        }
+    }
+    None
+}
+
+#[test]
+fn test_bfs() {
+    // Steps of +1 or *2 up to 20
+    let distances = bfs(1, |&n| [n + 1, n * 2].into_iter().filter(|&n| n <= 20));
+    assert_eq!(distances[&1], 0);
+    assert_eq!(distances[&16], 4);
+    assert_eq!(distances[&17], 5);
+    assert_eq!(distances.len(), 20);
+}
+
+#[test]
+fn test_weighted() {
+    //   a --1-- b --1-- c
+    //    \             /
+    //     -----5------
+    let edges = |node: &char| match node {
+        'a' => vec![('b', 1), ('c', 5)],
+        'b' => vec![('c', 1)],
+        _ => vec![],
+    };
+    let costs = dijkstra('a', edges);
+    assert_eq!(costs, HashMap::from([('a', 0), ('b', 1), ('c', 2)]));
+    assert_eq!(
+        astar('a', edges, |_| 0, |&node| node == 'c'),
+        Some((vec!['a', 'b', 'c'], 2))
+    );
+    assert_eq!(
+        astar('a', edges, |_| 0, |&node| node == 'a'),
+        Some((vec!['a'], 0))
+    );
+    assert_eq!(astar('b', edges, |_| 0, |&node| node == 'a'), None);
+}
+
+#[test]
+fn test_astar_on_grid() {
+    use crate::{Coord, Grid};
+
+    let grid: Grid<char> = "..#.\n..#.\n....\n".parse().unwrap();
+    let end = Coord::new(3, 0);
+    let (path, steps) = astar(
+        Coord::new(0, 0),
+        |&c| {
+            grid.neighbors4(c)
+                .filter(|&n| grid[n] == '.')
+                .map(|n| (n, 1))
+        },
+        |&c| (end - c).manhattan(),
+        |&c| c == end,
+    )
+    .unwrap();
+    assert_eq!(steps, 7);
+    assert_eq!(path.len(), 8);
+    assert!(path.windows(2).all(|w| (w[1] - w[0]).manhattan() == 1));
+}
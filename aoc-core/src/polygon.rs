@@ -0,0 +1,108 @@
+//! Measurements of polygons on integer coordinates, given as their corners in order around
+//! them with the last joined back up to the first. Corners partway along a straight edge are
+//! fine, so a loop through every tile it passes is a polygon too, but edges can't cross.
+
+use crate::{number::gcd, Coord};
+
+/// The edges between each corner and the next, including the last back to the first.
+fn edges(corners: &[Coord]) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+    corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Twice the area inside the polygon, by the shoelace formula. Doubled so that it's always a
+/// whole number.
+pub fn double_area(corners: &[Coord]) -> i64 {
+    edges(corners)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .abs()
+}
+
+/// How many integer points the edges pass through. For a polygon with only horizontal and
+/// vertical edges, this is also the length around it.
+pub fn perimeter(corners: &[Coord]) -> i64 {
+    edges(corners)
+        .map(|(a, b)| gcd((b.x - a.x).unsigned_abs(), (b.y - a.y).unsigned_abs()) as i64)
+        .sum()
+}
+
+/// How many integer points are strictly inside the polygon, by Pick's theorem.
+pub fn interior_points(corners: &[Coord]) -> i64 {
+    (double_area(corners) - perimeter(corners) + 2) / 2
+}
+
+/// Whether `point` is on one of the polygon's edges.
+pub fn on_boundary(corners: &[Coord], point: Coord) -> bool {
+    edges(corners).any(|(a, b)| {
+        let (ab, ap) = (b - a, point - a);
+        ab.x * ap.y == ab.y * ap.x
+            && a.x.min(b.x) <= point.x
+            && point.x <= a.x.max(b.x)
+            && a.y.min(b.y) <= point.y
+            && point.y <= a.y.max(b.y)
+    })
+}
+
+/// Whether `point` is strictly inside the polygon, not on an edge or outside it.
+pub fn contains(corners: &[Coord], point: Coord) -> bool {
+    if on_boundary(corners, point) {
+        return false;
+    }
+    // Count the edges crossed by a ray going east from the point. An edge only counts if it
+    // starts on or below the ray and ends above it or the other way around, so a corner the
+    // ray passes through is counted once if the edges keep going the same way and otherwise
+    // not at all.
+    edges(corners)
+        .filter(|&(a, b)| {
+            if (a.y > point.y) == (b.y > point.y) {
+                return false;
+            }
+            // Which side of the edge the point is on: the crossing is east of the point if
+            // it's on the left going up or the right going down
+            let side = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+            (side > 0) == (b.y > a.y)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+#[test]
+fn test_polygon() {
+    // A 4x3 rectangle with a 1x1 notch out of the top, and a corner partway along the bottom
+    let corners = [
+        (0, 0),
+        (1, 0),
+        (1, 1),
+        (2, 1),
+        (2, 0),
+        (4, 0),
+        (4, 3),
+        (2, 3),
+        (0, 3),
+    ]
+    .map(|(x, y)| Coord::new(x, y));
+    assert_eq!(double_area(&corners), 2 * (12 - 1));
+    assert_eq!(perimeter(&corners), 16);
+    assert_eq!(interior_points(&corners), 4);
+    assert!(contains(&corners, Coord::new(3, 1)));
+    assert!(contains(&corners, Coord::new(1, 2)));
+    assert!(!contains(&corners, Coord::new(1, 1)));
+    assert!(on_boundary(&corners, Coord::new(1, 1)));
+    assert!(on_boundary(&corners, Coord::new(3, 3)));
+    assert!(!contains(&corners, Coord::new(5, 1)));
+    assert!(!contains(&corners, Coord::new(-1, 0)));
+    assert!(!on_boundary(&corners, Coord::new(5, 0)));
+
+    // Diagonal edges
+    let triangle = [(0, 0), (4, 0), (0, 4)].map(|(x, y)| Coord::new(x, y));
+    assert_eq!(double_area(&triangle), 16);
+    assert_eq!(perimeter(&triangle), 12);
+    assert_eq!(interior_points(&triangle), 3);
+    assert!(contains(&triangle, Coord::new(1, 2)));
+    assert!(on_boundary(&triangle, Coord::new(2, 2)));
+    assert!(!contains(&triangle, Coord::new(3, 2)));
+}
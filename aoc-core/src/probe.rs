@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+use crate::SolveError;
+
+/// A command for poking at a day's parsed puzzle from `aoc explore`, like asking how many
+/// plots are reachable after some number of steps.
+pub struct Probe<S> {
+    pub name: &'static str,
+    /// What it takes, one word per argument, like `<steps>`
+    pub usage: &'static str,
+    pub help: &'static str,
+    pub run: fn(&S, &ProbeArgs) -> Result<String, SolveError>,
+}
+
+impl<S> Probe<S> {
+    /// How many arguments it takes.
+    pub fn arity(&self) -> usize {
+        self.usage.split_whitespace().count()
+    }
+}
+
+/// The words a probe was given after its name.
+pub struct ProbeArgs<'a>(&'a [&'a str]);
+
+impl<'a> ProbeArgs<'a> {
+    pub fn new(args: &'a [&'a str]) -> Self {
+        Self(args)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Argument `i` read as a `T`, with `name` saying what it was meant to be if it can't be.
+    pub fn get<T: FromStr>(&self, i: usize, name: &str) -> Result<T, SolveError> {
+        let arg = self
+            .0
+            .get(i)
+            .ok_or_else(|| SolveError::failed(format!("expected {name}")))?;
+        arg.parse()
+            .map_err(|_| SolveError::failed(format!("can't read {name} {arg:?}")))
+    }
+}
+
+#[test]
+fn test_probe_args() {
+    let words = ["12", "north"];
+    let args = ProbeArgs::new(&words);
+    assert_eq!(args.len(), 2);
+    assert_eq!(args.get::<i64>(0, "steps"), Ok(12));
+    assert_eq!(
+        args.get::<i64>(1, "steps"),
+        Err(SolveError::failed("can't read steps \"north\""))
+    );
+    assert_eq!(
+        args.get::<i64>(2, "a radius"),
+        Err(SolveError::failed("expected a radius"))
+    );
+
+    let probe = Probe::<()> {
+        name: "reachable",
+        usage: "<steps> <radius>",
+        help: "",
+        run: |_, _| Ok(String::new()),
+    };
+    assert_eq!(probe.arity(), 2);
+}
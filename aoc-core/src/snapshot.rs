@@ -0,0 +1,102 @@
+use std::path::Path;
+
+/// Environment variable that makes [`assert_snapshot!`](crate::assert_snapshot) save what it's
+/// given as the new snapshot instead of comparing with the old one.
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// What's wrong with `actual` as a copy of `expected`, saying the first line they differ on.
+fn difference(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => continue,
+            (None, None) => return Some("they differ only in line endings".to_string()),
+            (e, a) => {
+                let show = |line: Option<&str>| {
+                    line.map_or("end of text".to_string(), |l| format!("{l:?}"))
+                };
+                return Some(format!(
+                    "line {line} differs:\n  expected {}\n       got {}",
+                    show(e),
+                    show(a)
+                ));
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Compares `actual` with the snapshot saved at `path`, saving it there instead if there's no
+/// snapshot yet or [`UPDATE_VAR`] is set. A new snapshot still fails the test, so that it's
+/// looked at before it's trusted.
+pub fn check(path: &Path, actual: &str) {
+    let update = std::env::var_os(UPDATE_VAR).is_some();
+    let saved = std::fs::read_to_string(path).ok();
+    if let (Some(expected), false) = (&saved, update) {
+        if let Some(difference) = difference(expected, actual) {
+            panic!(
+                "{} doesn't match; set {UPDATE_VAR} to replace it if the change is right\n{difference}",
+                path.display()
+            );
+        }
+        return;
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).expect("should create the snapshot directory");
+    }
+    std::fs::write(path, actual).expect("should save the snapshot");
+    if saved.is_none() && !update {
+        panic!(
+            "saved a new snapshot at {}; check it and run again",
+            path.display()
+        );
+    }
+}
+
+/// Asserts that something's [`Display`](std::fmt::Display) rendering is the same as last time,
+/// as saved in the calling crate's `snapshots/{name}.txt`.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $value:expr) => {
+        $crate::snapshot::check(
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("snapshots")
+                .join(format!("{}.txt", $name)),
+            &$value.to_string(),
+        )
+    };
+}
+
+#[test]
+fn test_difference() {
+    assert_eq!(difference("a\nb\n", "a\nb\n"), None);
+    assert_eq!(
+        difference("a\nb\n", "a\nc\n").unwrap(),
+        "line 2 differs:\n  expected \"b\"\n       got \"c\""
+    );
+    assert_eq!(
+        difference("a\n", "a\nb\n").unwrap(),
+        "line 2 differs:\n  expected end of text\n       got \"b\""
+    );
+    assert_eq!(
+        difference("a", "a\n").unwrap(),
+        "they differ only in line endings"
+    );
+}
+
+#[test]
+fn test_check() {
+    let path = std::env::temp_dir()
+        .join(format!("aoc-snapshot-test-{}", std::process::id()))
+        .join("grid.txt");
+    let new = std::panic::catch_unwind(|| check(&path, "#.\n.#\n"));
+    assert!(new.is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "#.\n.#\n");
+    check(&path, "#.\n.#\n");
+    assert!(std::panic::catch_unwind(|| check(&path, "#.\n##\n")).is_err());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, fmt, str::FromStr, time::Duration};
+
+use thiserror::Error;
+
+use crate::{Cancel, Probe, StateDump, Svg};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SolveError {
+    #[error("not implemented")]
+    NotImplemented,
+    #[error("{0}")]
+    Failed(String),
+    /// Gave up at the time limit, having got as far as `progress` says if it says
+    #[error("timed out{}", progress.as_ref().map(|p| format!(" with {p}")).unwrap_or_default())]
+    TimedOut { progress: Option<String> },
+}
+
+impl SolveError {
+    pub fn failed(e: impl fmt::Display) -> Self {
+        Self::Failed(e.to_string())
+    }
+}
+
+/// Settings that change what a day's puzzle asks for, like how many steps to take, by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: impl ToString) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Every setting and its value, in order of name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The setting `name` read as a `T`, or None if it isn't set.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, SolveError> {
+        self.0
+            .get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| SolveError::failed(format!("can't read {name} = {value:?}")))
+            })
+            .transpose()
+    }
+
+    /// An error if any setting isn't one of `known`, since it would be ignored.
+    pub fn only(&self, known: &[&str]) -> Result<(), SolveError> {
+        match self.0.keys().find(|name| !known.contains(&name.as_str())) {
+            Some(name) if known.is_empty() => Err(SolveError::failed(format!(
+                "there's no setting {name}; this day doesn't have any"
+            ))),
+            Some(name) => Err(SolveError::failed(format!(
+                "there's no setting {name}, only {}",
+                known.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A day's puzzle, parsed once from the input and then solved for either part with the
+/// settings the day's own binary uses by default.
+pub trait Solver: Sized {
+    fn parse(input: &str) -> Result<Self, SolveError>;
+
+    fn part1(&self) -> Result<String, SolveError>;
+
+    fn part2(&self) -> Result<String, SolveError>;
+
+    /// Changes settings from what the day's own binary uses, for days that have any.
+    fn configure(&mut self, params: &Params) -> Result<(), SolveError> {
+        params.only(&[])
+    }
+
+    /// Makes each part give up once it's taken `limit`, with [`SolveError::TimedOut`] saying
+    /// how far it got, for days whose parts can take that long.
+    fn set_time_limit(&mut self, limit: Duration) {
+        let _ = limit;
+    }
+
+    /// Solves `part` like [`part1`](Solver::part1) or [`part2`](Solver::part2), but gives up
+    /// with [`SolveError::TimedOut`] once `cancel` is cancelled, for days whose parts check it.
+    fn solve_cancellable(&self, part: u8, cancel: &Cancel) -> Result<String, SolveError> {
+        let _ = cancel;
+        match part {
+            1 => self.part1(),
+            _ => self.part2(),
+        }
+    }
+
+    /// Solves `part` the slow, obvious way, or with a different algorithm, for days that have
+    /// one, to check the answers [`part1`](Solver::part1) and [`part2`](Solver::part2) give on
+    /// inputs small enough for it.
+    fn naive(&self, part: u8) -> Result<String, SolveError> {
+        let _ = part;
+        Err(SolveError::NotImplemented)
+    }
+
+    /// Writes state from partway through solving to `dump`, for days that have any worth
+    /// looking at.
+    fn dump_state(&self, dump: &StateDump) -> Result<(), SolveError> {
+        let _ = dump;
+        Ok(())
+    }
+
+    /// A picture of the puzzle for reports, for days that have one worth looking at.
+    fn picture(&self) -> Option<Svg> {
+        None
+    }
+
+    /// Commands for poking at the parsed puzzle from `aoc explore`, for days that have any.
+    fn probes() -> Vec<Probe<Self>> {
+        Vec::new()
+    }
+
+    /// Parses `input` and solves `part`, which should be 1 or 2.
+    fn solve(input: &str, part: u8) -> Result<String, SolveError> {
+        let puzzle = Self::parse(input)?;
+        match part {
+            1 => puzzle.part1(),
+            2 => puzzle.part2(),
+            _ => panic!("there is no part {part}"),
+        }
+    }
+}
+
+#[test]
+fn test_timed_out() {
+    assert_eq!(
+        SolveError::TimedOut { progress: None }.to_string(),
+        "timed out"
+    );
+    let progress = Some("a route 6418 steps long".to_string());
+    assert_eq!(
+        SolveError::TimedOut { progress }.to_string(),
+        "timed out with a route 6418 steps long"
+    );
+}
+
+#[test]
+fn test_params() {
+    let mut params = Params::new();
+    assert_eq!(params.only(&[]), Ok(()));
+    params.set("steps", 100);
+    params.set("dir", "N");
+    assert_eq!(params.get::<u64>("steps"), Ok(Some(100)));
+    assert_eq!(params.get::<u64>("start"), Ok(None));
+    assert_eq!(
+        params.get::<u64>("dir"),
+        Err(SolveError::failed("can't read dir = \"N\""))
+    );
+    assert_eq!(
+        params.iter().collect::<Vec<_>>(),
+        [("dir", "N"), ("steps", "100")]
+    );
+    assert_eq!(params.only(&["dir", "steps", "start"]), Ok(()));
+    assert_eq!(
+        params.only(&["steps", "start"]),
+        Err(SolveError::failed(
+            "there's no setting dir, only steps, start"
+        ))
+    );
+    assert_eq!(
+        params.only(&[]),
+        Err(SolveError::failed(
+            "there's no setting dir; this day doesn't have any"
+        ))
+    );
+}
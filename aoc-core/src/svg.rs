@@ -0,0 +1,145 @@
+use std::{fmt, io, path::Path};
+
+use crate::{Grid, Visualize};
+
+/// How wide or tall the picture is shown, whichever is bigger, in pixels.
+const SIZE: f64 = 1000.0;
+
+/// A picture built up out of shapes in puzzle coordinates, where a grid tile is one unit
+/// square, written as SVG. For inputs too big to make sense of printed out.
+#[derive(Debug, Default)]
+pub struct Svg {
+    elements: Vec<String>,
+    /// Smallest and largest x and y of anything drawn so far
+    bounds: Option<[f64; 4]>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn include(&mut self, x: f64, y: f64) {
+        let [min_x, min_y, max_x, max_y] = self.bounds.get_or_insert([x, y, x, y]);
+        *min_x = min_x.min(x);
+        *min_y = min_y.min(y);
+        *max_x = max_x.max(x);
+        *max_y = max_y.max(y);
+    }
+
+    /// A rectangle with its top left corner at (x, y).
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        self.include(x, y);
+        self.include(x + width, y + height);
+        self.elements.push(format!(
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}"/>"#
+        ));
+    }
+
+    pub fn circle(&mut self, x: f64, y: f64, radius: f64, fill: &str) {
+        self.include(x - radius, y - radius);
+        self.include(x + radius, y + radius);
+        self.elements.push(format!(
+            r#"<circle cx="{x}" cy="{y}" r="{radius}" fill="{fill}"/>"#
+        ));
+    }
+
+    /// A closed shape through `points`, outlined `stroke_width` units wide.
+    pub fn polygon(&mut self, points: &[(f64, f64)], fill: &str, stroke: &str, stroke_width: f64) {
+        let half = stroke_width / 2.0;
+        for &(x, y) in points {
+            self.include(x - half, y - half);
+            self.include(x + half, y + half);
+        }
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        self.elements.push(format!(
+            r#"<polygon points="{}" fill="{fill}" stroke="{stroke}" stroke-width="{stroke_width}"/>"#,
+            points.join(" ")
+        ));
+    }
+
+    /// Fills each tile of `grid` with the color `fill` gives its cell, if any. Tiles next to
+    /// each other in a row with the same color are drawn as one rectangle, to keep the file
+    /// small for big grids.
+    pub fn grid<T, S: AsRef<str> + PartialEq>(
+        &mut self,
+        grid: &Grid<T>,
+        mut fill: impl FnMut(&T) -> Option<S>,
+    ) {
+        for (y, row) in grid.rows().enumerate() {
+            let colors: Vec<_> = row.iter().map(&mut fill).collect();
+            let mut x = 0;
+            for run in colors.chunk_by(|a, b| a == b) {
+                if let Some(color) = &run[0] {
+                    self.rect(x as f64, y as f64, run.len() as f64, 1.0, color.as_ref());
+                }
+                x += run.len();
+            }
+        }
+    }
+
+    /// Fills each tile of `frame`'s picture with the color it gives the tile's character.
+    pub fn frame(&mut self, frame: &impl Visualize) {
+        self.grid(&frame.frame(), |&ch| {
+            let [r, g, b] = frame.color(ch);
+            Some(format!("#{r:02x}{g:02x}{b:02x}"))
+        });
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [min_x, min_y, max_x, max_y] = self.bounds.unwrap_or_default();
+        let (width, height) = ((max_x - min_x).max(1.0), (max_y - min_y).max(1.0));
+        let scale = SIZE / width.max(height);
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{min_x} {min_y} {width} {height}" width="{}" height="{}">"#,
+            (width * scale).round(),
+            (height * scale).round()
+        )?;
+        for element in &self.elements {
+            writeln!(f, "{element}")?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[test]
+fn test_svg() {
+    let mut svg = Svg::new();
+    let grid: Grid<char> = "##.#\n..##\n".parse().unwrap();
+    svg.grid(&grid, |&ch| (ch == '#').then_some("black"));
+    svg.circle(1.5, 0.5, 0.5, "red");
+    svg.polygon(&[(0.5, 0.5), (3.5, 1.5), (0.5, 1.5)], "none", "blue", 0.1);
+    assert_eq!(
+        svg.to_string(),
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 4 2" width="1000" height="500">
+<rect x="0" y="0" width="2" height="1" fill="black"/>
+<rect x="3" y="0" width="1" height="1" fill="black"/>
+<rect x="2" y="1" width="2" height="1" fill="black"/>
+<circle cx="1.5" cy="0.5" r="0.5" fill="red"/>
+<polygon points="0.5,0.5 3.5,1.5 0.5,1.5" fill="none" stroke="blue" stroke-width="0.1"/>
+</svg>
+"#
+    );
+}
+
+#[test]
+fn test_frame() {
+    let mut svg = Svg::new();
+    let grid: Grid<char> = "#..\n".parse().unwrap();
+    svg.frame(&grid);
+    assert_eq!(
+        svg.to_string(),
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 3 1" width="1000" height="333">
+<rect x="0" y="0" width="1" height="1" fill="#ffffff"/>
+<rect x="1" y="0" width="2" height="1" fill="#000000"/>
+</svg>
+"##
+    );
+}
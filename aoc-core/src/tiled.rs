@@ -0,0 +1,137 @@
+use std::collections::{hash_map, HashMap};
+
+use crate::Coord;
+
+/// Which copy of the tile a position is in, counting from the copy whose top left is the
+/// origin.
+pub type Tile = (i64, i64);
+
+/// An endless plane cut into `width` by `height` tiles, holding a `C` for only the tiles that
+/// have been given one, for puzzles whose map repeats forever. Each `C` covers its tile, like
+/// a [`Grid`](crate::Grid) or a bitset of it, so that work can be done a tile at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tiled<C> {
+    width: usize,
+    height: usize,
+    tiles: HashMap<Tile, C>,
+}
+
+impl<C> Tiled<C> {
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0, "tiles can't be empty");
+        Self {
+            width,
+            height,
+            tiles: HashMap::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The tile `coord` is in, and where it is within that tile.
+    pub fn split(&self, coord: Coord) -> (Tile, Coord) {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (
+            (coord.x.div_euclid(width), coord.y.div_euclid(height)),
+            Coord::new(coord.x.rem_euclid(width), coord.y.rem_euclid(height)),
+        )
+    }
+
+    /// Where `local`, a position within `tile`, is on the whole plane.
+    pub fn join(&self, tile: Tile, local: Coord) -> Coord {
+        Coord::new(
+            tile.0 * self.width as i64 + local.x,
+            tile.1 * self.height as i64 + local.y,
+        )
+    }
+
+    pub fn get(&self, tile: Tile) -> Option<&C> {
+        self.tiles.get(&tile)
+    }
+
+    pub fn get_mut(&mut self, tile: Tile) -> Option<&mut C> {
+        self.tiles.get_mut(&tile)
+    }
+
+    /// What's in `tile`, putting what `fill` makes there first if it's empty.
+    pub fn get_or_insert_with(&mut self, tile: Tile, fill: impl FnOnce() -> C) -> &mut C {
+        self.tiles.entry(tile).or_insert_with(fill)
+    }
+
+    pub fn insert(&mut self, tile: Tile, contents: C) -> Option<C> {
+        self.tiles.insert(tile, contents)
+    }
+
+    /// How many tiles have something in them.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// The tiles that have something in them, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Tile, &C)> {
+        self.tiles.iter().map(|(&tile, contents)| (tile, contents))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Tile, &mut C)> {
+        self.tiles
+            .iter_mut()
+            .map(|(&tile, contents)| (tile, contents))
+    }
+
+    /// Empties the tiles whose contents `keep` says no to.
+    pub fn retain(&mut self, mut keep: impl FnMut(Tile, &mut C) -> bool) {
+        self.tiles.retain(|&tile, contents| keep(tile, contents));
+    }
+}
+
+impl<C> IntoIterator for Tiled<C> {
+    type Item = (Tile, C);
+    type IntoIter = hash_map::IntoIter<Tile, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tiles.into_iter()
+    }
+}
+
+#[test]
+fn test_split() {
+    let tiled = Tiled::<()>::new(11, 7);
+    for (coord, tile, local) in [
+        (Coord::new(0, 0), (0, 0), Coord::new(0, 0)),
+        (Coord::new(10, 6), (0, 0), Coord::new(10, 6)),
+        (Coord::new(11, 7), (1, 1), Coord::new(0, 0)),
+        (Coord::new(-1, 3), (-1, 0), Coord::new(10, 3)),
+        (Coord::new(-23, -14), (-3, -2), Coord::new(10, 0)),
+    ] {
+        assert_eq!(tiled.split(coord), (tile, local), "{coord}");
+        assert_eq!(tiled.join(tile, local), coord);
+    }
+}
+
+#[test]
+fn test_tiles() {
+    let mut tiled = Tiled::new(3, 3);
+    assert!(tiled.is_empty());
+    *tiled.get_or_insert_with((-1, 2), || 0) += 5;
+    *tiled.get_or_insert_with((-1, 2), || 0) += 1;
+    tiled.insert((0, 0), 2);
+    assert_eq!(tiled.len(), 2);
+    assert_eq!(tiled.get((-1, 2)), Some(&6));
+    assert_eq!(tiled.get((2, -1)), None);
+    tiled.iter_mut().for_each(|(_, n)| *n *= 10);
+    tiled.insert((5, 5), 0);
+    tiled.retain(|_, &mut n| n > 0);
+    let mut tiles: Vec<_> = tiled.into_iter().collect();
+    tiles.sort();
+    assert_eq!(tiles, [((-1, 2), 60), ((0, 0), 20)]);
+}
@@ -0,0 +1,108 @@
+/// Sets of the numbers `0..len`, starting with each in a set of its own, that can be merged and
+/// asked which set something is in (a disjoint-set forest).
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    /// Each element's parent in its set's tree, with the root its own parent
+    parent: Vec<usize>,
+    /// How many elements are in the set each root is the root of
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// How many elements there are, in every set.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// How many separate sets there are.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The element that stands for the set `x` is in, which only changes when the set is
+    /// merged with another.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            // Skipping every other step on the way up keeps the trees shallow
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets `a` and `b` are in, returning whether they were separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// How many elements are in the same set as `x`, counting `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The size of every set, biggest first.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+            .collect();
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+#[test]
+fn test_union_find() {
+    let mut sets = UnionFind::new(6);
+    assert_eq!((sets.len(), sets.sets()), (6, 6));
+    assert!(sets.union(0, 1));
+    assert!(sets.union(2, 3));
+    assert!(sets.union(1, 3));
+    assert!(!sets.union(0, 2));
+    assert!(sets.same(0, 3));
+    assert!(!sets.same(0, 4));
+    assert_eq!(sets.size_of(2), 4);
+    assert_eq!(sets.size_of(5), 1);
+    assert_eq!(sets.sets(), 3);
+    assert_eq!(sets.set_sizes(), [4, 1, 1]);
+    assert!(UnionFind::new(0).set_sizes().is_empty());
+}
+
+#[test]
+fn test_long_chain() {
+    let len = 100_000;
+    let mut sets = UnionFind::new(len);
+    for i in 1..len {
+        sets.union(i - 1, i);
+    }
+    assert_eq!(sets.set_sizes(), [len]);
+    assert!(sets.same(0, len - 1));
+}
@@ -0,0 +1,82 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::Grid;
+
+/// Something that can be drawn as a grid of characters, like a puzzle's state partway through
+/// a simulation.
+pub trait Visualize {
+    fn frame(&self) -> Grid<char>;
+
+    /// The color to draw a character of the frame in when it's saved as an image. Unless the
+    /// puzzle says otherwise, `.` and spaces are black and anything else is white.
+    fn color(&self, ch: char) -> [u8; 3] {
+        match ch {
+            '.' | ' ' => [0, 0, 0],
+            _ => [255, 255, 255],
+        }
+    }
+}
+
+/// Somewhere to send a simulation's frames as it runs: shown in a terminal, or saved as an
+/// animation.
+pub trait Recorder {
+    fn record(&mut self, frame: &impl Visualize, caption: &str) -> io::Result<()>;
+}
+
+impl Visualize for Grid<char> {
+    fn frame(&self) -> Grid<char> {
+        self.clone()
+    }
+}
+
+/// Moves the cursor to the top left corner and clears the screen.
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+/// Draws frames in a terminal one after another, each replacing the one before.
+pub struct Terminal<W: Write> {
+    out: W,
+    /// How long each frame stays up before the next can be drawn
+    delay: Duration,
+}
+
+impl Terminal<io::Stdout> {
+    pub fn stdout(delay: Duration) -> Self {
+        Self::new(io::stdout(), delay)
+    }
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Self { out, delay }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Recorder for Terminal<W> {
+    /// Clears the screen and draws `frame` with `caption` under it, then waits out the delay.
+    fn record(&mut self, frame: &impl Visualize, caption: &str) -> io::Result<()> {
+        writeln!(self.out, "{CLEAR}{}{caption}", frame.frame())?;
+        self.out.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_terminal() {
+    let mut terminal = Terminal::new(vec![], Duration::ZERO);
+    let grid: Grid<char> = "#.\n.#\n".parse().unwrap();
+    terminal.record(&grid, "first").unwrap();
+    terminal.record(&grid.map(|_| '.'), "second").unwrap();
+    assert_eq!(
+        String::from_utf8(terminal.into_inner()).unwrap(),
+        format!("{CLEAR}#.\n.#\nfirst\n{CLEAR}..\n..\nsecond\n")
+    );
+}
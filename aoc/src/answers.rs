@@ -0,0 +1,211 @@
+use std::{
+    io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::client::Verdict;
+
+/// How long adventofcode.com makes you wait after a wrong answer, at least.
+const WRONG_ANSWER_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Submission {
+    day: u8,
+    part: u8,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Answers already judged by adventofcode.com, and when it will next accept one, so the runner
+/// doesn't submit anything whose verdict it can already tell or that would be turned away.
+///
+/// Saved one line per answer, as `day part answer verdict`, after a `wait-until` line with the
+/// time in seconds since the Unix epoch.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    submissions: Vec<Submission>,
+    wait_until: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn verdict_name(verdict: Verdict) -> Option<&'static str> {
+    Some(match verdict {
+        Verdict::Correct => "correct",
+        Verdict::TooHigh => "too-high",
+        Verdict::TooLow => "too-low",
+        Verdict::Wrong => "wrong",
+        Verdict::TooSoon(_) | Verdict::AlreadySolved => return None,
+    })
+}
+
+fn parse_verdict_name(name: &str) -> Option<Verdict> {
+    [
+        Verdict::Correct,
+        Verdict::TooHigh,
+        Verdict::TooLow,
+        Verdict::Wrong,
+    ]
+    .into_iter()
+    .find(|&v| verdict_name(v) == Some(name))
+}
+
+impl Answers {
+    /// Loads the answers saved at `path`, or none if nothing has been saved there yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => contents.parse().map_err(|line| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: can't read {line:?}", path.display()),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    /// The verdict `answer` would get, if the answers judged so far are enough to tell.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Option<Verdict> {
+        let judged = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part);
+        let number: Option<i128> = answer.parse().ok();
+        for s in judged {
+            if s.answer == answer {
+                return Some(s.verdict);
+            }
+            let bound: Option<i128> = s.answer.parse().ok();
+            match (s.verdict, number, bound) {
+                (Verdict::Correct, _, _) => return Some(Verdict::Wrong),
+                (Verdict::TooHigh, Some(n), Some(b)) if n >= b => return Some(Verdict::TooHigh),
+                (Verdict::TooLow, Some(n), Some(b)) if n <= b => return Some(Verdict::TooLow),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// The answer adventofcode.com accepted for `part` of `day`, if one has been.
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.submissions
+            .iter()
+            .find(|s| (s.day, s.part, s.verdict) == (day, part, Verdict::Correct))
+            .map(|s| s.answer.as_str())
+    }
+
+    /// How much longer to wait before adventofcode.com will take another answer.
+    pub fn wait_remaining(&self) -> Option<Duration> {
+        let now = now();
+        (self.wait_until > now).then(|| Duration::from_secs(self.wait_until - now))
+    }
+
+    /// Records what adventofcode.com said about `answer`.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, verdict: Verdict) {
+        let wait = match verdict {
+            Verdict::TooSoon(wait) => wait,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong => WRONG_ANSWER_WAIT,
+            Verdict::Correct | Verdict::AlreadySolved => Duration::ZERO,
+        };
+        self.wait_until = self.wait_until.max(now() + wait.as_secs());
+        if verdict_name(verdict).is_some() {
+            self.submissions.push(Submission {
+                day,
+                part,
+                answer: answer.to_string(),
+                verdict,
+            });
+        }
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "wait-until {}", self.wait_until)?;
+        for s in &self.submissions {
+            let verdict = verdict_name(s.verdict).expect("only judged answers are recorded");
+            writeln!(f, "{} {} {} {verdict}", s.day, s.part, s.answer)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Answers {
+    /// The line that couldn't be read
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let bad_line = || line.to_string();
+            if let Some(wait_until) = line.strip_prefix("wait-until ") {
+                answers.wait_until = wait_until.trim().parse().map_err(|_| bad_line())?;
+                continue;
+            }
+            let [day, part, answer, verdict] = line
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| bad_line())?;
+            answers.submissions.push(Submission {
+                day: day.parse().map_err(|_| bad_line())?,
+                part: part.parse().map_err(|_| bad_line())?,
+                answer: answer.to_string(),
+                verdict: parse_verdict_name(verdict).ok_or_else(bad_line)?,
+            });
+        }
+        Ok(answers)
+    }
+}
+
+#[test]
+fn test_check() {
+    let mut answers = Answers::default();
+    answers.record(3, 1, "500", Verdict::TooHigh);
+    answers.record(3, 1, "100", Verdict::TooLow);
+    answers.record(3, 2, "abc", Verdict::Wrong);
+    answers.record(4, 1, "42", Verdict::Correct);
+    answers.record(4, 2, "7", Verdict::AlreadySolved);
+
+    assert_eq!(answers.check(3, 1, "500"), Some(Verdict::TooHigh));
+    assert_eq!(answers.check(3, 1, "600"), Some(Verdict::TooHigh));
+    assert_eq!(answers.check(3, 1, "99"), Some(Verdict::TooLow));
+    assert_eq!(answers.check(3, 1, "250"), None);
+    assert_eq!(answers.check(3, 2, "abc"), Some(Verdict::Wrong));
+    assert_eq!(answers.check(3, 2, "abd"), None);
+    assert_eq!(answers.check(4, 1, "42"), Some(Verdict::Correct));
+    assert_eq!(answers.check(4, 1, "43"), Some(Verdict::Wrong));
+    assert_eq!(answers.check(4, 2, "7"), None);
+    assert_eq!(answers.check(5, 1, "500"), None);
+    assert_eq!(answers.accepted(4, 1), Some("42"));
+    assert_eq!(answers.accepted(3, 1), None);
+    assert_eq!(answers.accepted(4, 2), None);
+
+    let wait = answers.wait_remaining().unwrap();
+    assert!(wait <= WRONG_ANSWER_WAIT && wait >= WRONG_ANSWER_WAIT - Duration::from_secs(5));
+}
+
+#[test]
+fn test_round_trip() {
+    let mut answers = Answers::default();
+    answers.record(3, 1, "500", Verdict::TooHigh);
+    answers.record(4, 2, "42", Verdict::Correct);
+    let saved = answers.to_string();
+    assert!(saved.ends_with("3 1 500 too-high\n4 2 42 correct\n"));
+    assert_eq!(saved.parse(), Ok(answers));
+
+    assert_eq!(
+        "3 1 500 sideways".parse::<Answers>(),
+        Err("3 1 500 sideways".to_string())
+    );
+}
@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, io, path::Path, time::Duration};
+
+use aoc_core::Params;
+
+use crate::run::{self, DayRun, PartRun, RunOptions};
+
+/// A hash of a puzzle input and any settings changed for it, which stays the same between
+/// builds, unlike std's hashers.
+pub fn input_hash(input: &str, params: &Params) -> u64 {
+    let settings = params
+        .iter()
+        .flat_map(|(name, value)| [b"\0".as_slice(), name.as_bytes(), b"=", value.as_bytes()]);
+    // 64-bit FNV-1a
+    std::iter::once(input.as_bytes())
+        .chain(settings)
+        .flatten()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+/// Answers worked out before, by day, part and a hash of the input and settings, so that
+/// running a day again on the same input doesn't solve it again. Nothing notices a solution
+/// changing, so the cache has to be skipped after fixing one.
+///
+/// Saved one line per answer, as `day part hash answer`, with the hash in hex.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Cache {
+    answers: BTreeMap<(u8, u8, u64), String>,
+}
+
+impl Cache {
+    /// Loads the answers saved at `path`, or none if nothing has been saved there yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => contents.parse().map_err(|line| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: can't read {line:?}", path.display()),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: u8, input_hash: u64) -> Option<&str> {
+        self.answers
+            .get(&(day, part, input_hash))
+            .map(String::as_str)
+    }
+
+    /// Records every answer `run` found that wasn't cached already. Answers over more than one
+    /// line aren't kept, since they can't be saved.
+    pub fn record(&mut self, run: &DayRun, input_hash: u64) {
+        for part in run.parts.iter().flatten().filter(|p| !p.cached) {
+            if let Ok(answer) = &part.answer {
+                if !answer.contains('\n') {
+                    self.answers
+                        .insert((run.day, part.part, input_hash), answer.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Like [`run::run_day`], but parts with an answer in `cache`, which should be `year`'s, aren't
+/// solved again, and if they all have one the input isn't even parsed.
+pub fn run_day(
+    cache: &Cache,
+    year: u16,
+    day: u8,
+    input: &str,
+    parts: &[u8],
+    options: &RunOptions,
+) -> DayRun {
+    let hash = input_hash(input, &options.params);
+    let cached: Vec<PartRun> = parts
+        .iter()
+        .filter_map(|&part| {
+            cache.get(day, part, hash).map(|answer| PartRun {
+                part,
+                answer: Ok(answer.to_string()),
+                time: Duration::ZERO,
+                cached: true,
+                peak_memory: None,
+                naive: None,
+            })
+        })
+        .collect();
+    let uncached: Vec<u8> = parts
+        .iter()
+        .copied()
+        .filter(|&part| cached.iter().all(|p| p.part != part))
+        .collect();
+    let mut run = if uncached.is_empty() {
+        DayRun {
+            day,
+            parse_time: Duration::ZERO,
+            parts: Ok(vec![]),
+            peak_memory: None,
+            picture: None,
+        }
+    } else {
+        run::run_day(year, day, input, &uncached, options)
+    };
+    if let Ok(parts) = &mut run.parts {
+        parts.extend(cached);
+        parts.sort_by_key(|p| p.part);
+    }
+    run
+}
+
+impl std::fmt::Display for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part, hash), answer) in &self.answers {
+            writeln!(f, "{day} {part} {hash:016x} {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Cache {
+    /// The line that couldn't be read
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cache = Cache::default();
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let bad_line = || line.to_string();
+            let [day, part, hash, answer] = line
+                .splitn(4, ' ')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| bad_line())?;
+            let key = (
+                day.parse().map_err(|_| bad_line())?,
+                part.parse().map_err(|_| bad_line())?,
+                u64::from_str_radix(hash, 16).map_err(|_| bad_line())?,
+            );
+            cache.answers.insert(key, answer.to_string());
+        }
+        Ok(cache)
+    }
+}
+
+#[test]
+fn test_input_hash() {
+    let none = &Params::new();
+    assert_eq!(input_hash("", none), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(input_hash("a", none), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(input_hash("1 2\n", none), input_hash("1 2", none));
+    let mut params = Params::new();
+    params.set("steps", 10);
+    assert_ne!(input_hash("a", &params), input_hash("a", none));
+}
+
+#[test]
+fn test_run_day() {
+    let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n";
+    let none = &Params::new();
+    let options = &RunOptions::default();
+    let hash = input_hash(input, none);
+    let mut cache = Cache::default();
+    let run = run_day(&cache, 2023, 9, input, &[1], options);
+    assert!(!run.parts.as_ref().unwrap()[0].cached);
+    cache.record(&run, hash);
+    assert_eq!(cache.get(9, 1, hash), Some("46"));
+    assert_eq!(cache.get(9, 2, hash), None);
+
+    let run = run_day(&cache, 2023, 9, input, &[1, 2], options);
+    let parts = run.parts.as_ref().unwrap();
+    assert_eq!((parts[0].part, parts[0].cached), (1, true));
+    assert_eq!((parts[1].part, parts[1].cached), (2, false));
+    assert_eq!(parts[1].answer, Ok("-3".to_string()));
+    cache.record(&run, hash);
+
+    // With every answer cached, a broken solver would never be called
+    cache
+        .answers
+        .insert((19, 1, input_hash("x", none)), "19114".to_string());
+    let run = run_day(&cache, 2023, 19, "x", &[1], options);
+    assert_eq!(run.part(1).unwrap().answer, Ok("19114".to_string()));
+    assert_eq!(run.parse_time, Duration::ZERO);
+
+    let saved = cache.to_string();
+    assert!(saved.starts_with(&format!("9 1 {hash:016x} 46\n9 2 {hash:016x} -3\n")));
+    assert_eq!(saved.parse(), Ok(cache));
+    assert_eq!("9 1 xyz 46".parse::<Cache>(), Err("9 1 xyz 46".to_string()));
+}
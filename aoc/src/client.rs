@@ -0,0 +1,197 @@
+use std::{fmt, io, time::Duration};
+
+use thiserror::Error;
+
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error(transparent)]
+    Request(Box<ureq::Error>),
+    #[error("couldn't read the response from {url}: {source}")]
+    Response { url: String, source: io::Error },
+    #[error("didn't recognize the response to the answer; it said {0:?}")]
+    UnexpectedResponse(String),
+}
+
+/// What adventofcode.com said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way
+    Wrong,
+    /// Not judged because the last answer was too recent
+    TooSoon(Duration),
+    /// Not judged because the part has already been solved
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooSoon(wait) => write!(f, "too soon, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Reads the verdict out of the page adventofcode.com returns for a submitted answer.
+fn parse_verdict(page: &str) -> Result<Verdict, ClientError> {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    if article.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if article.contains("That's not the right answer") {
+        Ok(if article.contains("too high") {
+            Verdict::TooHigh
+        } else if article.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if article.contains("You gave an answer too recently") {
+        Ok(Verdict::TooSoon(
+            parse_wait(article).unwrap_or(Duration::from_secs(60)),
+        ))
+    } else if article.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::AlreadySolved)
+    } else {
+        let text: String = article.chars().take(200).collect();
+        Err(ClientError::UnexpectedResponse(text))
+    }
+}
+
+/// Parses the wait from "You have 1m 23s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    let mut secs = 0;
+    for part in wait.split_whitespace() {
+        let (num, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let num: u64 = num.parse().ok()?;
+        secs += match unit {
+            "h" => num * 3600,
+            "m" => num * 60,
+            "s" => num,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Talks to adventofcode.com as the user whose session cookie it has.
+pub struct Client {
+    session: String,
+}
+
+impl Client {
+    pub fn new(session: String) -> Self {
+        Self { session }
+    }
+
+    /// A client for the session in `AOC_SESSION`, if it's set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(SESSION_VAR).ok().map(Self::new)
+    }
+
+    fn request(&self, method: &str, path: &str) -> (String, ureq::Request) {
+        let url = format!("{BASE_URL}/{path}");
+        let request = ureq::request(method, &url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set(
+                "User-Agent",
+                "github.com/AdamCDunlap/AdventOfCode2023 aoc runner",
+            );
+        (url, request)
+    }
+
+    fn read_response(
+        url: String,
+        response: Result<ureq::Response, ureq::Error>,
+    ) -> Result<String, ClientError> {
+        let response = response.map_err(|e| ClientError::Request(Box::new(e)))?;
+        response
+            .into_string()
+            .map_err(|source| ClientError::Response { url, source })
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let (url, request) = self.request("GET", &format!("{year}/day/{day}/input"));
+        Self::read_response(url, request.call())
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        let (url, request) = self.request("POST", &format!("{year}/day/{day}/answer"));
+        let level = part.to_string();
+        let page = Self::read_response(
+            url,
+            request.send_form(&[("level", &level), ("answer", answer)]),
+        )?;
+        parse_verdict(&page)
+    }
+}
+
+#[test]
+fn test_parse_verdict() {
+    let page = |article: &str| format!("<main>\n<article><p>{article}</p></article>\n</main>");
+    assert_eq!(
+        parse_verdict(&page(
+            "That's the right answer! You are one gold star closer."
+        ))
+        .unwrap(),
+        Verdict::Correct
+    );
+    assert_eq!(
+        parse_verdict(&page(
+            "That's not the right answer; your answer is too high. Please wait one minute."
+        ))
+        .unwrap(),
+        Verdict::TooHigh
+    );
+    assert_eq!(
+        parse_verdict(&page(
+            "That's not the right answer; your answer is too low."
+        ))
+        .unwrap(),
+        Verdict::TooLow
+    );
+    assert_eq!(
+        parse_verdict(&page("That's not the right answer. If you're stuck, ...")).unwrap(),
+        Verdict::Wrong
+    );
+    assert_eq!(
+        parse_verdict(&page(
+            "You gave an answer too recently. You have 1m 5s left to wait."
+        ))
+        .unwrap(),
+        Verdict::TooSoon(Duration::from_secs(65))
+    );
+    assert_eq!(
+        parse_verdict(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        ))
+        .unwrap(),
+        Verdict::AlreadySolved
+    );
+    assert!(matches!(
+        parse_verdict("<html>Puzzle inputs differ by user.</html>"),
+        Err(ClientError::UnexpectedResponse(_))
+    ));
+}
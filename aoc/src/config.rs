@@ -0,0 +1,127 @@
+use std::{collections::BTreeMap, io, path::PathBuf, str::FromStr};
+
+use aoc_core::Params;
+use thiserror::Error;
+use toml::{Table, Value};
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("couldn't read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("{}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: Box<ConfigError>,
+    },
+    #[error("{0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("expected a table for each day, like [day21], not {0:?}")]
+    NotADay(String),
+    #[error("[day{day}] {name} should be a string, number or boolean")]
+    BadValue { day: u8, name: String },
+}
+
+/// Settings that change what the days' puzzles ask for, from a TOML file with a table for each
+/// day that has any, like
+///
+/// ```toml
+/// [day21]
+/// part2_steps = 5000
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    days: BTreeMap<u8, Params>,
+}
+
+impl Config {
+    /// Loads the settings in `path`, or none if `optional` and there's no file there.
+    pub fn load(path: PathBuf, optional: bool) -> Result<Self, ConfigError> {
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => contents.parse().map_err(|e| ConfigError::Parse {
+                path,
+                source: Box::new(e),
+            }),
+            Err(e) if optional && e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(ConfigError::Read { path, source }),
+        }
+    }
+
+    /// The settings for `day`, which are empty if it doesn't have any.
+    pub fn params(&self, day: u8) -> Params {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+}
+
+impl FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeMap::new();
+        for (key, value) in s.parse::<Table>()? {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .filter(|day| (1..=25).contains(day));
+            let (Some(day), Value::Table(table)) = (day, value) else {
+                return Err(ConfigError::NotADay(key));
+            };
+            let mut params = Params::new();
+            for (name, value) in table {
+                match value {
+                    Value::String(s) => params.set(&name, s),
+                    Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => {
+                        params.set(&name, value)
+                    }
+                    _ => return Err(ConfigError::BadValue { day, name }),
+                }
+            }
+            days.insert(day, params);
+        }
+        Ok(Self { days })
+    }
+}
+
+#[test]
+fn test_parse() {
+    let config: Config = "[day21]\npart2_steps = 5000\n\n[day16]\nstart_dir = \"S\"\n"
+        .parse()
+        .unwrap();
+    assert_eq!(config.params(21).get::<i64>("part2_steps"), Ok(Some(5000)));
+    assert_eq!(
+        config.params(16).get::<String>("start_dir"),
+        Ok(Some("S".into()))
+    );
+    assert!(config.params(1).is_empty());
+
+    let error = |s: &str| s.parse::<Config>().unwrap_err().to_string();
+    assert_eq!(
+        error("[day26]\nsteps = 1"),
+        "expected a table for each day, like [day21], not \"day26\""
+    );
+    assert_eq!(
+        error("steps = 1"),
+        "expected a table for each day, like [day21], not \"steps\""
+    );
+    assert_eq!(
+        error("[day3]\nsteps = [1, 2]"),
+        "[day3] steps should be a string, number or boolean"
+    );
+    assert!("[day3".parse::<Config>().is_err());
+}
+
+#[test]
+fn test_load() {
+    let path = std::env::temp_dir().join(format!("aoc-config-test-{}.toml", std::process::id()));
+    assert_eq!(Config::load(path.clone(), true).unwrap(), Config::default());
+    assert!(matches!(
+        Config::load(path.clone(), false),
+        Err(ConfigError::Read { .. })
+    ));
+    std::fs::write(&path, "[day11]\nexpansion = 10\nexpansion = 100\n").unwrap();
+    let error = Config::load(path.clone(), true).unwrap_err().to_string();
+    assert!(
+        error.starts_with(&format!("{}: ", path.display())),
+        "{error}"
+    );
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,200 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+    time::Instant,
+};
+
+use aoc_core::{ProbeArgs, SolveError, Solver, Svg};
+
+use crate::timing::format_duration;
+
+/// A parsed puzzle of any day, as `aoc explore` sees it.
+pub trait Explore {
+    /// The name, usage and help of each of the day's probes.
+    fn probes(&self) -> Vec<(&'static str, &'static str, &'static str)>;
+
+    /// Runs the probe called `name`, or None if the day doesn't have one.
+    fn probe(&self, name: &str, args: &[&str]) -> Option<Result<String, SolveError>>;
+
+    fn solve(&self, part: u8) -> Result<String, SolveError>;
+
+    fn picture(&self) -> Option<Svg>;
+}
+
+impl<S: Solver> Explore for S {
+    fn probes(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        S::probes()
+            .into_iter()
+            .map(|p| (p.name, p.usage, p.help))
+            .collect()
+    }
+
+    fn probe(&self, name: &str, args: &[&str]) -> Option<Result<String, SolveError>> {
+        let probe = S::probes().into_iter().find(|p| p.name == name)?;
+        if args.len() != probe.arity() {
+            return Some(Err(SolveError::failed(format!(
+                "usage: {name} {}",
+                probe.usage
+            ))));
+        }
+        Some((probe.run)(self, &ProbeArgs::new(args)))
+    }
+
+    fn solve(&self, part: u8) -> Result<String, SolveError> {
+        match part {
+            1 => self.part1(),
+            _ => self.part2(),
+        }
+    }
+
+    fn picture(&self) -> Option<Svg> {
+        Solver::picture(self)
+    }
+}
+
+/// Something to do with a day's parsed puzzle before its parts are solved.
+pub type Explorer<'a> = &'a dyn Fn(&dyn Explore);
+
+/// The commands every day has, as name, usage and help.
+const BUILTINS: [(&str, &str, &str); 5] = [
+    ("help", "", "list the commands"),
+    ("part1", "", "solve part 1"),
+    ("part2", "", "solve part 2"),
+    ("picture", "<file>", "draw the puzzle to an SVG file"),
+    ("quit", "", "stop exploring"),
+];
+
+fn help(puzzle: &dyn Explore) -> String {
+    let commands: Vec<_> = BUILTINS.into_iter().chain(puzzle.probes()).collect();
+    let width = commands
+        .iter()
+        .map(|(name, usage, _)| name.len() + usage.len() + 1)
+        .max()
+        .unwrap_or(0);
+    commands
+        .iter()
+        .map(|(name, usage, help)| {
+            let command = format!("{name} {usage}");
+            format!("{:width$}  {help}\n", command.trim_end())
+        })
+        .collect()
+}
+
+/// What to print for one command, or None to stop.
+fn command(puzzle: &dyn Explore, words: &[&str]) -> Option<Result<String, SolveError>> {
+    let reply = match words {
+        ["quit" | "exit"] => return None,
+        ["help"] => Ok(help(puzzle)),
+        [part @ ("part1" | "part2")] => {
+            let part = if *part == "part1" { 1 } else { 2 };
+            let start = Instant::now();
+            puzzle.solve(part).map(|answer| {
+                let time = format_duration(start.elapsed());
+                format!("part {part}: {answer} ({time})\n")
+            })
+        }
+        ["picture", path] => match puzzle.picture() {
+            Some(svg) => svg
+                .save(Path::new(path))
+                .map(|()| format!("wrote {path}\n"))
+                .map_err(SolveError::failed),
+            None => Err(SolveError::failed("this day doesn't draw a picture")),
+        },
+        [name, args @ ..] => match puzzle.probe(name, args) {
+            Some(reply) => reply.map(|mut reply| {
+                if !reply.ends_with('\n') {
+                    reply.push('\n');
+                }
+                reply
+            }),
+            None => Err(SolveError::failed(format!(
+                "unknown command {name}; try help"
+            ))),
+        },
+        [] => Ok(String::new()),
+    };
+    Some(reply)
+}
+
+/// Reads commands from `input` one line at a time until it ends or says to quit, writing what
+/// each one gives to `out`. A command that fails doesn't stop it.
+pub fn repl(puzzle: &dyn Explore, mut input: impl BufRead, mut out: impl Write) -> io::Result<()> {
+    let mut line = String::new();
+    loop {
+        write!(out, "> ")?;
+        out.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match command(puzzle, &words) {
+            Some(Ok(reply)) => write!(out, "{reply}")?,
+            Some(Err(e)) => writeln!(out, "error: {e}")?,
+            None => return Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_repl() {
+    use aoc_core::Probe;
+
+    struct Numbers(Vec<i64>);
+
+    impl Solver for Numbers {
+        fn parse(input: &str) -> Result<Self, SolveError> {
+            aoc_core::parse_numbers(input)
+                .map(Numbers)
+                .map_err(SolveError::failed)
+        }
+
+        fn part1(&self) -> Result<String, SolveError> {
+            Ok(self.0.iter().sum::<i64>().to_string())
+        }
+
+        fn part2(&self) -> Result<String, SolveError> {
+            Err(SolveError::NotImplemented)
+        }
+
+        fn probes() -> Vec<Probe<Self>> {
+            vec![Probe {
+                name: "above",
+                usage: "<n>",
+                help: "count the numbers above n",
+                run: |numbers, args| {
+                    let n: i64 = args.get(0, "n")?;
+                    Ok(numbers.0.iter().filter(|&&x| x > n).count().to_string())
+                },
+            }]
+        }
+    }
+
+    let numbers = Numbers::parse("3 8 1 9").unwrap();
+    let input = "help\nabove 2\n\nabove\nabove x\npart2\npicture out.svg\nnothing\nquit\npart1\n";
+    let mut out = Vec::new();
+    repl(&numbers, input.as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let expected = "\
+> help            list the commands
+part1           solve part 1
+part2           solve part 2
+picture <file>  draw the puzzle to an SVG file
+quit            stop exploring
+above <n>       count the numbers above n
+> 3
+> > error: usage: above <n>
+> error: can't read n \"x\"
+> error: not implemented
+> error: this day doesn't draw a picture
+> error: unknown command nothing; try help
+> ";
+    assert_eq!(out, expected);
+
+    let mut out = Vec::new();
+    repl(&numbers, "part1".as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("> part 1: 21 ("), "{out}");
+    assert!(out.ends_with(")\n> \n"), "{out}");
+}
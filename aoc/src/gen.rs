@@ -0,0 +1,293 @@
+use rand::{
+    rngs::Xoshiro256PlusPlus,
+    seq::{IndexedRandom, SliceRandom},
+    Rng, RngExt, SeedableRng,
+};
+
+/// Days of the first year there's a generator for.
+pub const DAYS: [u8; 9] = [1, 2, 7, 9, 12, 15, 18, 22, 24];
+
+/// A random, valid puzzle input for `day` with roughly `size` lines, or `size` steps for day 15.
+/// The same seed always makes the same input. None if there's no generator for the day.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut Xoshiro256PlusPlus::seed_from_u64(seed);
+    Some(match day {
+        1 => calibration_document(rng, size),
+        2 => game_record(rng, size),
+        7 => camel_cards(rng, size),
+        9 => report(rng, size),
+        12 => condition_records(rng, size),
+        15 => init_sequence(rng, size),
+        18 => dig_plan(rng, size),
+        22 => bricks(rng, size),
+        24 => hailstones(rng, size),
+        _ => return None,
+    })
+}
+
+/// One line per item, each ending in a newline.
+fn lines(size: usize, mut line: impl FnMut(usize) -> String) -> String {
+    (0..size).map(|i| line(i) + "\n").collect()
+}
+
+/// Letters, digits, and spelled out digits, with at least one real digit on every line.
+fn calibration_document(rng: &mut impl Rng, size: usize) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    lines(size, |_| {
+        let mut tokens: Vec<String> = (0..rng.random_range(0..6))
+            .map(|_| match rng.random_range(0..3) {
+                0 => rng.random_range(1..=9).to_string(),
+                1 => WORDS.choose(rng).unwrap().to_string(),
+                _ => (0..rng.random_range(1..4))
+                    .map(|_| rng.random_range('a'..='z'))
+                    .collect(),
+            })
+            .collect();
+        let at = rng.random_range(0..=tokens.len());
+        tokens.insert(at, rng.random_range(1..=9).to_string());
+        tokens.concat()
+    })
+}
+
+fn game_record(rng: &mut impl Rng, size: usize) -> String {
+    lines(size, |i| {
+        let rounds: Vec<String> = (0..rng.random_range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                colors.shuffle(rng);
+                colors[..rng.random_range(1..=3)]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.random_range(1..=20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        format!("Game {}: {}", i + 1, rounds.join("; "))
+    })
+}
+
+fn camel_cards(rng: &mut impl Rng, size: usize) -> String {
+    const CARDS: &[u8] = b"23456789TJQKA";
+    lines(size, |_| {
+        let hand: String = (0..5)
+            .map(|_| *CARDS.choose(rng).unwrap() as char)
+            .collect();
+        format!("{hand} {}", rng.random_range(1..=1000))
+    })
+}
+
+/// Polynomials of low degree sampled at 0 to 20, like the real input's sequences.
+fn report(rng: &mut impl Rng, size: usize) -> String {
+    lines(size, |_| {
+        let coefficients: Vec<i64> = (0..=rng.random_range(0..=5))
+            .map(|_| rng.random_range(-5..=5))
+            .collect();
+        (0..=20)
+            .map(|x: i64| {
+                let y = coefficients.iter().rev().fold(0, |y, c| y * x + c);
+                y.to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+/// Random rows of springs with their groups, with some of the springs hidden.
+fn condition_records(rng: &mut impl Rng, size: usize) -> String {
+    lines(size, |_| {
+        let mut springs: Vec<bool> = (0..rng.random_range(1..=20))
+            .map(|_| rng.random_bool(0.5))
+            .collect();
+        let at = rng.random_range(0..springs.len());
+        springs[at] = true;
+        let groups: Vec<String> = springs
+            .split(|&broken| !broken)
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect();
+        let row: String = springs
+            .iter()
+            .map(|&broken| match (rng.random_bool(0.4), broken) {
+                (true, _) => '?',
+                (false, true) => '#',
+                (false, false) => '.',
+            })
+            .collect();
+        format!("{row} {}", groups.join(","))
+    })
+}
+
+/// Steps on a few distinct labels, so lenses get replaced and removed.
+fn init_sequence(rng: &mut impl Rng, size: usize) -> String {
+    let labels: Vec<String> = (0..size / 4 + 1)
+        .map(|_| {
+            (0..rng.random_range(1..=6))
+                .map(|_| rng.random_range('a'..='z'))
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.random_bool(0.3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.random_range(1..=9))
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+/// The moves around a histogram with the given column widths and heights: up or down to each
+/// column's height and right across it, then down to the start and back left.
+fn histogram_outline(widths: &[u32], heights: &[u32]) -> Vec<(char, u32)> {
+    let mut moves = vec![];
+    let mut height = 0;
+    for (&width, &next_height) in widths.iter().zip(heights) {
+        if next_height > height {
+            moves.push(('U', next_height - height));
+        } else {
+            moves.push(('D', height - next_height));
+        }
+        moves.push(('R', width));
+        height = next_height;
+    }
+    moves.push(('D', height));
+    moves.push(('L', widths.iter().sum()));
+    moves
+}
+
+/// Random heights, each different from the one before and the first different from 0.
+fn random_heights(rng: &mut impl Rng, columns: usize, max: u32) -> Vec<u32> {
+    let mut heights: Vec<u32> = vec![];
+    while heights.len() < columns {
+        let height = rng.random_range(1..=max);
+        if heights.last() != Some(&height) {
+            heights.push(height);
+        }
+    }
+    heights
+}
+
+/// Two histogram shaped loops with the same number of moves, one in the directions and
+/// lengths for part 1 and one in the colors for part 2.
+fn dig_plan(rng: &mut impl Rng, size: usize) -> String {
+    fn outline(rng: &mut impl Rng, columns: usize, max: u32) -> Vec<(char, u32)> {
+        let max_width = max.min(0xfffff / columns as u32).max(1);
+        let widths: Vec<u32> = (0..columns)
+            .map(|_| rng.random_range(1..=max_width))
+            .collect();
+        histogram_outline(&widths, &random_heights(rng, columns, max))
+    }
+    // The colors only have 5 hex digits, which the move back along the bottom has to fit in.
+    let columns = (size / 2).clamp(1, 0xfffff);
+    let part1 = outline(rng, columns, 10);
+    let part2 = outline(rng, columns, 100_000);
+    part1
+        .iter()
+        .zip(&part2)
+        .map(|(&(dir, len), &(color_dir, color_len))| {
+            let color_dir = "RDLU".find(color_dir).unwrap();
+            format!("{dir} {len} (#{color_len:05x}{color_dir})\n")
+        })
+        .collect()
+}
+
+/// Bricks dropped at random into a 10 by 10 area, each starting above everything under it so
+/// none overlap, listed in a random order.
+fn bricks(rng: &mut impl Rng, size: usize) -> String {
+    const WIDTH: usize = 10;
+    let mut tops = [[0; WIDTH]; WIDTH];
+    let mut bricks: Vec<String> = (0..size)
+        .map(|_| {
+            let axis = rng.random_range(0..3);
+            let len = rng.random_range(0..4);
+            let [x_len, y_len, z_len] = std::array::from_fn(|i| if i == axis { len } else { 0 });
+            let x = rng.random_range(0..WIDTH - x_len);
+            let y = rng.random_range(0..WIDTH - y_len);
+            let covered = || (x..=x + x_len).flat_map(|x| (y..=y + y_len).map(move |y| (x, y)));
+            let below = covered().map(|(x, y)| tops[x][y]).max().unwrap();
+            let z = below + 1 + rng.random_range(0..3);
+            for (x, y) in covered() {
+                tops[x][y] = z + z_len;
+            }
+            format!("{x},{y},{z}~{},{},{}", x + x_len, y + y_len, z + z_len)
+        })
+        .collect();
+    bricks.shuffle(rng);
+    bricks.iter().map(|brick| format!("{brick}\n")).collect()
+}
+
+/// Hailstones that a single rock throw hits, each at a different time, at about the real
+/// input's scale.
+fn hailstones(rng: &mut impl Rng, size: usize) -> String {
+    let rock_position: [i64; 3] =
+        std::array::from_fn(|_| rng.random_range(200..400) * 1_000_000_000_000);
+    let rock_velocity: [i64; 3] = std::array::from_fn(|_| rng.random_range(-300..=300));
+    let mut times: Vec<i64> = vec![];
+    while times.len() < size {
+        let time = rng.random_range(100_000_000_000..1_000_000_000_000);
+        if !times.contains(&time) {
+            times.push(time);
+        }
+    }
+    lines(size, |i| {
+        let velocity = loop {
+            let velocity: [i64; 3] = std::array::from_fn(|_| rng.random_range(-300..=300));
+            if velocity != rock_velocity {
+                break velocity;
+            }
+        };
+        let position: [i64; 3] = std::array::from_fn(|axis| {
+            rock_position[axis] + (rock_velocity[axis] - velocity[axis]) * times[i]
+        });
+        let [px, py, pz] = position;
+        let [vx, vy, vz] = velocity;
+        format!("{px}, {py}, {pz} @ {vx}, {vy}, {vz}")
+    })
+}
+
+#[test]
+fn test_generated_inputs_solve() {
+    use aoc_core::SolveError;
+
+    for day in DAYS {
+        let input = generate(day, 20, 1).unwrap();
+        let run = crate::run::run_day(2023, day, &input, &[1, 2], &Default::default());
+        let parts = run
+            .parts
+            .unwrap_or_else(|e| panic!("day {day}: {e}\n{input}"));
+        for part in parts {
+            assert!(
+                !matches!(part.answer, Err(ref e) if *e != SolveError::NotImplemented),
+                "day {day} part {}: {:?}\n{input}",
+                part.part,
+                part.answer
+            );
+        }
+    }
+}
+
+#[test]
+fn test_generate() {
+    assert_eq!(generate(18, 50, 7), generate(18, 50, 7));
+    assert_ne!(generate(18, 50, 7), generate(18, 50, 8));
+    assert_eq!(generate(22, 30, 0).unwrap().lines().count(), 30);
+    assert_eq!(generate(3, 10, 0), None);
+    // Wide enough that the colors' lengths would overflow if the columns weren't narrowed.
+    let run = crate::run::run_day(
+        2023,
+        18,
+        &generate(18, 100, 0).unwrap(),
+        &[2],
+        &Default::default(),
+    );
+    assert!(run.part(2).unwrap().answer.is_ok());
+    assert_eq!(
+        histogram_outline(&[2, 3], &[4, 1]),
+        [('U', 4), ('R', 2), ('D', 3), ('R', 3), ('D', 1), ('L', 5)]
+    );
+}
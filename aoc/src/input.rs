@@ -0,0 +1,130 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::{examples, InputSource};
+use thiserror::Error;
+
+use crate::{
+    client::{Client, ClientError, SESSION_VAR},
+    year,
+};
+
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("couldn't read {input}: {source}")]
+    Read {
+        input: InputSource,
+        source: io::Error,
+    },
+    #[error("{} doesn't exist; set {SESSION_VAR} to download it", path.display())]
+    NoSession { path: PathBuf },
+    #[error("couldn't download day {day} of {year}'s input: {source}")]
+    Download {
+        year: u16,
+        day: u8,
+        source: ClientError,
+    },
+    #[error("couldn't save the input to {}: {source}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+    #[error("day {day} doesn't have an example {n}, only {count}")]
+    NoExample { day: u8, n: u8, count: u8 },
+}
+
+/// Where a day's own binary looks for its input, relative to the repository root.
+fn default_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    let dir = root.join(year::day_path(year, day));
+    [
+        dir.join(if (year, day) == (year::FIRST_YEAR, 6) {
+            "input1.txt"
+        } else {
+            "input.txt"
+        }),
+        dir.join("src/input.txt"),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+    .unwrap_or_else(|| dir.join("input.txt"))
+}
+
+/// Reads a day's input from the day's directory under `root`, downloading it with `client`
+/// and saving it there first if it isn't there yet, even if the day hasn't been started.
+pub fn load(
+    root: &Path,
+    year: u16,
+    day: u8,
+    client: Option<&Client>,
+) -> Result<String, InputError> {
+    let path = default_path(root, year, day);
+    if !path.exists() {
+        let Some(client) = client else {
+            return Err(InputError::NoSession { path });
+        };
+        let input = client
+            .input(year, day)
+            .map_err(|source| InputError::Download { year, day, source })?;
+        let dir = path.parent().unwrap_or(root);
+        std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::write(&path, &input))
+            .map_err(|source| InputError::Write {
+                path: path.clone(),
+                source,
+            })?;
+        return Ok(input);
+    }
+    read(&InputSource::File(path))
+}
+
+/// The input of `day`'s example numbered `n`.
+pub fn example(day: u8, n: u8) -> Result<String, InputError> {
+    examples::find(day, n)
+        .map(|example| example.input.to_string())
+        .ok_or_else(|| InputError::NoExample {
+            day,
+            n,
+            count: examples::count(day),
+        })
+}
+
+pub fn read(input: &InputSource) -> Result<String, InputError> {
+    input.read().map_err(|source| InputError::Read {
+        input: input.clone(),
+        source,
+    })
+}
+
+#[test]
+fn test_load() {
+    let root = std::env::temp_dir().join(format!("aoc-input-test-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("day3")).unwrap();
+    std::fs::create_dir_all(root.join("day6")).unwrap();
+    std::fs::write(root.join("day3/input.txt"), "467..114..\n").unwrap();
+    std::fs::write(root.join("day6/input1.txt"), "Time: 7\n").unwrap();
+
+    std::fs::create_dir_all(root.join("2024/day6")).unwrap();
+    std::fs::write(root.join("2024/day6/input.txt"), "3   4\n").unwrap();
+
+    assert_eq!(load(&root, 2023, 3, None).unwrap(), "467..114..\n");
+    assert_eq!(load(&root, 2023, 6, None).unwrap(), "Time: 7\n");
+    assert_eq!(load(&root, 2024, 6, None).unwrap(), "3   4\n");
+    assert!(matches!(
+        load(&root, 2023, 4, None),
+        Err(InputError::NoSession { path }) if path == root.join("day4/input.txt")
+    ));
+    assert!(matches!(
+        load(&root, 2024, 3, None),
+        Err(InputError::NoSession { path }) if path == root.join("2024/day3/input.txt")
+    ));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_example() {
+    assert!(example(8, 3).unwrap().starts_with("LR\n\n11A"));
+    assert_eq!(
+        example(8, 4).unwrap_err().to_string(),
+        "day 8 doesn't have an example 4, only 3"
+    );
+}
@@ -0,0 +1,595 @@
+#[cfg(feature = "memory")]
+mod alloc;
+mod answers;
+mod cache;
+mod client;
+mod config;
+mod explore;
+mod gen;
+mod input;
+mod report;
+mod run;
+mod scaffold;
+mod serve;
+mod status;
+mod timing;
+mod year;
+
+use std::{
+    fmt::Write as _,
+    io::Write as _,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use answers::Answers;
+use aoc_core::{InputSource, SolveError, StateDump};
+use cache::Cache;
+use clap::{ArgAction, Parser, Subcommand};
+use client::{Client, Verdict};
+use config::Config;
+use input::InputError;
+use rayon::prelude::*;
+use run::{DayRun, RunOptions};
+use status::Status;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: alloc::PeakAlloc = alloc::PeakAlloc::new();
+
+/// Runs any day's solution on its input.
+#[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Days to run, from 1 to 25
+    #[arg(
+        long,
+        required = true,
+        value_delimiter = ',',
+        value_parser = clap::value_parser!(u8).range(1..=25)
+    )]
+    day: Vec<u8>,
+
+    /// Year whose puzzles to use; the days of years after the first are in a directory named
+    /// after the year, along with their settings and saved answers
+    #[arg(
+        long,
+        global = true,
+        default_value_t = year::FIRST_YEAR,
+        value_parser = clap::value_parser!(u16).range(2015..)
+    )]
+    year: u16,
+
+    /// Part to run, instead of both
+    #[arg(long, global = true, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input to use instead of the day's `input.txt`, which is downloaded if it's missing
+    /// and AOC_SESSION is set, or - for stdin
+    #[arg(long, value_name = "FILE")]
+    input: Option<InputSource>,
+
+    /// Run on the example numbered N from the day's puzzle description instead of its input
+    #[arg(long, value_name = "N", conflicts_with_all = ["input", "submit"])]
+    example: Option<u8>,
+
+    /// Submit each answer to adventofcode.com, using the session in AOC_SESSION, unless the
+    /// answers submitted before show what it would say
+    #[arg(long)]
+    submit: bool,
+
+    /// Report how long parsing and each part took, and the most memory used if built with the
+    /// `memory` feature, with a table of every day at the end if there's more than one
+    #[arg(long, global = true)]
+    time: bool,
+
+    /// Settings for the days to use instead of their defaults, which are read from aoc.toml if
+    /// it exists
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Have each day write state from partway through solving, for the days that have any, to
+    /// JSON files in a directory for the day under DIR
+    #[arg(long, global = true, value_name = "DIR")]
+    dump_state: Option<PathBuf>,
+
+    /// Give up on a part once it's taken this long, like 60s or 2m, reporting how far it got
+    /// if the day keeps track
+    #[arg(long, global = true, value_name = "DURATION", value_parser = timing::parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Solve each part again the slow, obvious way, for the days that have one, and report
+    /// whether the answers agree; best with small inputs like the examples
+    #[arg(long, global = true)]
+    verify: bool,
+
+    /// Solve every part, even ones whose answer on the same input is cached from before
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// How many threads to share between days run at once and days that solve in parallel,
+    /// instead of one per core
+    #[arg(long, global = true, env = "AOC_THREADS", value_name = "N")]
+    threads: Option<NonZeroUsize>,
+
+    /// Log what the days are doing to stderr; repeat for more detail
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Print nothing but the answers, in order of day, so that runs can be compared with diff;
+    /// errors are still printed on stderr
+    #[arg(short, long, global = true, conflicts_with_all = ["verbose", "time"])]
+    quiet: bool,
+}
+
+impl Args {
+    /// Where to write a report of the days run, if one was asked for.
+    fn report(&self) -> Option<&Path> {
+        match &self.command {
+            Some(Command::Report { out }) => Some(out),
+            _ => None,
+        }
+    }
+
+    /// The parts to run.
+    fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs every day at once, printing each one's answers as soon as it finishes. Peak memory
+    /// isn't reported, since the days share it.
+    All,
+    /// Prints a random input for a day, to try the solutions on bigger inputs than the real one
+    Gen {
+        /// Day to make an input for
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Seed for the random numbers; the same seed always makes the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Roughly how many lines to make
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+    },
+    /// Creates a new day's crate with a stub solver, and adds it to the workspace, the runner
+    /// and the other front ends
+    NewDay {
+        /// Day to create
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Runs the days given with --day, or every day, and writes a page with their answers,
+    /// timings and pictures of the days that draw one, which can be opened without anything
+    /// else
+    Report {
+        /// File to write the page to
+        #[arg(long, default_value = "report.html")]
+        out: PathBuf,
+    },
+    /// Loads the input of the one day given with --day and reads commands for looking into
+    /// it, like solving a part or running one of the day's probes; `help` lists them
+    Explore,
+    /// Prints which parts were solved, stubbed out, failed or timed out the last time they
+    /// were run, and which have had an answer accepted by adventofcode.com
+    Status,
+    /// Serves the solutions over HTTP: `POST /solve/{day}/{part}` with the puzzle input as the
+    /// body replies with the answer and timings as JSON
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8000")]
+        addr: String,
+    },
+}
+
+/// Where the answers submitted so far are kept, relative to the year's directory.
+const ANSWERS_PATH: &str = "answers.txt";
+
+/// Where the days' settings are read from without --config, relative to the year's directory.
+const CONFIG_PATH: &str = "aoc.toml";
+
+/// Where answers are cached between runs, relative to the year's directory.
+const CACHE_PATH: &str = "cache.txt";
+
+/// Where how each part went the last time it was run is kept, relative to the year's directory.
+const STATUS_PATH: &str = "status.txt";
+
+/// Submits `answer` unless the verdict is already known or it's too soon, recording what
+/// adventofcode.com says.
+fn submit(client: &Client, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+    let path = &year::dir(Path::new("."), year).join(ANSWERS_PATH);
+    let mut answers = Answers::load(path).map_err(|e| e.to_string())?;
+    if let Some(verdict) = answers.check(day, part, answer) {
+        return Ok(verdict);
+    }
+    if let Some(wait) = answers.wait_remaining() {
+        return Ok(Verdict::TooSoon(wait));
+    }
+    let verdict = client
+        .submit(year, day, part, answer)
+        .map_err(|e| e.to_string())?;
+    answers.record(day, part, answer, verdict);
+    answers.save(path).map_err(|e| e.to_string())?;
+    Ok(verdict)
+}
+
+/// What to print for one day, kept together so days running at once don't interleave.
+struct DayOutput {
+    day: u8,
+    out: String,
+    errors: String,
+    /// Printed on stderr like errors, but without making the run fail
+    warnings: String,
+    run: Option<DayRun>,
+    /// The [`cache::input_hash`] of the input, if there was one
+    input_hash: Option<u64>,
+}
+
+impl DayOutput {
+    fn failed(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    fn print(&self, heading: bool) {
+        let mut stdout = std::io::stdout().lock();
+        let mut stderr = std::io::stderr().lock();
+        if heading {
+            let _ = writeln!(stdout, "day {}", self.day);
+        }
+        let _ = write!(stderr, "{}", self.warnings);
+        let _ = write!(stdout, "{}", self.out);
+        let _ = stdout.flush();
+        let _ = write!(stderr, "{}", self.errors);
+    }
+}
+
+/// Runs one day on its input, skipping parts with answers in `cache`, warning about parts
+/// `status` says aren't finished, and submitting the answers with `client` if it's given.
+#[allow(clippy::too_many_arguments)]
+fn run_day(
+    day: u8,
+    input: Result<String, InputError>,
+    config: &Config,
+    cache: &Cache,
+    status: &Status,
+    client: Option<&Client>,
+    args: &Args,
+    track_memory: bool,
+) -> DayOutput {
+    let mut output = DayOutput {
+        day,
+        out: String::new(),
+        errors: String::new(),
+        warnings: String::new(),
+        run: None,
+        input_hash: None,
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            writeln!(output.errors, "error: {e}").unwrap();
+            return output;
+        }
+    };
+
+    let mut options = RunOptions {
+        params: config.params(day),
+        track_memory,
+        dump: None,
+        time_limit: args.timeout,
+        verify: args.verify,
+        picture: args.report().is_some(),
+        explore: None,
+    };
+    output.input_hash = Some(cache::input_hash(&input, &options.params));
+    let parts = &args.parts();
+    for &part in parts {
+        if let Some(warning) = status.warning(day, part) {
+            writeln!(output.warnings, "warning: {warning}").unwrap();
+        }
+    }
+    let run = match &args.dump_state {
+        // The cache is skipped, since the state is only dumped while solving
+        Some(dir) => {
+            let dump = StateDump::new(dir.join(format!("day{day}")));
+            options.dump = Some(&dump);
+            run::run_day(args.year, day, &input, parts, &options)
+        }
+        // Likewise, cached answers can't be checked, and reports show how long solving takes
+        None if args.verify || options.picture => {
+            run::run_day(args.year, day, &input, parts, &options)
+        }
+        None => cache::run_day(cache, args.year, day, &input, parts, &options),
+    };
+    if let Err(e) = &run.parts {
+        writeln!(output.errors, "error: {e}").unwrap();
+    }
+    for part in run.parts.iter().flatten() {
+        let n = part.part;
+        match &part.answer {
+            Ok(answer) => match client {
+                Some(client) => match submit(client, args.year, day, n, answer) {
+                    Ok(verdict) => writeln!(output.out, "part {n}: {answer} ({verdict})").unwrap(),
+                    Err(e) => {
+                        writeln!(output.out, "part {n}: {answer}").unwrap();
+                        writeln!(output.errors, "part {n}: couldn't submit: {e}").unwrap();
+                    }
+                },
+                None => writeln!(output.out, "part {n}: {answer}").unwrap(),
+            },
+            Err(e) => writeln!(output.errors, "part {n}: error: {e}").unwrap(),
+        }
+        match (&part.naive, &part.answer) {
+            (None, _) => {}
+            (Some(Ok(naive)), Ok(answer)) if naive == answer => {
+                if !args.quiet {
+                    writeln!(output.out, "part {n}: the naive way agrees").unwrap();
+                }
+            }
+            (Some(Ok(naive)), _) => {
+                writeln!(output.errors, "part {n}: the naive way got {naive}").unwrap()
+            }
+            (Some(Err(SolveError::NotImplemented)), _) => {
+                if !args.quiet {
+                    writeln!(output.out, "part {n}: there's no naive way to check it").unwrap();
+                }
+            }
+            (Some(Err(e)), _) => {
+                writeln!(output.errors, "part {n}: the naive way failed: {e}").unwrap()
+            }
+        }
+    }
+    if args.time {
+        writeln!(output.out, "time: {}", timing::day_report(&run)).unwrap();
+    }
+    output.run = Some(run);
+    output
+}
+
+/// Loads one day's input and lets someone explore the parsed puzzle from stdin until they're
+/// done, or says why it couldn't be parsed.
+fn explore_day(
+    day: u8,
+    input: Result<String, InputError>,
+    config: &Config,
+    args: &Args,
+) -> Result<(), String> {
+    let input = input.map_err(|e| e.to_string())?;
+    let repl = |puzzle: &dyn explore::Explore| {
+        let stdin = std::io::stdin().lock();
+        if let Err(e) = explore::repl(puzzle, stdin, std::io::stdout()) {
+            eprintln!("error: {e}");
+        }
+    };
+    let options = RunOptions {
+        params: config.params(day),
+        explore: Some(&repl),
+        ..Default::default()
+    };
+    let run = run::run_day(args.year, day, &input, &[], &options);
+    run.parts.map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.quiet {
+        aoc_core::init_quiet_logging();
+    } else {
+        aoc_core::init_logging(args.verbose);
+    }
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.get())
+            .build_global()
+            .expect("nothing should have used rayon's pool yet");
+    }
+    if let Some(Command::Gen { day, seed, size }) = args.command {
+        if args.year != year::FIRST_YEAR {
+            eprintln!("error: there are only generators for {}", year::FIRST_YEAR);
+            std::process::exit(1);
+        }
+        match gen::generate(day, size, seed) {
+            Some(input) => print!("{input}"),
+            None => {
+                eprintln!(
+                    "error: there's no generator for day {day}, only for days {:?}",
+                    gen::DAYS
+                );
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(Command::NewDay { day }) = args.command {
+        match scaffold::new_day(Path::new("."), args.year, day) {
+            Ok(changed) => {
+                for path in changed {
+                    println!("wrote {}", path.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(Command::Status) = args.command {
+        let dir = year::dir(Path::new("."), args.year);
+        let status = Status::load(&dir.join(STATUS_PATH));
+        let answers = Answers::load(&dir.join(ANSWERS_PATH));
+        match status.and_then(|status| Ok((status, answers?))) {
+            Ok((status, answers)) => print!("{}", status.table(&answers)),
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let config = match &args.config {
+        Some(path) => Config::load(path.clone(), false),
+        None => Config::load(year::dir(Path::new("."), args.year).join(CONFIG_PATH), true),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    });
+    if let Some(Command::Serve { addr }) = &args.command {
+        if let Err(e) = serve::serve(addr, args.year, &config, args.timeout) {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    let all = matches!(args.command, Some(Command::All));
+    let client = Client::from_env();
+    // Checked here rather than by clap, since --submit isn't one of the subcommands' arguments
+    if args.submit && args.verify {
+        eprintln!("error: --verify can't be used with --submit");
+        std::process::exit(1);
+    }
+    if args.submit && client.is_none() {
+        eprintln!("error: set {} to submit answers", client::SESSION_VAR);
+        std::process::exit(1);
+    }
+    // Reports are of every day unless they're given
+    let days: Vec<u8> = match args.report() {
+        Some(_) if args.day.is_empty() => (1..=25).collect(),
+        _ => args.day.clone(),
+    };
+    if args.input.is_some() && (all || days.len() > 1) {
+        eprintln!("error: --input can only be used with a single day");
+        std::process::exit(1);
+    }
+    if args.example.is_some() && all {
+        eprintln!("error: `all` can't be used with --example");
+        std::process::exit(1);
+    }
+    if args.example.is_some() && args.year != year::FIRST_YEAR {
+        eprintln!("error: there are only examples for {}", year::FIRST_YEAR);
+        std::process::exit(1);
+    }
+    if all && (args.submit || !args.day.is_empty()) {
+        eprintln!("error: `all` can't be used with --day or --submit");
+        std::process::exit(1);
+    }
+
+    let load = |day| match (&args.input, args.example) {
+        (Some(source), _) => input::read(source),
+        (None, Some(n)) => input::example(day, n),
+        (None, None) => input::load(Path::new("."), args.year, day, client.as_ref()),
+    };
+    if let Some(Command::Explore) = args.command {
+        let &[day] = &args.day[..] else {
+            eprintln!("error: `explore` needs exactly one --day");
+            std::process::exit(1);
+        };
+        if let Err(e) = explore_day(day, load(day), &config, &args) {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    let submit_client = client.as_ref().filter(|_| args.submit);
+    let cache_path = &year::dir(Path::new("."), args.year).join(CACHE_PATH);
+    // Not saved if it couldn't be loaded, so whatever's there isn't lost
+    let mut cache = Cache::load(cache_path)
+        .inspect_err(|e| eprintln!("warning: not caching answers: {e}"))
+        .ok();
+    let status_path = &year::dir(Path::new("."), args.year).join(STATUS_PATH);
+    let mut status = Status::load(status_path)
+        .inspect_err(|e| eprintln!("warning: not keeping track of which parts are finished: {e}"))
+        .ok();
+    let no_status = Status::default();
+    let last_status = status.as_ref().unwrap_or(&no_status);
+    let no_cache = Cache::default();
+    let lookup = cache
+        .as_ref()
+        .filter(|_| !args.no_cache)
+        .unwrap_or(&no_cache);
+    let outputs: Vec<DayOutput> = if all {
+        // Inputs are fetched one at a time so missing ones aren't all downloaded at once.
+        let inputs: Vec<_> = (1..=25).map(|day| (day, load(day))).collect();
+        let outputs: Vec<DayOutput> = inputs
+            .into_par_iter()
+            .map(|(day, input)| {
+                run_day(day, input, &config, lookup, last_status, None, &args, false)
+            })
+            .inspect(|output| {
+                if !args.quiet {
+                    output.print(true);
+                }
+            })
+            .collect();
+        // Printed once they've all finished, since they finish in a different order each time
+        if args.quiet {
+            outputs.iter().for_each(|output| output.print(true));
+        }
+        outputs
+    } else {
+        days.iter()
+            .map(|&day| {
+                let output = run_day(
+                    day,
+                    load(day),
+                    &config,
+                    lookup,
+                    last_status,
+                    submit_client,
+                    &args,
+                    true,
+                );
+                output.print(days.len() > 1);
+                output
+            })
+            .collect()
+    };
+
+    // Nothing ran if none of the inputs could be read, which for a new year might be because
+    // its directory doesn't exist yet
+    let ran = outputs.iter().any(|output| output.run.is_some());
+    if let Some(cache) = cache.as_mut().filter(|_| ran) {
+        for output in &outputs {
+            if let (Some(run), Some(hash)) = (&output.run, output.input_hash) {
+                cache.record(run, hash);
+            }
+        }
+        if let Err(e) = cache.save(cache_path) {
+            eprintln!("warning: couldn't save the answer cache: {e}");
+        }
+    }
+    if let Some(status) = status.as_mut().filter(|_| ran) {
+        for run in outputs.iter().filter_map(|o| o.run.as_ref()) {
+            status.record(run);
+        }
+        if let Err(e) = status.save(status_path) {
+            eprintln!("warning: couldn't save which parts are finished: {e}");
+        }
+    }
+
+    let failed = outputs.iter().any(DayOutput::failed);
+    let runs: Vec<DayRun> = outputs.into_iter().filter_map(|o| o.run).collect();
+    if args.time && runs.len() > 1 {
+        println!();
+        print!("{}", timing::summary_table(&runs));
+    }
+    if let Some(path) = args.report() {
+        if let Err(e) = std::fs::write(path, report::html(args.year, &runs)) {
+            eprintln!("error: couldn't write {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
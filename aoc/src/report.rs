@@ -0,0 +1,165 @@
+use std::{fmt::Write as _, time::Duration};
+
+use crate::{
+    run::DayRun,
+    timing::{format_duration, part_time},
+};
+
+/// Keeps the page readable on its own, with the pictures shrunk to fit narrow windows.
+const STYLE: &str =
+    "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { text-align: right; font-variant-numeric: tabular-nums; }
+code { white-space: pre; }
+.error { color: #b00; }
+svg { max-width: 100%; height: auto; border: 1px solid #ddd; }";
+
+/// `text` with the characters that mean something in HTML replaced by entities.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// The table cells for one day: each part's answer or what went wrong, and how long it took,
+/// or what went wrong before either part could be solved.
+fn part_cells(run: &DayRun) -> String {
+    let parts = match &run.parts {
+        Ok(parts) => parts,
+        Err(e) => {
+            return format!(
+                r#"<td class="error" colspan="4">{}</td>"#,
+                escape(&e.to_string())
+            )
+        }
+    };
+    let mut cells = String::new();
+    for part in 1..=2 {
+        let Some(p) = parts.iter().find(|p| p.part == part) else {
+            cells.push_str(r#"<td>-</td><td class="time"></td>"#);
+            continue;
+        };
+        match &p.answer {
+            Ok(answer) => write!(cells, "<td><code>{}</code></td>", escape(answer)).unwrap(),
+            Err(e) => write!(
+                cells,
+                r#"<td class="error">{}</td>"#,
+                escape(&e.to_string())
+            )
+            .unwrap(),
+        }
+        write!(cells, r#"<td class="time">{}</td>"#, part_time(p)).unwrap();
+    }
+    cells
+}
+
+/// A page showing every day in `runs`: a table of their answers and timings, then the
+/// pictures of the days that drew one, all in the one file with nothing to fetch.
+pub fn html(year: u16, runs: &[DayRun]) -> String {
+    let mut page = String::new();
+    let title = format!("Advent of Code {year}");
+    writeln!(page, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>").unwrap();
+    writeln!(page, "<meta charset=\"utf-8\">\n<title>{title}</title>").unwrap();
+    writeln!(page, "<style>\n{STYLE}\n</style>\n</head>\n<body>").unwrap();
+    writeln!(page, "<h1>{title}</h1>\n<table>").unwrap();
+    writeln!(
+        page,
+        "<tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Time</th><th>Part 2</th><th>Time</th></tr>"
+    )
+    .unwrap();
+    let mut total = Duration::ZERO;
+    for run in runs {
+        let day = match &run.picture {
+            Some(_) => format!(r##"<a href="#day{0}">{0}</a>"##, run.day),
+            None => run.day.to_string(),
+        };
+        writeln!(
+            page,
+            r#"<tr><td>{day}</td><td class="time">{}</td>{}</tr>"#,
+            format_duration(run.parse_time),
+            part_cells(run)
+        )
+        .unwrap();
+        total += run.parse_time + run.parts.iter().flatten().map(|p| p.time).sum::<Duration>();
+    }
+    writeln!(
+        page,
+        "</table>\n<p>Total time {}</p>",
+        format_duration(total)
+    )
+    .unwrap();
+    for run in runs {
+        if let Some(picture) = &run.picture {
+            let day = run.day;
+            writeln!(page, "<h2 id=\"day{day}\">Day {day}</h2>\n{picture}").unwrap();
+        }
+    }
+    writeln!(page, "</body>\n</html>").unwrap();
+    page
+}
+
+#[test]
+fn test_html() {
+    use crate::run::PartRun;
+    use aoc_core::{SolveError, Svg};
+
+    let mut picture = Svg::new();
+    picture.rect(0.0, 0.0, 2.0, 1.0, "gold");
+    let runs = [
+        DayRun {
+            day: 10,
+            parse_time: Duration::from_micros(20),
+            parts: Ok(vec![
+                PartRun {
+                    part: 1,
+                    answer: Ok("<8>".to_string()),
+                    time: Duration::from_millis(2),
+                    cached: false,
+                    peak_memory: None,
+                    naive: None,
+                },
+                PartRun {
+                    part: 2,
+                    answer: Err(SolveError::failed("no loop & no start")),
+                    time: Duration::from_millis(1),
+                    cached: false,
+                    peak_memory: None,
+                    naive: None,
+                },
+            ]),
+            peak_memory: None,
+            picture: Some(picture),
+        },
+        DayRun {
+            day: 19,
+            parse_time: Duration::from_micros(5),
+            parts: Err(SolveError::NotImplemented),
+            peak_memory: None,
+            picture: None,
+        },
+    ];
+    let page = html(2023, &runs);
+    assert!(
+        page.contains("<title>Advent of Code 2023</title>"),
+        "{page}"
+    );
+    assert!(page.contains(
+        r##"<tr><td><a href="#day10">10</a></td><td class="time">20.0µs</td><td><code>&lt;8&gt;</code></td><td class="time">2.0ms</td><td class="error">no loop &amp; no start</td><td class="time">1.0ms</td></tr>"##
+    ), "{page}");
+    assert!(page.contains(
+        r#"<tr><td>19</td><td class="time">5.0µs</td><td class="error" colspan="4">not implemented</td></tr>"#
+    ), "{page}");
+    assert!(page.contains("<p>Total time 3.0ms</p>"), "{page}");
+    assert!(page.contains(
+        "<h2 id=\"day10\">Day 10</h2>\n<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 2 1\""
+    ), "{page}");
+}
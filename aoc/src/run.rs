@@ -0,0 +1,479 @@
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use aoc_core::{Cancel, Params, SolveError, Solver, StateDump, Svg};
+use tracing::info_span;
+
+use crate::explore::Explorer;
+
+/// How long past its time limit a part is waited for before it's abandoned, for parts that
+/// check the limit themselves to report how far they got.
+const GRACE: Duration = Duration::from_secs(1);
+
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<String, SolveError>,
+    pub time: Duration,
+    /// Whether the answer came from the cache instead of being solved
+    pub cached: bool,
+    /// Most bytes allocated at once while solving the part, beyond what already was, if
+    /// memory was tracked
+    pub peak_memory: Option<usize>,
+    /// What solving the part the naive way gave, if the answer was to be verified
+    pub naive: Option<Result<String, SolveError>>,
+}
+
+/// How one day went: how long parsing took, then each part's answer and time.
+pub struct DayRun {
+    pub day: u8,
+    pub parse_time: Duration,
+    /// The error parsing failed with, or how each part went
+    pub parts: Result<Vec<PartRun>, SolveError>,
+    /// Most bytes allocated at once while running the day, beyond what already was, if memory
+    /// was tracked
+    pub peak_memory: Option<usize>,
+    /// A picture of the puzzle, if one was asked for and the day has one
+    pub picture: Option<Svg>,
+}
+
+impl DayRun {
+    pub fn part(&self, part: u8) -> Option<&PartRun> {
+        self.parts.as_ref().ok()?.iter().find(|p| p.part == part)
+    }
+}
+
+/// How to run a day, apart from which parts.
+#[derive(Default)]
+pub struct RunOptions<'a> {
+    /// Settings changed from what the day's own binary uses
+    pub params: Params,
+    /// Whether to track peak memory, which only works if the runner is built with the
+    /// `memory` feature and can't be told apart from other days' when they run at the same time
+    pub track_memory: bool,
+    /// Where the day writes its state from partway through solving, before solving
+    pub dump: Option<&'a StateDump>,
+    /// How long each part can take before it's abandoned
+    pub time_limit: Option<Duration>,
+    /// Whether to solve each part that gets an answer again the naive way, to check it
+    pub verify: bool,
+    /// Whether to draw the puzzle once its parts are solved
+    pub picture: bool,
+    /// What to do with the parsed puzzle before solving, like letting someone explore it
+    pub explore: Option<Explorer<'a>>,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Memory allocated so far and the most allocated at once since the last call, which is None
+/// unless the runner is built with the `memory` feature.
+#[cfg(feature = "memory")]
+fn memory_mark() -> Option<(usize, usize)> {
+    let peak = crate::ALLOCATOR.peak();
+    Some((crate::ALLOCATOR.reset_peak(), peak))
+}
+
+#[cfg(not(feature = "memory"))]
+fn memory_mark() -> Option<(usize, usize)> {
+    None
+}
+
+/// Solves `part` of `puzzle`, the naive way if `naive` is set. With a `time_limit`, it's
+/// solved on a thread of its own that's cancelled and left behind if it's still going a little
+/// after the limit, since parts that don't check for cancelling can't be stopped.
+fn solve<S: Solver + Send + Sync + 'static>(
+    puzzle: &Arc<S>,
+    part: u8,
+    naive: bool,
+    time_limit: Option<Duration>,
+) -> Result<String, SolveError> {
+    let solve = move |puzzle: &S, cancel: &Cancel| {
+        if naive {
+            puzzle.naive(part)
+        } else {
+            puzzle.solve_cancellable(part, cancel)
+        }
+    };
+    let Some(limit) = time_limit else {
+        return solve(puzzle, &Cancel::new());
+    };
+    let (send, receive) = mpsc::channel();
+    let puzzle = Arc::clone(puzzle);
+    let cancel = Cancel::new();
+    let span = tracing::Span::current();
+    std::thread::spawn({
+        let cancel = cancel.clone();
+        move || {
+            let _span = span.entered();
+            // Nobody's listening any more if the part was abandoned
+            let _ = send.send(solve(&puzzle, &cancel));
+        }
+    });
+    match receive.recv_timeout(limit + GRACE) {
+        Ok(answer) => answer,
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            Err(SolveError::TimedOut { progress: None })
+        }
+        // The thread only hangs up without sending if the part panicked
+        Err(RecvTimeoutError::Disconnected) => Err(SolveError::failed("the part panicked")),
+    }
+}
+
+fn run<S: Solver + Send + Sync + 'static>(
+    day: u8,
+    input: &str,
+    parts: &[u8],
+    options: &RunOptions,
+) -> DayRun {
+    let _span = info_span!("day", day).entered();
+    let mark = || memory_mark().filter(|_| options.track_memory);
+    let start = mark();
+    // The most allocated at once since the day started
+    let mut day_peak = 0;
+    let (puzzle, parse_time) = timed(|| {
+        let mut puzzle = S::parse(input)?;
+        puzzle.configure(&options.params)?;
+        if let Some(limit) = options.time_limit {
+            puzzle.set_time_limit(limit);
+        }
+        Ok(puzzle)
+    });
+    let puzzle = puzzle.and_then(|puzzle| {
+        if let Some(dump) = options.dump {
+            puzzle.dump_state(dump)?;
+        }
+        Ok(Arc::new(puzzle))
+    });
+    let mut picture = None;
+    let parts = puzzle.map(|puzzle| {
+        if let Some(explore) = options.explore {
+            explore(&*puzzle);
+        }
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let _span = info_span!("part", part).entered();
+                let before = mark();
+                let (answer, time) = timed(|| solve(&puzzle, part, false, options.time_limit));
+                let after = mark();
+                let peak_memory = before.zip(after).map(|((current, earlier), (_, peak))| {
+                    day_peak = day_peak.max(earlier).max(peak);
+                    peak.saturating_sub(current)
+                });
+                let naive = (options.verify && answer.is_ok())
+                    .then(|| solve(&puzzle, part, true, options.time_limit));
+                PartRun {
+                    part,
+                    answer,
+                    time,
+                    cached: false,
+                    peak_memory,
+                    naive,
+                }
+            })
+            .collect();
+        if options.picture {
+            picture = puzzle.picture();
+        }
+        parts
+    });
+    let end = mark();
+    DayRun {
+        day,
+        parse_time,
+        parts,
+        peak_memory: start
+            .zip(end)
+            .map(|((current, _), (_, peak))| day_peak.max(peak).saturating_sub(current)),
+        picture,
+    }
+}
+
+/// Runs the given parts of one day of `year`, using the same settings as that day's own binary
+/// does unless `options` changes them.
+pub fn run_day(year: u16, day: u8, input: &str, parts: &[u8], options: &RunOptions) -> DayRun {
+    match (year, day) {
+        (2023, 1) => run::<day1::CalibrationDocument>(day, input, parts, options),
+        (2023, 2) => run::<day2::GameRecord>(day, input, parts, options),
+        (2023, 3) => run::<day3::Schematic>(day, input, parts, options),
+        (2023, 4) => run::<day4::Scratchcards>(day, input, parts, options),
+        (2023, 5) => run::<day5::Almanac>(day, input, parts, options),
+        (2023, 6) => run::<day6::RaceSheet>(day, input, parts, options),
+        (2023, 7) => run::<day7::CamelCards>(day, input, parts, options),
+        (2023, 8) => run::<day8::Maps>(day, input, parts, options),
+        (2023, 9) => run::<day9::Report>(day, input, parts, options),
+        (2023, 10) => run::<day10::Puzzle>(day, input, parts, options),
+        (2023, 11) => run::<day11::StarMap>(day, input, parts, options),
+        (2023, 12) => run::<day12::ConditionRecords>(day, input, parts, options),
+        (2023, 13) => run::<day13::Notes>(day, input, parts, options),
+        (2023, 14) => run::<day14::Platform>(day, input, parts, options),
+        (2023, 15) => run::<day15::InitSequence>(day, input, parts, options),
+        (2023, 16) => run::<day16::Contraption>(day, input, parts, options),
+        (2023, 17) => run::<day17::Map>(day, input, parts, options),
+        (2023, 18) => run::<day18::DigPlan>(day, input, parts, options),
+        (2023, 19) => run::<day19::Puzzle>(day, input, parts, options),
+        (2023, 20) => run::<day20::ModuleConfiguration>(day, input, parts, options),
+        (2023, 21) => run::<day21::GardenMap>(day, input, parts, options),
+        (2023, 22) => run::<day22::SupportGraph>(day, input, parts, options),
+        (2023, 23) => run::<day23::Maze>(day, input, parts, options),
+        (2023, 24) => run::<day24::Hailstones>(day, input, parts, options),
+        (2023, 25) => run::<day25::Graph>(day, input, parts, options),
+        _ => DayRun {
+            day,
+            parse_time: Duration::ZERO,
+            parts: Err(SolveError::NotImplemented),
+            peak_memory: None,
+            picture: None,
+        },
+    }
+}
+
+#[test]
+fn test_run_day() {
+    let tracked = RunOptions {
+        track_memory: true,
+        ..Default::default()
+    };
+    let run = run_day(
+        2023,
+        9,
+        "0 3 6 9 12 15\n1 3 6 10 15 21\n",
+        &[1, 2],
+        &tracked,
+    );
+    assert_eq!(run.part(1).unwrap().answer, Ok("46".to_string()));
+    assert_eq!(run.part(2).unwrap().answer, Ok("-3".to_string()));
+
+    let run = run_day(2023, 25, "jqt: rhn\n", &[2], &Default::default());
+    assert!(run.part(1).is_none());
+    assert_eq!(run.peak_memory, None);
+    assert_eq!(run.part(2).unwrap().answer, Err(SolveError::NotImplemented));
+
+    let run = run_day(2024, 1, "3   4\n", &[1], &Default::default());
+    assert_eq!(run.parts.err(), Some(SolveError::NotImplemented));
+
+    let run = run_day(2023, 19, "no blank line", &[1, 2], &tracked);
+    assert!(run.parts.is_err());
+
+    let mut expanded = RunOptions::default();
+    expanded.params.set("expansion", 10);
+    let stars = "...#......\n.......#..\n#.........\n..........\n......#...\n\
+                 .#........\n.........#\n..........\n.......#..\n#...#.....\n";
+    let run = run_day(2023, 11, stars, &[2], &expanded);
+    assert_eq!(run.part(2).unwrap().answer, Ok("1030".to_string()));
+
+    let run = run_day(2023, 9, "0 3 6\n", &[1], &expanded);
+    assert_eq!(
+        run.parts.err(),
+        Some(SolveError::failed(
+            "there's no setting expansion; this day doesn't have any"
+        ))
+    );
+}
+
+#[cfg(feature = "memory")]
+#[test]
+fn test_peak_memory() {
+    let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n".repeat(100);
+    let options = RunOptions {
+        track_memory: true,
+        ..Default::default()
+    };
+    let run = run_day(2023, 9, &input, &[1, 2], &options);
+    let day_peak = run.peak_memory.unwrap();
+    for part in run.parts.as_ref().unwrap() {
+        assert!(part.peak_memory.unwrap() <= day_peak);
+    }
+    assert!(day_peak > 0);
+}
+
+#[test]
+fn test_time_limit() {
+    let options = RunOptions {
+        time_limit: Some(Duration::ZERO),
+        ..Default::default()
+    };
+    // Day 9 doesn't check the limit, but finishes well within the grace period
+    let run = run_day(2023, 9, "0 3 6\n", &[1], &options);
+    assert_eq!(run.part(1).unwrap().answer, Ok("9".to_string()));
+
+    let run = run_day(2023, 23, aoc_core::examples::get(23, 1), &[1, 2], &options);
+    assert_eq!(run.part(1).unwrap().answer, Ok("94".to_string()));
+    assert!(matches!(
+        run.part(2).unwrap().answer,
+        Err(SolveError::TimedOut { .. })
+    ));
+
+    // A part that never finishes is abandoned
+    struct Stuck;
+    impl Solver for Stuck {
+        fn parse(_: &str) -> Result<Self, SolveError> {
+            Ok(Stuck)
+        }
+        fn part1(&self) -> Result<String, SolveError> {
+            std::thread::sleep(Duration::from_secs(3600));
+            Ok(String::new())
+        }
+        fn part2(&self) -> Result<String, SolveError> {
+            Ok("done".to_string())
+        }
+    }
+    let stuck = self::run::<Stuck>(1, "", &[1, 2], &options);
+    assert_eq!(
+        stuck.part(1).unwrap().answer,
+        Err(SolveError::TimedOut { progress: None })
+    );
+    assert_eq!(stuck.part(2).unwrap().answer, Ok("done".to_string()));
+
+    // A part that panics is reported as failing
+    struct Panicking;
+    impl Solver for Panicking {
+        fn parse(_: &str) -> Result<Self, SolveError> {
+            Ok(Panicking)
+        }
+        fn part1(&self) -> Result<String, SolveError> {
+            panic!("part 1 panicked");
+        }
+        fn part2(&self) -> Result<String, SolveError> {
+            Ok("done".to_string())
+        }
+    }
+    let options = RunOptions {
+        time_limit: Some(Duration::from_secs(60)),
+        ..Default::default()
+    };
+    let run = self::run::<Panicking>(1, "", &[1, 2], &options);
+    assert_eq!(
+        run.part(1).unwrap().answer,
+        Err(SolveError::failed("the part panicked"))
+    );
+    assert_eq!(run.part(2).unwrap().answer, Ok("done".to_string()));
+}
+
+#[test]
+fn test_cancel_abandoned_part() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+
+    /// Spins until it's cancelled, then says it stopped.
+    struct Spinning;
+    impl Solver for Spinning {
+        fn parse(_: &str) -> Result<Self, SolveError> {
+            Ok(Spinning)
+        }
+        fn part1(&self) -> Result<String, SolveError> {
+            self.solve_cancellable(1, &Cancel::new())
+        }
+        fn part2(&self) -> Result<String, SolveError> {
+            Err(SolveError::NotImplemented)
+        }
+        fn solve_cancellable(&self, _part: u8, cancel: &Cancel) -> Result<String, SolveError> {
+            while !cancel.is_cancelled() {
+                std::thread::yield_now();
+            }
+            STOPPED.store(true, Ordering::Relaxed);
+            Err(SolveError::TimedOut { progress: None })
+        }
+    }
+    let options = RunOptions {
+        time_limit: Some(Duration::ZERO),
+        ..Default::default()
+    };
+    let run = self::run::<Spinning>(1, "", &[1], &options);
+    assert_eq!(
+        run.part(1).unwrap().answer,
+        Err(SolveError::TimedOut { progress: None })
+    );
+    let start = Instant::now();
+    while !STOPPED.load(Ordering::Relaxed) {
+        assert!(start.elapsed() < Duration::from_secs(10), "never stopped");
+        std::thread::yield_now();
+    }
+}
+
+#[test]
+fn test_verify() {
+    let options = RunOptions {
+        verify: true,
+        ..Default::default()
+    };
+    let run = run_day(2023, 8, aoc_core::examples::get(8, 3), &[2], &options);
+    assert_eq!(run.part(2).unwrap().naive, Some(Ok("6".to_string())));
+    let run = run_day(2023, 8, aoc_core::examples::get(8, 1), &[1], &options);
+    assert_eq!(
+        run.part(1).unwrap().naive,
+        Some(Err(SolveError::NotImplemented))
+    );
+
+    // Day 22's part 2 is checked with the dominator tree instead of searching each chain
+    // reaction
+    let run = run_day(2023, 22, aoc_core::examples::get(22, 1), &[1, 2], &options);
+    assert_eq!(run.part(2).unwrap().answer, Ok("7".to_string()));
+    assert_eq!(run.part(2).unwrap().naive, Some(Ok("7".to_string())));
+    assert_eq!(
+        run.part(1).unwrap().naive,
+        Some(Err(SolveError::NotImplemented))
+    );
+
+    // Parts without an answer aren't checked
+    let run = run_day(2023, 20, aoc_core::examples::get(20, 1), &[2], &options);
+    assert!(run.part(2).unwrap().answer.is_err());
+    assert_eq!(run.part(2).unwrap().naive, None);
+    let run = run_day(2023, 9, "0 3 6\n", &[1], &Default::default());
+    assert_eq!(run.part(1).unwrap().naive, None);
+}
+
+#[test]
+fn test_examples() {
+    for example in aoc_core::examples::EXAMPLES {
+        for (part, expected) in (1..=2).zip(example.answers) {
+            let Some(expected) = expected else { continue };
+            let run = run_day(
+                2023,
+                example.day,
+                example.input,
+                &[part],
+                &Default::default(),
+            );
+            let answer = &run.part(part).unwrap().answer;
+            if *answer != Err(SolveError::NotImplemented) {
+                assert_eq!(
+                    *answer,
+                    Ok(expected.to_string()),
+                    "day {} example {} part {part}",
+                    example.day,
+                    example.n
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_explore() {
+    use crate::explore::Explore;
+    use std::cell::RefCell;
+
+    let replies = RefCell::new(Vec::new());
+    let explore = |puzzle: &dyn Explore| {
+        replies.borrow_mut().push(puzzle.probe("reachable", &["6"]));
+        replies.borrow_mut().push(puzzle.probe("nothing", &[]));
+    };
+    let options = RunOptions {
+        explore: Some(&explore),
+        ..Default::default()
+    };
+    let run = run_day(2023, 21, aoc_core::examples::get(21, 1), &[], &options);
+    assert!(run.parts.unwrap().is_empty());
+    assert_eq!(replies.into_inner(), [Some(Ok("16".to_string())), None]);
+}
@@ -0,0 +1,364 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::year;
+
+#[derive(Debug, Error)]
+pub enum ScaffoldError {
+    #[error("{} already exists", path.display())]
+    Exists { path: PathBuf },
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("couldn't find where to add day {day} of {year} in {}", path.display())]
+    NoPlace { path: PathBuf, year: u16, day: u8 },
+}
+
+const CARGO_TOML: &str = r#"[package]
+name = "{crate}"
+version.workspace = true
+edition.workspace = true
+
+[dependencies]
+aoc-core.workspace = true
+clap.workspace = true
+"#;
+
+const LIB_RS: &str = r#"use aoc_core::{SolveError, Solver};
+
+/// Day {day}'s puzzle input.
+pub struct Puzzle {
+    pub input: String,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self {
+            input: input.to_string(),
+        })
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+}
+
+#[test]
+fn test_part1() {
+    let puzzle = Puzzle::parse(TEST_INPUT).unwrap();
+    assert_eq!(puzzle.part1(), Err(SolveError::NotImplemented));
+}
+
+#[test]
+fn test_part2() {
+    let puzzle = Puzzle::parse(TEST_INPUT).unwrap();
+    assert_eq!(puzzle.part2(), Err(SolveError::NotImplemented));
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = r"";
+"#;
+
+const MAIN_RS: &str = r#"use aoc_core::{InputSource, Solver};
+use clap::{ArgAction, Parser};
+use {lib}::Puzzle;
+
+#[derive(Parser)]
+struct Args {
+    /// Puzzle input to read instead of `input.txt`, or - for stdin
+    #[arg(long, value_name = "FILE", default_value = "input.txt")]
+    input: InputSource,
+
+    /// Log what's going on to stderr; repeat for more detail
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+fn main() {
+    let args = Args::parse();
+    aoc_core::init_logging(args.verbose);
+    let input = &args.input.read_or_exit();
+    let puzzle = Puzzle::parse(input).unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    });
+    for (part, answer) in [(1, puzzle.part1()), (2, puzzle.part2())] {
+        match answer {
+            Ok(answer) => println!("part {part}: {answer}"),
+            Err(e) => eprintln!("part {part}: error: {e}"),
+        }
+    }
+}
+"#;
+
+/// A list of days in one of the files every day is registered in: how to start the line for
+/// a day of a year that's already there, and the whole line to add for a new one.
+struct Registry {
+    path: &'static str,
+    prefix: fn(u16, u8) -> String,
+    line: fn(u16, u8) -> String,
+    /// Whether days from years after the first are registered here too
+    every_year: bool,
+}
+
+/// The name `year`'s crate for `day` is used by in Rust.
+fn lib_name(year: u16, day: u8) -> String {
+    year::crate_name(year, day).replace('-', "_")
+}
+
+const REGISTRIES: [Registry; 6] = [
+    Registry {
+        path: "Cargo.toml",
+        prefix: |year, day| format!("\"{}\",", year::day_path(year, day)),
+        line: |year, day| format!("\"{}\",", year::day_path(year, day)),
+        every_year: true,
+    },
+    Registry {
+        path: "Cargo.toml",
+        prefix: |year, day| format!("{} = {{", year::crate_name(year, day)),
+        line: |year, day| {
+            format!(
+                "{} = {{ path = \"{}\" }}",
+                year::crate_name(year, day),
+                year::day_path(year, day)
+            )
+        },
+        every_year: true,
+    },
+    Registry {
+        path: "aoc/Cargo.toml",
+        prefix: |year, day| format!("{}.workspace", year::crate_name(year, day)),
+        line: |year, day| format!("{}.workspace = true", year::crate_name(year, day)),
+        every_year: true,
+    },
+    Registry {
+        path: "solvers/Cargo.toml",
+        prefix: |year, day| format!("{}.workspace", year::crate_name(year, day)),
+        line: |year, day| format!("{}.workspace = true", year::crate_name(year, day)),
+        every_year: false,
+    },
+    Registry {
+        path: "aoc/src/run.rs",
+        prefix: |year, day| format!("({year}, {day}) => run::<"),
+        line: |year, day| {
+            format!(
+                "({year}, {day}) => run::<{}::Puzzle>(day, input, parts, options),",
+                lib_name(year, day)
+            )
+        },
+        every_year: true,
+    },
+    Registry {
+        path: "solvers/src/lib.rs",
+        prefix: |year, day| format!("{day} => {}::", lib_name(year, day)),
+        line: |year, day| {
+            format!(
+                "{day} => {}::Puzzle::solve(input, part),",
+                lib_name(year, day)
+            )
+        },
+        every_year: false,
+    },
+];
+
+/// `text` with `registry`'s line for `day` of `year` added among the other days' lines, in
+/// order of year and then day and with the same indentation, or None if there aren't any other
+/// days to put it next to. Unchanged if the day is already there.
+fn register(text: &str, registry: &Registry, year: u16, day: u8) -> Option<String> {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    // Each listed day's line, by the year and day
+    let listed: Vec<(usize, (u16, u8))> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.trim_start();
+            (year::FIRST_YEAR..=year)
+                .flat_map(|year| (1..=25).map(move |day| (year, day)))
+                .find(|&(year, day)| line.starts_with(&(registry.prefix)(year, day)))
+                .map(|other| (i, other))
+        })
+        .collect();
+    if listed.iter().any(|&(_, other)| other == (year, day)) {
+        return Some(text.to_string());
+    }
+    let (neighbor, at) = match listed.iter().rev().find(|&&(_, other)| other < (year, day)) {
+        Some(&(i, _)) => (i, i + 1),
+        None => (listed.first()?.0, listed.first()?.0),
+    };
+    let neighbor = &lines[neighbor];
+    let indent = &neighbor[..neighbor.len() - neighbor.trim_start().len()];
+    lines.insert(at, format!("{indent}{}", (registry.line)(year, day)));
+    Some(lines.join("\n") + "\n")
+}
+
+fn write(path: PathBuf, contents: &str) -> Result<PathBuf, ScaffoldError> {
+    match std::fs::write(&path, contents) {
+        Ok(()) => Ok(path),
+        Err(source) => Err(ScaffoldError::Io { path, source }),
+    }
+}
+
+/// Creates `dayN/` in `year`'s directory under the repository `root` with a stub solver that
+/// the day's own binary and the runner use, as do the other front ends for the first year,
+/// returning every file created or changed.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let dir = root.join(year::day_path(year, day));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists { path: dir });
+    }
+
+    // Work out every change before making any, so a failure doesn't leave half a day
+    let mut registered: Vec<(PathBuf, String)> = vec![];
+    let registries = REGISTRIES
+        .iter()
+        .filter(|registry| registry.every_year || year == year::FIRST_YEAR);
+    for registry in registries {
+        let path = root.join(registry.path);
+        let text = match registered.iter().find(|(done, _)| *done == path) {
+            Some((_, text)) => text.clone(),
+            None => std::fs::read_to_string(&path).map_err(|source| ScaffoldError::Io {
+                path: path.clone(),
+                source,
+            })?,
+        };
+        let text = register(&text, registry, year, day).ok_or_else(|| ScaffoldError::NoPlace {
+            path: path.clone(),
+            year,
+            day,
+        })?;
+        registered.retain(|(done, _)| *done != path);
+        registered.push((path, text));
+    }
+
+    std::fs::create_dir_all(dir.join("src")).map_err(|source| ScaffoldError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    let mut changed = vec![];
+    for (path, template) in [
+        ("Cargo.toml", CARGO_TOML),
+        ("src/lib.rs", LIB_RS),
+        ("src/main.rs", MAIN_RS),
+    ] {
+        let contents = template
+            .replace("{day}", &day.to_string())
+            .replace("{crate}", &year::crate_name(year, day))
+            .replace("{lib}", &lib_name(year, day));
+        changed.push(write(dir.join(path), &contents)?);
+    }
+    for (path, text) in registered {
+        changed.push(write(path, &text)?);
+    }
+    Ok(changed)
+}
+
+#[test]
+fn test_register() {
+    let run =
+        "    match (year, day) {\n        (2023, 1) => run::<day1::Trebuchet>(day),\n        \
+               (2023, 3) => run::<day3::Schematic>(day),\n        _ => panic!(),\n    }\n";
+    assert_eq!(
+        register(run, &REGISTRIES[4], 2023, 2).unwrap(),
+        "    match (year, day) {\n        (2023, 1) => run::<day1::Trebuchet>(day),\n        \
+         (2023, 2) => run::<day2::Puzzle>(day, input, parts, options),\n        \
+         (2023, 3) => run::<day3::Schematic>(day),\n        _ => panic!(),\n    }\n"
+    );
+    assert_eq!(
+        register(run, &REGISTRIES[4], 2024, 1).unwrap(),
+        "    match (year, day) {\n        (2023, 1) => run::<day1::Trebuchet>(day),\n        \
+         (2023, 3) => run::<day3::Schematic>(day),\n        \
+         (2024, 1) => run::<aoc2024_day1::Puzzle>(day, input, parts, options),\n        \
+         _ => panic!(),\n    }\n"
+    );
+
+    let members = "members = [\n    \"aoc\",\n    \"day2\",\n    \"day10\",\n]\n";
+    assert_eq!(
+        register(members, &REGISTRIES[0], 2023, 1).unwrap(),
+        "members = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n    \"day10\",\n]\n"
+    );
+    assert_eq!(
+        register(members, &REGISTRIES[0], 2023, 11).unwrap(),
+        "members = [\n    \"aoc\",\n    \"day2\",\n    \"day10\",\n    \"day11\",\n]\n"
+    );
+    assert_eq!(
+        register(members, &REGISTRIES[0], 2024, 3).unwrap(),
+        "members = [\n    \"aoc\",\n    \"day2\",\n    \"day10\",\n    \"2024/day3\",\n]\n"
+    );
+    assert_eq!(register(members, &REGISTRIES[0], 2023, 2).unwrap(), members);
+    assert!(register("members = []\n", &REGISTRIES[0], 2023, 1).is_none());
+}
+
+#[test]
+fn test_new_day() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("aoc/src")).unwrap();
+    std::fs::create_dir_all(root.join("solvers/src")).unwrap();
+    std::fs::create_dir_all(root.join("day1")).unwrap();
+    let files = [
+        (
+            "Cargo.toml",
+            "members = [\n    \"day1\",\n]\n\n[workspace.dependencies]\nday1 = { path = \"day1\" }\n",
+        ),
+        ("aoc/Cargo.toml", "[dependencies]\nday1.workspace = true\n"),
+        ("solvers/Cargo.toml", "[dependencies]\nday1.workspace = true\n"),
+        ("aoc/src/run.rs", "        (2023, 1) => run::<day1::Document>(day),\n"),
+        ("solvers/src/lib.rs", "        1 => day1::Document::solve(input, part),\n"),
+    ];
+    for (path, text) in files {
+        std::fs::write(root.join(path), text).unwrap();
+    }
+
+    assert!(matches!(
+        new_day(&root, 2023, 1),
+        Err(ScaffoldError::Exists { .. })
+    ));
+    let changed = new_day(&root, 2023, 2).unwrap();
+    assert_eq!(changed.len(), 8);
+    let read = |path| std::fs::read_to_string(root.join(path)).unwrap();
+    assert!(read("day2/src/main.rs").contains("use day2::Puzzle;"));
+    assert!(read("day2/src/lib.rs").contains("/// Day 2's puzzle input."));
+    assert_eq!(
+        read("Cargo.toml"),
+        "members = [\n    \"day1\",\n    \"day2\",\n]\n\n[workspace.dependencies]\n\
+         day1 = { path = \"day1\" }\nday2 = { path = \"day2\" }\n"
+    );
+    assert_eq!(
+        read("solvers/Cargo.toml"),
+        "[dependencies]\nday1.workspace = true\nday2.workspace = true\n"
+    );
+    assert!(
+        read("solvers/src/lib.rs").ends_with("        2 => day2::Puzzle::solve(input, part),\n")
+    );
+
+    // Later years get their own directory, and only the runner runs them
+    let changed = new_day(&root, 2024, 1).unwrap();
+    assert_eq!(changed.len(), 6);
+    assert!(read("2024/day1/Cargo.toml").contains("name = \"aoc2024-day1\""));
+    assert!(read("2024/day1/src/main.rs").contains("use aoc2024_day1::Puzzle;"));
+    assert!(read("Cargo.toml").contains(
+        "    \"day2\",\n    \"2024/day1\",\n]\n\n[workspace.dependencies]\n\
+         day1 = { path = \"day1\" }\nday2 = { path = \"day2\" }\n\
+         aoc2024-day1 = { path = \"2024/day1\" }\n"
+    ));
+    assert!(read("aoc/src/run.rs").ends_with(
+        "        (2024, 1) => run::<aoc2024_day1::Puzzle>(day, input, parts, options),\n"
+    ));
+    assert!(!read("solvers/Cargo.toml").contains("2024"));
+
+    // Nothing is created if there's somewhere it can't be registered
+    std::fs::write(root.join("aoc/src/run.rs"), "").unwrap();
+    assert!(matches!(
+        new_day(&root, 2023, 3),
+        Err(ScaffoldError::NoPlace { day: 3, .. })
+    ));
+    assert!(!root.join("day3").exists());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,304 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+use aoc_core::SolveError;
+
+use crate::{
+    config::Config,
+    run::{self, RunOptions},
+};
+
+/// A response before it's sent: its status code and JSON body.
+#[derive(Debug, PartialEq)]
+struct Reply {
+    status: u16,
+    body: Value,
+}
+
+impl Reply {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// The day and part in a `/solve/{day}/{part}` path, or the reply to send if the path isn't
+/// one.
+fn route(url: &str) -> Result<(u8, u8), Reply> {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let [solve, day, part] = segments[..] else {
+        return Err(Reply::error(404, format!("no such path {path}")));
+    };
+    if solve != "solve" {
+        return Err(Reply::error(404, format!("no such path {path}")));
+    }
+    let day = day
+        .parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| Reply::error(400, format!("day {day:?} isn't from 1 to 25")))?;
+    let part = part
+        .parse()
+        .ok()
+        .filter(|part| (1..=2).contains(part))
+        .ok_or_else(|| Reply::error(400, format!("part {part:?} isn't 1 or 2")))?;
+    Ok((day, part))
+}
+
+/// Solves a `POST /solve/{day}/{part}` request for `year` whose body is the puzzle input,
+/// replying with the answer and how long parsing and solving took in seconds, or 504 if it
+/// takes longer than `time_limit`.
+fn handle(
+    method: &Method,
+    url: &str,
+    input: &str,
+    year: u16,
+    config: &Config,
+    time_limit: Option<Duration>,
+) -> Reply {
+    let (day, part) = match route(url) {
+        Ok(route) => route,
+        Err(reply) => return reply,
+    };
+    if *method != Method::Post {
+        return Reply::error(405, "puzzles can only be solved with POST");
+    }
+    let options = RunOptions {
+        params: config.params(day),
+        time_limit,
+        ..Default::default()
+    };
+    let run = run::run_day(year, day, input, &[part], &options);
+    let parsed = match run.parts {
+        Ok(parts) => parts,
+        Err(e @ SolveError::NotImplemented) => return Reply::error(501, e.to_string()),
+        Err(e) => return Reply::error(422, format!("couldn't parse the input: {e}")),
+    };
+    let solved = &parsed[0];
+    let mut body = json!({
+        "day": day,
+        "part": part,
+        "parse_seconds": run.parse_time.as_secs_f64(),
+        "solve_seconds": solved.time.as_secs_f64(),
+    });
+    match &solved.answer {
+        Ok(answer) => {
+            body["answer"] = answer.as_str().into();
+            Reply::ok(body)
+        }
+        Err(e) => {
+            body["error"] = e.to_string().into();
+            let status = match e {
+                SolveError::NotImplemented => 501,
+                SolveError::Failed(_) => 422,
+                SolveError::TimedOut { .. } => 504,
+            };
+            Reply { status, body }
+        }
+    }
+}
+
+/// What a panic said, if it said it with a string.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+/// Works out the reply to a request from its method, URL and body.
+type Handler<'a> = dyn Fn(&Method, &str, &str) -> Reply + Sync + 'a;
+
+fn respond(mut request: Request, handle: &Handler) {
+    let mut input = String::new();
+    let reply = match request.as_reader().read_to_string(&mut input) {
+        // A day that panics on the input shouldn't take the worker answering it down with it
+        Ok(_) => panic::catch_unwind(AssertUnwindSafe(|| {
+            handle(request.method(), request.url(), &input)
+        }))
+        .unwrap_or_else(|payload| {
+            let message = panic_message(&*payload);
+            warn!(url = request.url(), "solving panicked: {message}");
+            Reply::error(500, format!("solving panicked: {message}"))
+        }),
+        Err(e) => Reply::error(400, format!("couldn't read the input: {e}")),
+    };
+    info!(method = %request.method(), url = request.url(), status = reply.status);
+    let response = Response::from_string(reply.body.to_string())
+        .with_status_code(reply.status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(e) = request.respond(response) {
+        warn!("couldn't send a response: {e}");
+    }
+}
+
+/// Answers requests on `addr` until the process is stopped, solving as many at once as rayon's
+/// pool has threads, with `year`'s solutions and the days' settings from `config`, giving up
+/// on parts that take longer than `time_limit`.
+pub fn serve(
+    addr: &str,
+    year: u16,
+    config: &Config,
+    time_limit: Option<Duration>,
+) -> Result<(), String> {
+    let server = Server::http(addr).map_err(|e| format!("couldn't listen on {addr}: {e}"))?;
+    eprintln!("listening on http://{addr}");
+    answer_requests(&server, &|method, url, input| {
+        handle(method, url, input, year, config, time_limit)
+    });
+    Ok(())
+}
+
+/// Answers `server`'s requests with `handle` on as many threads as rayon's pool has, for as
+/// long as it's listening.
+fn answer_requests(server: &Server, handle: &Handler) {
+    thread::scope(|scope| {
+        for _ in 0..rayon::current_num_threads() {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    respond(request, handle);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_handle() {
+    let config = Config::default();
+    let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n";
+    let reply = handle(&Method::Post, "/solve/9/1", input, 2023, &config, None);
+    assert_eq!(reply.status, 200);
+    assert_eq!(reply.body["day"], 9);
+    assert_eq!(reply.body["part"], 1);
+    assert_eq!(reply.body["answer"], "46");
+    assert!(reply.body["parse_seconds"].is_f64());
+    assert!(reply.body["solve_seconds"].is_f64());
+
+    let reply = handle(
+        &Method::Post,
+        "/solve/25/2",
+        "jqt: rhn\n",
+        2023,
+        &config,
+        None,
+    );
+    assert_eq!(reply.status, 501);
+    assert_eq!(reply.body["error"], "not implemented");
+    let reply = handle(&Method::Post, "/solve/1/1", "3   4\n", 2024, &config, None);
+    assert_eq!(reply.status, 501);
+    assert_eq!(reply.body["error"], "not implemented");
+
+    assert_eq!(
+        handle(
+            &Method::Post,
+            "/solve/19/1",
+            "no blank line",
+            2023,
+            &config,
+            None
+        )
+        .status,
+        422
+    );
+    assert_eq!(
+        handle(&Method::Get, "/solve/9/1", input, 2023, &config, None).status,
+        405
+    );
+    assert_eq!(
+        handle(&Method::Post, "/solve/26/1", input, 2023, &config, None).status,
+        400
+    );
+    assert_eq!(
+        handle(&Method::Post, "/solve/9/3", input, 2023, &config, None).status,
+        400
+    );
+    assert_eq!(
+        handle(&Method::Post, "/solve/9", input, 2023, &config, None).status,
+        404
+    );
+    assert_eq!(
+        handle(&Method::Post, "/answer/9/1", input, 2023, &config, None).status,
+        404
+    );
+
+    let limit = Some(Duration::ZERO);
+    let reply = handle(
+        &Method::Post,
+        "/solve/20/2",
+        TEST_STR_RX_NEVER,
+        2023,
+        &config,
+        limit,
+    );
+    assert_eq!(reply.status, 504);
+    assert!(reply.body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("timed out with 1024 presses done"));
+}
+
+#[test]
+fn test_server_survives_panics() {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    thread::spawn(move || {
+        answer_requests(&server, &|method, url, input| {
+            if url == "/panic" {
+                panic!("the input was {input}");
+            }
+            handle(method, url, input, 2023, &Config::default(), None)
+        })
+    });
+
+    let post = |url: &str, body: &str| {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST {url} HTTP/1.1\r\nHost: {addr}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    // More panics than there are workers would leave nothing to answer with if each one took
+    // its worker down
+    for _ in 0..=rayon::current_num_threads() {
+        let response = post("/panic", "bad");
+        assert!(response.starts_with("HTTP/1.1 500"), "{response}");
+        assert!(
+            response.ends_with(r#"{"error":"solving panicked: the input was bad"}"#),
+            "{response}"
+        );
+    }
+    let response = post("/solve/9/1", "0 3 6 9 12 15\n");
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.contains(r#""answer":"18""#), "{response}");
+}
+
+#[cfg(test)]
+const TEST_STR_RX_NEVER: &str = "broadcaster -> c\n&c -> a\n%a -> f\n&f -> rx\n";
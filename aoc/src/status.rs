@@ -0,0 +1,218 @@
+use std::{collections::BTreeMap, fmt::Write as _, io, path::Path};
+
+use aoc_core::SolveError;
+
+use crate::{answers::Answers, run::DayRun};
+
+/// How a part went the last time it was run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    /// The day doesn't have a solution for it yet
+    Stubbed,
+    Failed,
+    TimedOut,
+}
+
+impl PartStatus {
+    const ALL: [PartStatus; 4] = [
+        PartStatus::Solved,
+        PartStatus::Stubbed,
+        PartStatus::Failed,
+        PartStatus::TimedOut,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Stubbed => "stubbed",
+            PartStatus::Failed => "failed",
+            PartStatus::TimedOut => "timed-out",
+        }
+    }
+
+    fn of(answer: &Result<String, SolveError>) -> Self {
+        match answer {
+            Ok(_) => PartStatus::Solved,
+            Err(SolveError::NotImplemented) => PartStatus::Stubbed,
+            Err(SolveError::TimedOut { .. }) => PartStatus::TimedOut,
+            Err(SolveError::Failed(_)) => PartStatus::Failed,
+        }
+    }
+
+    /// What happened, to finish "last time it ...".
+    fn last_time(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "was solved",
+            PartStatus::Stubbed => "wasn't implemented",
+            PartStatus::Failed => "failed",
+            PartStatus::TimedOut => "timed out",
+        }
+    }
+}
+
+/// How each part went the last time it was run, so that `aoc status` can show what's left to
+/// do and the runner can warn before running a part that isn't finished.
+///
+/// Saved one line per part, as `day part status`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Status {
+    parts: BTreeMap<(u8, u8), PartStatus>,
+}
+
+impl Status {
+    /// Loads the statuses saved at `path`, or none if nothing has been saved there yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => contents.parse().map_err(|line| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: can't read {line:?}", path.display()),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<PartStatus> {
+        self.parts.get(&(day, part)).copied()
+    }
+
+    /// A warning about `part` of `day` if it didn't get an answer the last time it was run.
+    pub fn warning(&self, day: u8, part: u8) -> Option<String> {
+        match self.get(day, part)? {
+            PartStatus::Solved => None,
+            status => Some(format!(
+                "part {part} isn't finished; last time it {}",
+                status.last_time()
+            )),
+        }
+    }
+
+    /// Records how each part in `run` went. Nothing is recorded if the input couldn't be
+    /// parsed, since it can't tell how the parts would have gone.
+    pub fn record(&mut self, run: &DayRun) {
+        for part in run.parts.iter().flatten() {
+            self.parts
+                .insert((run.day, part.part), PartStatus::of(&part.answer));
+        }
+    }
+
+    /// A table of every day's parts, showing how they went the last time they were run, or
+    /// that adventofcode.com accepted an answer for them, with a count of each at the end.
+    pub fn table(&self, answers: &Answers) -> String {
+        let mut table = format!("{:>3} {:>10} {:>10}\n", "day", "part 1", "part 2");
+        let mut verified = 0;
+        for day in 1..=25 {
+            let cell = |part| {
+                let accepted = answers.accepted(day, part).is_some();
+                match self.get(day, part) {
+                    Some(PartStatus::Solved) | None if accepted => "verified",
+                    Some(status) => status.name(),
+                    None => "-",
+                }
+            };
+            let cells = [cell(1), cell(2)];
+            verified += cells.iter().filter(|&&c| c == "verified").count();
+            writeln!(table, "{day:>3} {:>10} {:>10}", cells[0], cells[1]).unwrap();
+        }
+        let counts: Vec<String> = PartStatus::ALL
+            .iter()
+            .map(|&status| {
+                let count = self.parts.values().filter(|&&s| s == status).count();
+                format!("{count} {}", status.name())
+            })
+            .collect();
+        writeln!(table, "{}, {verified} verified", counts.join(", ")).unwrap();
+        table
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part), status) in &self.parts {
+            writeln!(f, "{day} {part} {}", status.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Status {
+    /// The line that couldn't be read
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut status = Status::default();
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let bad_line = || line.to_string();
+            let [day, part, name] = line
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| bad_line())?;
+            let key = (
+                day.parse().map_err(|_| bad_line())?,
+                part.parse().map_err(|_| bad_line())?,
+            );
+            let part_status = PartStatus::ALL
+                .into_iter()
+                .find(|s| s.name() == name)
+                .ok_or_else(bad_line)?;
+            status.parts.insert(key, part_status);
+        }
+        Ok(status)
+    }
+}
+
+#[test]
+fn test_record() {
+    use crate::run::{self, RunOptions};
+
+    let mut status = Status::default();
+    let options = &RunOptions::default();
+    status.record(&run::run_day(2023, 9, "0 3 6\n", &[1, 2], options));
+    status.record(&run::run_day(2023, 25, "jqt: rhn\n", &[2], options));
+    status.record(&run::run_day(2023, 19, "no blank line", &[1], options));
+    assert_eq!(status.get(9, 1), Some(PartStatus::Solved));
+    assert_eq!(status.get(25, 2), Some(PartStatus::Stubbed));
+    assert_eq!(status.get(25, 1), None);
+    assert_eq!(status.get(19, 1), None);
+    assert_eq!(status.warning(9, 2), None);
+    assert_eq!(
+        status.warning(25, 2),
+        Some("part 2 isn't finished; last time it wasn't implemented".to_string())
+    );
+
+    let saved = status.to_string();
+    assert_eq!(saved, "9 1 solved\n9 2 solved\n25 2 stubbed\n");
+    assert_eq!(saved.parse(), Ok(status));
+    assert_eq!("9 1 done".parse::<Status>(), Err("9 1 done".to_string()));
+}
+
+#[test]
+fn test_table() {
+    use crate::client::Verdict;
+
+    let status: Status = "1 1 solved\n1 2 timed-out\n2 1 failed\n7 1 stubbed\n"
+        .parse()
+        .unwrap();
+    let mut answers = Answers::default();
+    answers.record(1, 1, "142", Verdict::Correct);
+    answers.record(3, 2, "467835", Verdict::Correct);
+    let table = status.table(&answers);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "day     part 1     part 2");
+    assert_eq!(lines[1], "  1   verified  timed-out");
+    assert_eq!(lines[2], "  2     failed          -");
+    assert_eq!(lines[3], "  3          -   verified");
+    assert_eq!(lines[7], "  7    stubbed          -");
+    assert_eq!(
+        lines[26],
+        "1 solved, 1 stubbed, 1 failed, 1 timed-out, 2 verified"
+    );
+}
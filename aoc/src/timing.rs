@@ -0,0 +1,170 @@
+use std::{fmt::Write as _, time::Duration};
+
+use crate::run::{DayRun, PartRun};
+
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs < 1e-3 {
+        format!("{:.1}µs", secs * 1e6)
+    } else if secs < 1.0 {
+        format!("{:.1}ms", secs * 1e3)
+    } else {
+        format!("{secs:.2}s")
+    }
+}
+
+/// Reads a duration like `60s`, `2m` or `500ms`, or a number of seconds on its own.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let scale = match unit {
+        "ms" => 1e-3,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => return Err(format!("unknown unit {unit:?}; use ms, s, m or h")),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(|n| Duration::try_from_secs_f64(n * scale).ok())
+        .ok_or_else(|| format!("expected a duration like 60s but got {s:?}"))
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{size:.1}{}", UNITS[unit])
+    }
+}
+
+/// How long a part took, or that its answer was cached.
+pub fn part_time(part: &PartRun) -> String {
+    if part.cached {
+        "cached".to_string()
+    } else {
+        format_duration(part.time)
+    }
+}
+
+/// Timings and peak memory for one day on one line.
+pub fn day_report(run: &DayRun) -> String {
+    let mut report = format!("parse {}", format_duration(run.parse_time));
+    for part in run.parts.iter().flatten() {
+        write!(report, ", part {} {}", part.part, part_time(part)).unwrap();
+        if let Some(peak_memory) = part.peak_memory {
+            write!(report, " ({})", format_bytes(peak_memory)).unwrap();
+        }
+    }
+    if let Some(peak_memory) = run.peak_memory {
+        write!(report, ", peak memory {}", format_bytes(peak_memory)).unwrap();
+    }
+    report
+}
+
+/// A table of every day's timings and peak memory, overall and for each part, with a total
+/// time at the bottom.
+pub fn summary_table(runs: &[DayRun]) -> String {
+    let mut table = format!(
+        "{:>3} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+        "day", "parse", "part 1", "part 2", "memory", "part 1 mem", "part 2 mem"
+    );
+    let mut total = Duration::ZERO;
+    for run in runs {
+        let part_time = |part| run.part(part).map_or("-".to_string(), part_time);
+        let memory = |peak: Option<usize>| peak.map_or("-".to_string(), format_bytes);
+        let part_memory = |part| memory(run.part(part).and_then(|p| p.peak_memory));
+        writeln!(
+            table,
+            "{:>3} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            run.day,
+            format_duration(run.parse_time),
+            part_time(1),
+            part_time(2),
+            memory(run.peak_memory),
+            part_memory(1),
+            part_memory(2),
+        )
+        .unwrap();
+        total += run.parse_time + run.parts.iter().flatten().map(|p| p.time).sum::<Duration>();
+    }
+    writeln!(table, "total {}", format_duration(total)).unwrap();
+    table
+}
+
+#[test]
+fn test_format() {
+    assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+    assert_eq!(format_duration(Duration::from_micros(4_560)), "4.6ms");
+    assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50s");
+    assert_eq!(format_bytes(512), "512B");
+    assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5MiB");
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("60s"), Ok(Duration::from_secs(60)));
+    assert_eq!(parse_duration("1.5"), Ok(Duration::from_millis(1500)));
+    assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+    assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+    assert!(parse_duration("5 days").is_err());
+    assert!(parse_duration("-1s").is_err());
+    assert!(parse_duration("s").is_err());
+}
+
+#[test]
+fn test_reports() {
+    let runs = [
+        DayRun {
+            day: 3,
+            parse_time: Duration::from_micros(20),
+            parts: Ok(vec![
+                PartRun {
+                    part: 1,
+                    answer: Ok("4361".to_string()),
+                    time: Duration::from_millis(2),
+                    cached: false,
+                    peak_memory: Some(512),
+                    naive: None,
+                },
+                PartRun {
+                    part: 2,
+                    answer: Ok("467835".to_string()),
+                    time: Duration::ZERO,
+                    cached: true,
+                    peak_memory: None,
+                    naive: None,
+                },
+            ]),
+            peak_memory: Some(2048),
+            picture: None,
+        },
+        DayRun {
+            day: 19,
+            parse_time: Duration::from_micros(5),
+            parts: Err(aoc_core::SolveError::NotImplemented),
+            peak_memory: None,
+            picture: None,
+        },
+    ];
+    assert_eq!(
+        day_report(&runs[0]),
+        "parse 20.0µs, part 1 2.0ms (512B), part 2 cached, peak memory 2.0KiB"
+    );
+    assert_eq!(
+        summary_table(&runs),
+        "day      parse     part 1     part 2     memory part 1 mem part 2 mem
+  3     20.0µs      2.0ms     cached     2.0KiB       512B          -
+ 19      5.0µs          -          -          -          -          -
+total 2.0ms
+"
+    );
+}
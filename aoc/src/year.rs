@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+/// The year the repository started with, whose days are at its top level. Later years each
+/// get a directory named after them, laid out the same way.
+pub const FIRST_YEAR: u16 = 2023;
+
+/// Where `year`'s days, settings, saved answers and cache are, under the repository `root`.
+pub fn dir(root: &Path, year: u16) -> PathBuf {
+    if year == FIRST_YEAR {
+        root.to_path_buf()
+    } else {
+        root.join(year.to_string())
+    }
+}
+
+/// The directory of `year`'s crate for `day`, relative to the repository root.
+pub fn day_path(year: u16, day: u8) -> String {
+    if year == FIRST_YEAR {
+        format!("day{day}")
+    } else {
+        format!("{year}/day{day}")
+    }
+}
+
+/// The name of `year`'s crate for `day`, which has the year in it after the first year so
+/// that it's unique in the workspace.
+pub fn crate_name(year: u16, day: u8) -> String {
+    if year == FIRST_YEAR {
+        format!("day{day}")
+    } else {
+        format!("aoc{year}-day{day}")
+    }
+}
+
+#[test]
+fn test_layout() {
+    let root = Path::new("repo");
+    assert_eq!(dir(root, 2023), root);
+    assert_eq!(dir(root, 2024), Path::new("repo/2024"));
+    assert_eq!(root.join(day_path(2023, 7)), Path::new("repo/day7"));
+    assert_eq!(root.join(day_path(2024, 7)), Path::new("repo/2024/day7"));
+    assert_eq!(crate_name(2023, 12), "day12");
+    assert_eq!(crate_name(2025, 1), "aoc2025-day1");
+}
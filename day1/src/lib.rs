@@ -0,0 +1,94 @@
+use std::io::BufRead;
+
+use aoc_core::{for_each_line, AocError, SolveError, Solver};
+
+fn linenumber(s: &str) -> Option<u32> {
+    let spelled_nums = [
+        ("0", 0),
+        ("1", 1),
+        ("one", 1),
+        ("2", 2),
+        ("two", 2),
+        ("3", 3),
+        ("three", 3),
+        ("4", 4),
+        ("four", 4),
+        ("5", 5),
+        ("five", 5),
+        ("6", 6),
+        ("six", 6),
+        ("7", 7),
+        ("seven", 7),
+        ("8", 8),
+        ("eight", 8),
+        ("9", 9),
+        ("nine", 9),
+    ];
+
+    let firstdigit = spelled_nums
+        .iter()
+        .filter_map(|(search, val)| Some((s.find(search)?, val)))
+        .min_by_key(|(pos, _)| *pos)?
+        .1;
+
+    let lastdigit = spelled_nums
+        .iter()
+        .filter_map(|(search, val)| Some((s.rfind(search)?, val)))
+        .max_by_key(|(pos, _)| *pos)?
+        .1;
+    // dbg!(s);
+    // dbg!(firstdigit);
+    // dbg!(lastdigit);
+
+    // let firstdigitchar = s.chars().find(|x| char::is_digit(*x, 10))?;
+    // let lastdigitchar = s.chars().rev().find(|x| char::is_digit(*x, 10))?;
+
+    // let firstdigit = firstdigitchar.to_digit(10).unwrap();
+    // let lastdigit = lastdigitchar.to_digit(10).unwrap();
+
+    Some(firstdigit * 10 + lastdigit)
+}
+
+/// Sum of the calibration values, reading spelled-out digits as well as numerals. Lines
+/// without any digit count as zero.
+pub fn part2(input: &str) -> u32 {
+    input.lines().map(|l| linenumber(l).unwrap_or(0)).sum()
+}
+
+/// [`part2`], reading the document a line at a time.
+pub fn part2_streaming(reader: impl BufRead) -> Result<u32, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |line| {
+        sum += linenumber(line).unwrap_or(0);
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// The calibration document, as the calibration value on each line when spelled-out digits
+/// count too, or None for a line without any digit.
+pub struct CalibrationDocument(Vec<Option<u32>>);
+
+impl Solver for CalibrationDocument {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self(input.lines().map(linenumber).collect()))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        let sum: u32 = self.0.iter().map(|value| value.unwrap_or(0)).sum();
+        Ok(sum.to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_STR), 281);
+    assert_eq!(part2_streaming(TEST_STR.as_bytes()), Ok(281));
+}
+
+#[cfg(test)]
+const TEST_STR: &str = aoc_core::examples::get(1, 2);
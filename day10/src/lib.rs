@@ -0,0 +1,289 @@
+use std::str::FromStr;
+
+use aoc_core::{polygon, Coord, Dir, Grid, ParseGridError, SolveError, Solver, Svg};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AocError {
+    InvalidPuzzleChar(char),
+    NotRectangular,
+    NoStart,
+    StartDoesntConnect,
+    PipeWentOffEdge,
+    PipeHitNonPipe,
+    PipeHitNonconnectingPipe,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+enum Square {
+    Pipe(Dir, Dir),
+    Ground,
+    InsideLoop,
+    OutsideLoop,
+    Start,
+}
+
+impl TryFrom<char> for Square {
+    type Error = AocError;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        use Dir::*;
+        use Square::*;
+        match ch {
+            '|' => Ok(Pipe(North, South)),
+            '-' => Ok(Pipe(East, West)),
+            'L' => Ok(Pipe(North, East)),
+            'J' => Ok(Pipe(North, West)),
+            '7' => Ok(Pipe(South, West)),
+            'F' => Ok(Pipe(South, East)),
+            '.' => Ok(Ground),
+            'S' => Ok(Start),
+            'I' => Ok(InsideLoop),
+            'O' => Ok(OutsideLoop),
+            other => Err(AocError::InvalidPuzzleChar(other)),
+        }
+    }
+}
+
+impl From<ParseGridError<AocError>> for AocError {
+    fn from(e: ParseGridError<AocError>) -> Self {
+        match e {
+            ParseGridError::InvalidCell { error, .. } => error,
+            ParseGridError::Ragged { .. } => AocError::NotRectangular,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Puzzle(Grid<Square>);
+
+impl FromStr for Puzzle {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle(s.parse()?))
+    }
+}
+
+impl Puzzle {
+    fn get(&self, index: Coord) -> Option<&Square> {
+        self.0.get(index)
+    }
+
+    fn find_pipe_loop(&self) -> Result<Pipe<'_>, AocError> {
+        let start_pos = self
+            .0
+            .position(|sq| *sq == Square::Start)
+            .ok_or(AocError::NoStart)?;
+
+        let mut start_dirs = vec![];
+        for dir in [Dir::North, Dir::South, Dir::East, Dir::West] {
+            if let Some(Square::Pipe(other_d1, other_d2)) = self.get(start_pos + dir) {
+                if *other_d1 == dir.reverse() || *other_d2 == dir.reverse() {
+                    start_dirs.push(dir);
+                }
+            }
+        }
+
+        let start_dirs = start_dirs;
+
+        if start_dirs.len() != 2 {
+            return Err(AocError::StartDoesntConnect);
+        }
+
+        let mut cur_dir = start_dirs[0];
+        let mut path = vec![start_pos];
+        let mut cur_pos: Coord = start_pos;
+
+        loop {
+            cur_pos += cur_dir;
+            if cur_pos == start_pos {
+                break;
+            }
+            path.push(cur_pos);
+            let cur_sq = self.get(cur_pos).ok_or(AocError::PipeWentOffEdge)?;
+            let Square::Pipe(d1, d2) = cur_sq else {
+                return Err(AocError::PipeHitNonPipe);
+            };
+            if *d1 == cur_dir.reverse() {
+                cur_dir = *d2;
+            } else if *d2 == cur_dir.reverse() {
+                cur_dir = *d1;
+            } else {
+                return Err(AocError::PipeHitNonconnectingPipe);
+            }
+        }
+
+        Ok(Pipe { puzzle: self, path })
+    }
+
+    /// A picture of the pipe loop with the tiles it encloses filled in.
+    pub fn to_svg(&self) -> Result<Svg, AocError> {
+        let pipe = self.find_pipe_loop()?;
+        let inside = Grid::from_fn(self.0.width(), self.0.height(), |coord| {
+            pipe.is_point_inside(&coord)
+        });
+        let mut svg = Svg::new();
+        svg.rect(
+            0.0,
+            0.0,
+            inside.width() as f64,
+            inside.height() as f64,
+            "white",
+        );
+        svg.grid(&inside, |&inside| inside.then_some("gold"));
+        let path: Vec<_> = pipe
+            .path
+            .iter()
+            .map(|c| (c.x as f64 + 0.5, c.y as f64 + 0.5))
+            .collect();
+        svg.polygon(&path, "none", "black", 0.3);
+        Ok(svg)
+    }
+}
+
+#[test]
+fn test_to_svg() {
+    let svg = TEST_INPUT3
+        .parse::<Puzzle>()
+        .unwrap()
+        .to_svg()
+        .unwrap()
+        .to_string();
+    assert_eq!(svg.matches(r#"fill="gold""#).count(), 2, "{svg}");
+    assert!(svg.contains(r#"<polygon points="1.5,1.5 1.5,2.5"#), "{svg}");
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Pipe<'a> {
+    puzzle: &'a Puzzle,
+    path: Vec<Coord>,
+}
+
+impl Pipe<'_> {
+    fn max_dist(&self) -> usize {
+        self.path.len().div_ceil(2)
+    }
+
+    /// Whether a tile is enclosed by the loop, rather than outside it or part of it.
+    fn is_point_inside(&self, pt: &Coord) -> bool {
+        polygon::contains(&self.path, *pt)
+    }
+
+    /// Number of tiles enclosed by the loop.
+    fn area(&self) -> usize {
+        polygon::interior_points(&self.path) as usize
+    }
+}
+
+#[cfg(test)]
+fn check_is_point_inside(input: &str) {
+    let puzzle = input.parse::<Puzzle>().unwrap();
+    let pipe = puzzle.find_pipe_loop().unwrap();
+    for (coord, pp) in pipe.puzzle.0.cells() {
+        match *pp {
+            Square::InsideLoop => assert!(
+                pipe.is_point_inside(&coord),
+                "Expected {:?} to be inside",
+                coord
+            ),
+            Square::OutsideLoop => assert!(
+                !pipe.is_point_inside(&coord),
+                "Expected {:?} to be outside",
+                coord
+            ),
+            Square::Ground => (),
+            _ => assert!(
+                !pipe.is_point_inside(&coord),
+                "Expected {:?} to be on the pipe",
+                coord
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_is_point_inside() {
+    check_is_point_inside(TEST_INPUT5);
+    check_is_point_inside(TEST_INPUT6);
+}
+
+pub fn part1(input: &str) -> Result<usize, AocError> {
+    Ok(input.parse::<Puzzle>()?.find_pipe_loop()?.max_dist())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT1), Ok(4));
+    assert_eq!(part1(TEST_INPUT2), Ok(8));
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    Ok(input.parse::<Puzzle>()?.find_pipe_loop()?.area())
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input
+            .parse()
+            .map_err(|e| SolveError::failed(format!("{e:?}")))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let pipe = self
+            .find_pipe_loop()
+            .map_err(|e| SolveError::failed(format!("{e:?}")))?;
+        Ok(pipe.max_dist().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        let pipe = self
+            .find_pipe_loop()
+            .map_err(|e| SolveError::failed(format!("{e:?}")))?;
+        Ok(pipe.area().to_string())
+    }
+
+    fn picture(&self) -> Option<Svg> {
+        self.to_svg().ok()
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT3), Ok(4));
+    assert_eq!(part2(TEST_INPUT4), Ok(8));
+}
+
+#[cfg(test)]
+const TEST_INPUT1: &str = aoc_core::examples::get(10, 1);
+
+#[cfg(test)]
+const TEST_INPUT2: &str = aoc_core::examples::get(10, 2);
+
+#[cfg(test)]
+const TEST_INPUT3: &str = aoc_core::examples::get(10, 3);
+
+#[cfg(test)]
+const TEST_INPUT4: &str = aoc_core::examples::get(10, 4);
+
+#[cfg(test)]
+const TEST_INPUT5: &str = r#"...........
+.S-------7.
+.|F-----7|.
+.||OOOOO||.
+.||OOOOO||.
+.|L-7OF-J|.
+.|II|O|II|.
+.L--JOL--J.
+.....O....."#;
+
+#[cfg(test)]
+const TEST_INPUT6: &str = r#"OF----7F7F7F7F-7OOOO
+O|F--7||||||||FJOOOO
+O||OFJ||||||||L7OOOO
+FJL7L7LJLJ||LJIL-7OO
+L--JOL7IIILJS7F-7L7O
+OOOOF-JIIF7FJ|L7L7L7
+OOOOL7IF7||L7|IL7L7|
+OOOOO|FJLJ|FJ|F7|OLJ
+OOOOFJL-7O||O||||OOO
+OOOOL---JOLJOLJLJOOO"#;
@@ -0,0 +1,267 @@
+use std::{fmt::Display, str::FromStr};
+
+use aoc_core::{parse_grid, AocError, Grid, Params, SolveError, Solver, Svg};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Point {
+    Galaxy,
+    Empty,
+}
+
+#[derive(Debug)]
+pub struct StarMap {
+    points: Grid<Point>,
+    galaxies: Vec<(usize, usize)>,
+    do_rows_have_galaxies: Vec<bool>,
+    do_cols_have_galaxies: Vec<bool>,
+    /// How many times as wide empty rows and columns grow in part 2
+    expansion: usize,
+}
+
+impl FromStr for StarMap {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let points = parse_grid(s, "# or .", |ch| match ch {
+            '.' => Some(Point::Empty),
+            '#' => Some(Point::Galaxy),
+            _ => None,
+        })?;
+        Ok(Self::from_points(points))
+    }
+}
+
+impl Display for StarMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        for row in self.points.rows() {
+            for pt in row {
+                f.write_char(match pt {
+                    Point::Empty => '.',
+                    Point::Galaxy => '#',
+                })?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl StarMap {
+    pub const PART2_EXPANSION: usize = 1000000;
+
+    fn from_points(points: Grid<Point>) -> StarMap {
+        let nrows = points.height();
+        let ncols = points.width();
+
+        let mut do_rows_have_galaxies: Vec<bool> = vec![false; nrows];
+        let mut do_cols_have_galaxies: Vec<bool> = vec![false; ncols];
+        let mut galaxies = vec![];
+        for row in 0..nrows {
+            for col in 0..ncols {
+                match points[(col, row)] {
+                    Point::Galaxy => {
+                        do_rows_have_galaxies[row] = true;
+                        do_cols_have_galaxies[col] = true;
+                        galaxies.push((col, row));
+                    }
+                    Point::Empty => (),
+                }
+            }
+        }
+        StarMap {
+            points,
+            do_cols_have_galaxies,
+            do_rows_have_galaxies,
+            galaxies,
+            expansion: Self::PART2_EXPANSION,
+        }
+    }
+
+    fn get_distance(
+        &self,
+        p1: (usize, usize),
+        p2: (usize, usize),
+        expansion_coefficient: usize,
+    ) -> usize {
+        let min_x = std::cmp::min(p1.0, p2.0);
+        let max_x = std::cmp::max(p1.0, p2.0);
+        let min_y = std::cmp::min(p1.1, p2.1);
+        let max_y = std::cmp::max(p1.1, p2.1);
+        (min_x..max_x)
+            .map(|col| {
+                if self.do_cols_have_galaxies[col] {
+                    1
+                } else {
+                    expansion_coefficient
+                }
+            })
+            .sum::<usize>()
+            + (min_y..max_y)
+                .map(|row| {
+                    if self.do_rows_have_galaxies[row] {
+                        1
+                    } else {
+                        expansion_coefficient
+                    }
+                })
+                .sum::<usize>()
+    }
+
+    /// A picture of the galaxies once each empty row and column has grown
+    /// `expansion_coefficient` times as wide, with the grown ones shaded.
+    pub fn to_svg(&self, expansion_coefficient: usize) -> Svg {
+        // Where each row or column starts after expanding, then where the last one ends
+        let starts = |have_galaxies: &[bool]| -> Vec<f64> {
+            let mut starts = vec![0.0];
+            for &has_galaxies in have_galaxies {
+                let width = if has_galaxies {
+                    1
+                } else {
+                    expansion_coefficient
+                };
+                starts.push(starts.last().unwrap() + width as f64);
+            }
+            starts
+        };
+        let xs = starts(&self.do_cols_have_galaxies);
+        let ys = starts(&self.do_rows_have_galaxies);
+        let (width, height) = (*xs.last().unwrap(), *ys.last().unwrap());
+
+        let mut svg = Svg::new();
+        svg.rect(0.0, 0.0, width, height, "black");
+        for (col, _) in self
+            .do_cols_have_galaxies
+            .iter()
+            .enumerate()
+            .filter(|(_, has)| !**has)
+        {
+            svg.rect(xs[col], 0.0, xs[col + 1] - xs[col], height, "midnightblue");
+        }
+        for (row, _) in self
+            .do_rows_have_galaxies
+            .iter()
+            .enumerate()
+            .filter(|(_, has)| !**has)
+        {
+            svg.rect(0.0, ys[row], width, ys[row + 1] - ys[row], "midnightblue");
+        }
+        for &(col, row) in &self.galaxies {
+            svg.circle(xs[col] + 0.5, ys[row] + 0.5, 0.4, "gold");
+        }
+        svg
+    }
+
+    fn galaxy_distance_sum(&self, expansion_coefficient: usize) -> usize {
+        (0..self.galaxies.len())
+            .map(|i| {
+                (i + 1..self.galaxies.len())
+                    .map(|j| {
+                        self.get_distance(self.galaxies[i], self.galaxies[j], expansion_coefficient)
+                    })
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+#[test]
+fn test_get_distance() {
+    let map = TEST_STR.parse::<StarMap>().unwrap();
+    assert_eq!(map.get_distance(map.galaxies[0], map.galaxies[6], 2), 15);
+    assert_eq!(map.get_distance(map.galaxies[2], map.galaxies[5], 2), 17);
+    assert_eq!(map.get_distance(map.galaxies[7], map.galaxies[8], 2), 5);
+}
+
+#[test]
+fn test_display() {
+    let map = TEST_STR.parse::<StarMap>().unwrap();
+    aoc_core::assert_snapshot!("star_map", map);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!("..\n.#".parse::<StarMap>().unwrap().galaxies, [(1, 1)]);
+    assert_eq!(
+        "..\n.".parse::<StarMap>().unwrap_err().to_string(),
+        "line 2: the line is 1 characters long but the first is 2"
+    );
+    assert_eq!(
+        ".x".parse::<StarMap>().unwrap_err(),
+        AocError::malformed("x", "# or .").on_line(1)
+    );
+}
+
+#[test]
+fn test_to_svg() {
+    let svg = "#..\n...\n..#"
+        .parse::<StarMap>()
+        .unwrap()
+        .to_svg(3)
+        .to_string();
+    assert!(svg.contains(r#"viewBox="0 0 5 5""#), "{svg}");
+    assert!(
+        svg.contains(r#"<rect x="1" y="0" width="3" height="5""#),
+        "{svg}"
+    );
+    assert!(svg.contains(r#"<circle cx="4.5" cy="4.5""#), "{svg}");
+}
+
+pub fn part1(input: &str) -> usize {
+    input.parse::<StarMap>().unwrap().galaxy_distance_sum(2)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_STR), 374);
+}
+
+pub fn part2(input: &str) -> usize {
+    input
+        .parse::<StarMap>()
+        .unwrap()
+        .galaxy_distance_sum(StarMap::PART2_EXPANSION)
+}
+
+impl Solver for StarMap {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.galaxy_distance_sum(2).to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.galaxy_distance_sum(self.expansion).to_string())
+    }
+
+    fn picture(&self) -> Option<Svg> {
+        Some(self.to_svg(self.expansion))
+    }
+
+    fn configure(&mut self, params: &Params) -> Result<(), SolveError> {
+        params.only(&["expansion"])?;
+        if let Some(expansion) = params.get("expansion")? {
+            self.expansion = expansion;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_part2() {
+    let map = TEST_STR.parse::<StarMap>().unwrap();
+
+    assert_eq!(map.galaxy_distance_sum(10), 1030);
+    assert_eq!(map.galaxy_distance_sum(100), 8410);
+
+    let mut map = StarMap::parse(TEST_STR).unwrap();
+    let mut params = Params::new();
+    params.set("expansion", 10);
+    map.configure(&params).unwrap();
+    assert_eq!(map.part2(), Ok("1030".to_string()));
+}
+
+#[cfg(test)]
+const TEST_STR: &str = aoc_core::examples::get(11, 1);
@@ -0,0 +1,321 @@
+use std::{fmt::Display, str::FromStr};
+
+use aoc_core::{blocks, AocError, Grid, ParseGridError, SolveError, Solver};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum ReflectionLine {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl ReflectionLine {
+    fn score(&self) -> usize {
+        match self {
+            ReflectionLine::Vertical(num_left) => *num_left,
+            ReflectionLine::Horizontal(num_above) => num_above * 100,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Pattern(Grid<char>);
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+fn invert(ch: char) -> char {
+    match ch {
+        '#' => '.',
+        '.' => '#',
+        _ => panic!("Unrecognized character {}", ch),
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = ParseGridError<AocError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |ch| match ch {
+            '#' | '.' => Ok(ch),
+            _ => Err(AocError::malformed(&ch.to_string(), "# or .")),
+        })
+        .map(Self)
+    }
+}
+
+/// A pattern of any characters, for trying out the reflection checks.
+#[cfg(test)]
+fn pattern(s: &str) -> Pattern {
+    Pattern(s.parse::<Grid<char>>().unwrap())
+}
+
+impl Pattern {
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    /// Whether the rows mirror each other across the line under the first `num_above`.
+    fn has_horizontal_reflection_at(&self, num_above: usize) -> bool {
+        let num_to_check = usize::min(num_above, self.height() - num_above);
+        (0..num_to_check).all(|y| self.0.row(num_above - y - 1) == self.0.row(num_above + y))
+    }
+
+    /// Finds a line of reflection other than `exclude`. Columns are checked as the rows of
+    /// the transposed pattern.
+    fn find_reflection_excluding(
+        &self,
+        exclude: Option<&ReflectionLine>,
+    ) -> Result<ReflectionLine, ()> {
+        let transposed = Pattern(self.0.transpose());
+        (1..self.height())
+            .filter(|&n| self.has_horizontal_reflection_at(n))
+            .map(ReflectionLine::Horizontal)
+            .chain(
+                (1..self.width())
+                    .filter(|&n| transposed.has_horizontal_reflection_at(n))
+                    .map(ReflectionLine::Vertical),
+            )
+            .find(|line| exclude != Some(line))
+            .ok_or(())
+    }
+
+    fn find_reflection(&self) -> Result<ReflectionLine, ()> {
+        self.find_reflection_excluding(None)
+    }
+
+    /// The line of reflection made by fixing the one smudge that makes a different line from
+    /// the one there is now, if there is such a smudge.
+    fn find_smudged_reflection(&self) -> Option<ReflectionLine> {
+        let orig_reflection = self.find_reflection().ok();
+
+        let mut copy = self.clone();
+
+        for coord in self.0.coords() {
+            copy.0[coord] = invert(self.0[coord]);
+            if let Ok(new_line) = copy.find_reflection_excluding(orig_reflection.as_ref()) {
+                return Some(new_line);
+            }
+            copy.0[coord] = self.0[coord];
+        }
+
+        None
+    }
+}
+
+#[test]
+fn test_has_horizontal_reflection_at() {
+    assert!(pattern(
+        r"#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#"
+    )
+    .has_horizontal_reflection_at(4));
+
+    assert!(pattern(
+        r"A
+B
+B
+A
+C"
+    )
+    .has_horizontal_reflection_at(2));
+
+    assert!(pattern(
+        r"A
+B
+B
+A
+C"
+    )
+    .has_horizontal_reflection_at(2));
+    assert!(pattern(
+        r"A
+A"
+    )
+    .has_horizontal_reflection_at(1));
+}
+
+#[test]
+fn test_has_vertial_reflection_at() {
+    // Columns reflect where the rows of the transposed pattern do
+    let columns = |s: &str| Pattern(pattern(s).0.transpose());
+    assert!(columns(r"AA").has_horizontal_reflection_at(1));
+    assert!(!columns(r"ABA").has_horizontal_reflection_at(1));
+    assert!(!columns(r"ABA").has_horizontal_reflection_at(2));
+    assert!(!columns(r"ABBA").has_horizontal_reflection_at(1));
+    assert!(columns(r"ABBA").has_horizontal_reflection_at(2));
+    assert!(!columns(r"ABBA").has_horizontal_reflection_at(3));
+    assert!(columns(r"AABBA").has_horizontal_reflection_at(3));
+    assert!(columns(r"AABBA").has_horizontal_reflection_at(1));
+    assert!(columns(r"XYZAA").has_horizontal_reflection_at(4));
+}
+
+#[test]
+fn test_find_reflections() {
+    assert_eq!(
+        pattern(
+            r"#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#."
+        )
+        .find_reflection(),
+        Ok(ReflectionLine::Vertical(5))
+    );
+
+    assert_eq!(
+        pattern(
+            r"#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#"
+        )
+        .find_reflection(),
+        Ok(ReflectionLine::Horizontal(4))
+    );
+
+    assert_eq!(
+        pattern(
+            r".#.####.#....
+#.#....#.#...
+###....##.###
+#.##..##.#.##
+.#.#..#.#.###
+#.######.#...
+#.##..##.####"
+        )
+        .find_reflection(),
+        Ok(ReflectionLine::Vertical(12))
+    );
+}
+
+#[test]
+fn test_find_smudged_reflections() {
+    assert_eq!(
+        pattern(
+            r".#.####
+##..#.#
+##..#.#
+.#.####
+..#..#.
+####.#.
+#.#.#.#
+.#..#.#
+##.##..
+#.#..#.
+#.#...."
+        )
+        .find_smudged_reflection(),
+        Some(ReflectionLine::Horizontal(10))
+    );
+}
+
+/// The notes on the valley, with a blank line between patterns.
+pub struct Notes(Vec<Pattern>);
+
+impl FromStr for Notes {
+    type Err = ParseGridError<AocError>;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        blocks(input)
+            .into_iter()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Notes {
+    /// The summary of the patterns' lines of reflection, leaving out patterns without one.
+    fn summarize(&self) -> usize {
+        self.0
+            .iter()
+            .flat_map(Pattern::find_reflection)
+            .map(|l| l.score())
+            .sum()
+    }
+
+    /// The summary of the lines of reflection once each pattern's smudge is fixed.
+    fn summarize_smudged(&self) -> Result<usize, SolveError> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(i, pattern)| {
+                let line = pattern.find_smudged_reflection().ok_or_else(|| {
+                    SolveError::failed(format!(
+                        "no smudge in pattern {} makes a different line of reflection",
+                        i + 1
+                    ))
+                })?;
+                Ok(line.score())
+            })
+            .sum()
+    }
+}
+
+pub fn part1(input: &str) -> Result<usize, SolveError> {
+    Ok(Notes::parse(input)?.summarize())
+}
+
+pub fn part2(input: &str) -> Result<usize, SolveError> {
+    Notes::parse(input)?.summarize_smudged()
+}
+
+impl Solver for Notes {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input.parse().map_err(SolveError::failed)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.summarize().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.summarize_smudged()?.to_string())
+    }
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), Ok(405));
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(400));
+    assert_eq!(
+        Notes::parse("#.\n.#\n\n#.\n#").err(),
+        Some(SolveError::failed(
+            "row 1 is 1 cells wide but the first row is 2"
+        ))
+    );
+    assert_eq!(
+        part2("#.\n.O"),
+        Err(SolveError::failed("unexpected 'O' at (1,1)"))
+    );
+    assert_eq!(
+        part2("#"),
+        Err(SolveError::failed(
+            "no smudge in pattern 1 makes a different line of reflection"
+        ))
+    );
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(13, 1);
@@ -0,0 +1,317 @@
+use std::{
+    fmt::{Debug, Display, Write},
+    str::FromStr,
+};
+
+use aoc_core::{cycle, Dir, Grid, ParseGridError, SolveError, Solver, Svg, Visualize};
+use tracing::debug;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AocError {
+    UnknownSquare,
+    NotRectangular,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+enum Square {
+    Ground,
+    Rounded,
+    Cube,
+}
+
+impl TryFrom<char> for Square {
+    type Error = AocError;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        Ok(match ch {
+            'O' => Square::Rounded,
+            '#' => Square::Cube,
+            '.' => Square::Ground,
+            _ => return Err(AocError::UnknownSquare),
+        })
+    }
+}
+
+impl Square {
+    fn symbol(&self) -> char {
+        match self {
+            Square::Ground => '.',
+            Square::Cube => '#',
+            Square::Rounded => 'O',
+        }
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char(self.symbol())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Hash)]
+pub struct Platform(Grid<Square>);
+
+impl FromStr for Platform {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse().map_err(|e| match e {
+            ParseGridError::InvalidCell { error, .. } => error,
+            ParseGridError::Ragged { .. } => AocError::NotRectangular,
+        })?))
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Visualize for Platform {
+    fn frame(&self) -> Grid<char> {
+        self.0.map(Square::symbol)
+    }
+
+    fn color(&self, ch: char) -> [u8; 3] {
+        match ch {
+            '#' => [110, 110, 120],
+            'O' => [230, 150, 40],
+            _ => [20, 20, 30],
+        }
+    }
+}
+
+impl Platform {
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    fn slide_piece_north(&mut self, row: usize, col: usize) {
+        assert_eq!(self.0[(col, row)], Square::Rounded);
+
+        let stop_point = (0..row)
+            .rev()
+            .find(|search_row| self.0[(col, *search_row)] != Square::Ground);
+        let new_row = stop_point.map(|r| r + 1).unwrap_or(0);
+        self.0[(col, row)] = Square::Ground;
+        self.0[(col, new_row)] = Square::Rounded;
+    }
+
+    fn slide_north(&mut self) {
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                if self.0[(col, row)] == Square::Rounded {
+                    self.slide_piece_north(row, col);
+                }
+            }
+        }
+    }
+
+    /// Tilts the platform towards `dir` by turning it so that side faces north, tilting it
+    /// north, then turning it back.
+    fn slide(&mut self, dir: Dir) {
+        type Turn = fn(&Grid<Square>) -> Grid<Square>;
+        let (turn, turn_back): (Turn, Turn) = match dir {
+            Dir::North => return self.slide_north(),
+            Dir::West => (Grid::rotate_cw, Grid::rotate_ccw),
+            Dir::East => (Grid::rotate_ccw, Grid::rotate_cw),
+            Dir::South => (Grid::flip_vertical, Grid::flip_vertical),
+        };
+        self.0 = turn(&self.0);
+        self.slide_north();
+        self.0 = turn_back(&self.0);
+    }
+
+    fn slide_cycle(&mut self) {
+        self.slide_cycle_with(|_, _| {});
+    }
+
+    /// Tilts the platform north, west, south, then east, calling `on_tilt` after each.
+    pub fn slide_cycle_with(&mut self, mut on_tilt: impl FnMut(&Platform, Dir)) {
+        for dir in [Dir::North, Dir::West, Dir::South, Dir::East] {
+            self.slide(dir);
+            on_tilt(self, dir);
+        }
+    }
+
+    /// Spins the platform `iters` times, skipping ahead once it starts repeating.
+    fn slide_cycle_many(&mut self, iters: usize) {
+        let spin = |mut platform: Platform| {
+            platform.slide_cycle();
+            platform
+        };
+        let spins = cycle::simulate(self.clone(), spin);
+        debug!(
+            "found a cycle: after {} spins, the platform repeats every {}",
+            spins.offset(),
+            spins.period()
+        );
+        *self = spins.nth(iters).clone();
+    }
+
+    /// Total load on the north support beams.
+    pub fn get_north_load(&self) -> usize {
+        (0..self.height())
+            .map(|row| {
+                (0..self.width())
+                    .map(|col| match self.0[(col, row)] {
+                        Square::Rounded => self.height() - row,
+                        _ => 0,
+                    })
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+#[test]
+fn test_slide_north() {
+    let mut grid: Platform = TEST_STR.parse().unwrap();
+
+    let expected: Platform = r"OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#...."
+        .parse()
+        .unwrap();
+
+    grid.slide_north();
+
+    assert_eq!(grid, expected);
+}
+
+#[test]
+fn test_display() {
+    let mut grid: Platform = TEST_STR.parse().unwrap();
+    for _ in 0..3 {
+        grid.slide_cycle();
+    }
+    aoc_core::assert_snapshot!("platform_after_3_cycles", grid);
+}
+
+#[test]
+fn test_slide_cycle() {
+    let mut grid: Platform = TEST_STR.parse().unwrap();
+
+    let mut tilts = vec![];
+    grid.slide_cycle_with(|platform, dir| tilts.push((dir, platform.frame())));
+    assert_eq!(
+        grid,
+        r".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."
+            .parse()
+            .unwrap()
+    );
+    assert_eq!(
+        tilts.iter().map(|(dir, _)| *dir).collect::<Vec<_>>(),
+        [Dir::North, Dir::West, Dir::South, Dir::East]
+    );
+    assert_eq!(tilts[0].1.row(0), "OOOO.#.O..".chars().collect::<Vec<_>>());
+    assert_eq!(tilts[3].1, grid.frame());
+    grid.slide_cycle();
+    assert_eq!(
+        grid,
+        r".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#..OO###..
+#.OOO#...O"
+            .parse()
+            .unwrap()
+    );
+    grid.slide_cycle();
+    assert_eq!(
+        grid,
+        r".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O"
+            .parse()
+            .unwrap()
+    );
+}
+
+pub fn part1(input: &str) -> usize {
+    let mut grid: Platform = input.parse().unwrap();
+    grid.slide_north();
+    grid.get_north_load()
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_STR), 136);
+}
+
+pub fn part2(input: &str) -> usize {
+    let mut grid: Platform = input.parse().unwrap();
+
+    grid.slide_cycle_many(1000000000);
+    grid.get_north_load()
+}
+
+impl Solver for Platform {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input
+            .parse()
+            .map_err(|e| SolveError::failed(format!("{e:?}")))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let mut platform = self.clone();
+        platform.slide_north();
+        Ok(platform.get_north_load().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        let mut platform = self.clone();
+        platform.slide_cycle_many(1000000000);
+        Ok(platform.get_north_load().to_string())
+    }
+
+    /// The platform tilted north, as part 1 weighs it.
+    fn picture(&self) -> Option<Svg> {
+        let mut platform = self.clone();
+        platform.slide_north();
+        let mut svg = Svg::new();
+        svg.frame(&platform);
+        Some(svg)
+    }
+}
+
+#[cfg(test)]
+const TEST_STR: &str = aoc_core::examples::get(14, 1);
@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use aoc_core::{AocError, SolveError, Solver};
+
+fn hash(input: &str) -> u8 {
+    input
+        .as_bytes()
+        .iter()
+        .fold(0_u8, |cur, ch| cur.wrapping_add(*ch).wrapping_mul(17))
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(hash("HASH"), 52);
+    assert_eq!(hash("rn=1"), 30);
+    assert_eq!(hash("rn"), 0);
+}
+
+pub fn part1(input: &str) -> u64 {
+    input.trim_end().split(',').map(|s| hash(s) as u64).sum()
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), 1320);
+}
+
+/// One step of the initialization sequence.
+enum Step {
+    Remove { label: String },
+    Insert { label: String, focal_length: u8 },
+}
+
+impl FromStr for Step {
+    type Err = AocError;
+
+    fn from_str(instruction: &str) -> Result<Self, Self::Err> {
+        if let Some(label) = instruction.strip_suffix('-') {
+            return Ok(Step::Remove {
+                label: label.to_string(),
+            });
+        }
+        let (label, focal_length) = instruction
+            .split_once('=')
+            .and_then(|(label, focal_length)| Some((label, focal_length.parse().ok()?)))
+            .ok_or_else(|| AocError::malformed(instruction, "label- or label=focal length"))?;
+        Ok(Step::Insert {
+            label: label.to_string(),
+            focal_length,
+        })
+    }
+}
+
+#[derive(Clone)]
+struct Lens {
+    label: String,
+    focal_length: u8,
+}
+
+/// The 256 boxes, each holding lenses in the order they were put in.
+pub struct Boxes(Vec<Vec<Lens>>);
+
+impl Default for Boxes {
+    fn default() -> Self {
+        Boxes(vec![vec![]; 256])
+    }
+}
+
+impl Boxes {
+    fn delete(&mut self, label: &str) {
+        let b = &mut self.0[hash(label) as usize];
+        if let Some(pos) = b.iter().position(|lens| lens.label == label) {
+            b.remove(pos);
+        }
+    }
+
+    fn insert(&mut self, label: &str, focal_length: u8) {
+        let b = &mut self.0[hash(label) as usize];
+        if let Some(old_lens) = b.iter_mut().find(|lens| lens.label == label) {
+            old_lens.focal_length = focal_length;
+        } else {
+            b.push(Lens {
+                label: label.to_string(),
+                focal_length,
+            });
+        }
+    }
+
+    fn apply(&mut self, step: &Step) {
+        match step {
+            Step::Remove { label } => self.delete(label),
+            Step::Insert {
+                label,
+                focal_length,
+            } => self.insert(label, *focal_length),
+        }
+    }
+
+    /// Carries out each comma-separated step of `list` in turn, stopping at the first that
+    /// can't be read.
+    pub fn apply_list(&mut self, list: &str) -> Result<(), AocError> {
+        list.trim_end().split(',').try_for_each(|instruction| {
+            self.apply(&instruction.parse()?);
+            Ok(())
+        })
+    }
+
+    pub fn get_focusing_power(&self) -> u64 {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(box_idx, b)| {
+                b.iter()
+                    .enumerate()
+                    .map(|(lens_idx, lens)| {
+                        (1 + box_idx as u64) * (1 + lens_idx as u64) * (lens.focal_length as u64)
+                    })
+                    .sum::<u64>()
+            })
+            .sum()
+    }
+}
+
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    let mut boxes = Boxes::default();
+    boxes.apply_list(input)?;
+    Ok(boxes.get_focusing_power())
+}
+
+/// The initialization sequence, with steps separated by commas.
+pub struct InitSequence {
+    /// Each step as it's written, which is what part 1 hashes
+    text: Vec<String>,
+    steps: Vec<Step>,
+}
+
+impl Solver for InitSequence {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let text: Vec<String> = input.trim_end().split(',').map(String::from).collect();
+        let steps = text
+            .iter()
+            .map(|step| step.parse())
+            .collect::<Result<_, AocError>>()?;
+        Ok(Self { text, steps })
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let sum: u64 = self.text.iter().map(|step| hash(step) as u64).sum();
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        let mut boxes = Boxes::default();
+        self.steps.iter().for_each(|step| boxes.apply(step));
+        Ok(boxes.get_focusing_power().to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(145));
+    assert_eq!(
+        part2("rn=1,cm,qp=3"),
+        Err(AocError::malformed("cm", "label- or label=focal length"))
+    );
+    assert_eq!(
+        part2("rn=1,qp=300"),
+        Err(AocError::malformed(
+            "qp=300",
+            "label- or label=focal length"
+        ))
+    );
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(15, 1);
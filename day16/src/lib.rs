@@ -0,0 +1,335 @@
+use std::{fmt::Display, str::FromStr};
+
+use aoc_core::{
+    BitGrid, Coord, Dir, Grid, Params, ParseGridError, SolveError, Solver, Svg, Visualize,
+};
+
+/// What's on a tile of the contraption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    /// `/`, turning beams heading east to the north
+    MirrorUp,
+    /// `\`, turning beams heading east to the south
+    MirrorDown,
+    /// `|`, splitting beams heading east or west to the north and south
+    SplitterNorthSouth,
+    /// `-`, splitting beams heading north or south to the east and west
+    SplitterEastWest,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = ();
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '.' => Ok(Tile::Empty),
+            '/' => Ok(Tile::MirrorUp),
+            '\\' => Ok(Tile::MirrorDown),
+            '|' => Ok(Tile::SplitterNorthSouth),
+            '-' => Ok(Tile::SplitterEastWest),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ch = match self {
+            Tile::Empty => '.',
+            Tile::MirrorUp => '/',
+            Tile::MirrorDown => '\\',
+            Tile::SplitterNorthSouth => '|',
+            Tile::SplitterEastWest => '-',
+        };
+        write!(f, "{ch}")
+    }
+}
+
+pub struct Contraption {
+    tiles: Grid<Tile>,
+    /// Where part 1's beam comes in and the way it's heading
+    start: (Coord, Dir),
+}
+
+/// The tiles beams have gone through heading each way, indexed by `Dir as usize`.
+pub struct EnergizedMap([BitGrid; 4]);
+
+impl EnergizedMap {
+    fn new(width: usize, height: usize) -> Self {
+        Self(std::array::from_fn(|_| BitGrid::new(width, height)))
+    }
+
+    /// The ways beams have gone through `coord`.
+    fn dirs_at(&self, coord: Coord) -> impl Iterator<Item = Dir> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter(move |&dir| self.0[dir as usize].get(coord))
+    }
+
+    /// How many tiles have had a beam go through them.
+    pub fn count(&self) -> usize {
+        let [first, rest @ ..] = &self.0;
+        let mut any = first.clone();
+        rest.iter().for_each(|dir| any.or(dir));
+        any.count()
+    }
+}
+
+impl Display for Contraption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.tiles, f)
+    }
+}
+
+impl Visualize for EnergizedMap {
+    /// Each tile's direction if one beam has gone through it, or how many have.
+    fn frame(&self) -> Grid<char> {
+        let (width, height) = (self.0[0].width(), self.0[0].height());
+        Grid::from_fn(width, height, |coord| {
+            let mut dirs = self.dirs_at(coord);
+            match (dirs.next(), dirs.count()) {
+                (None, _) => '.',
+                (Some(Dir::North), 0) => '^',
+                (Some(Dir::South), 0) => 'v',
+                (Some(Dir::East), 0) => '>',
+                (Some(Dir::West), 0) => '<',
+                (Some(_), n) => char::from_digit(n as u32 + 1, 10).unwrap(),
+            }
+        })
+    }
+
+    /// Brighter the more beams have gone through a tile.
+    fn color(&self, ch: char) -> [u8; 3] {
+        match ch {
+            '.' => [20, 20, 30],
+            '2' => [250, 200, 60],
+            '3' | '4' => [255, 240, 160],
+            _ => [220, 140, 30],
+        }
+    }
+}
+
+impl FromStr for Contraption {
+    type Err = ParseGridError<()>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            tiles: s.parse()?,
+            start: Self::PART1_START,
+        })
+    }
+}
+
+impl Contraption {
+    /// Where part 1's beam comes in, heading east into the top left tile.
+    pub const PART1_START: (Coord, Dir) = (Coord::new(-1, 0), Dir::East);
+
+    fn height(&self) -> usize {
+        self.tiles.height()
+    }
+    fn width(&self) -> usize {
+        self.tiles.width()
+    }
+
+    /// Most tiles energized by a beam entering from any edge tile.
+    fn max_energized(&self) -> usize {
+        (0..self.width() as i64)
+            .flat_map(|i| {
+                [
+                    (Coord::new(i, self.height() as i64), Dir::North),
+                    (Coord::new(i, -1), Dir::South),
+                ]
+            })
+            .chain((0..self.height() as i64).flat_map(|i| {
+                [
+                    (Coord::new(self.width() as i64, i), Dir::West),
+                    (Coord::new(-1, i), Dir::East),
+                ]
+            }))
+            .map(|start| self.get_energized_map(start).count())
+            .max()
+            // With no tiles there's no edge to come in from
+            .unwrap_or(0)
+    }
+
+    fn get_energized_map(&self, start: (Coord, Dir)) -> EnergizedMap {
+        self.trace_beam(start, |_| {})
+    }
+
+    /// Follows a beam coming in from the coordinate and direction in `start`, calling `on_step`
+    /// each time every beam has moved one more tile.
+    pub fn trace_beam(
+        &self,
+        start: (Coord, Dir),
+        mut on_step: impl FnMut(&EnergizedMap),
+    ) -> EnergizedMap {
+        let mut energized = EnergizedMap::new(self.width(), self.height());
+        let mut beams = vec![start];
+        while !beams.is_empty() {
+            let mut moved = vec![];
+            for (prev, dir) in beams {
+                self.move_beam(&mut energized, prev, dir, &mut moved);
+            }
+            if !moved.is_empty() {
+                on_step(&energized);
+            }
+            beams = moved;
+        }
+        energized
+    }
+
+    /// Moves a beam one tile on from `prev`, adding where it goes from there to `modified_tiles`
+    /// unless another beam has already gone through that tile in the same direction.
+    fn move_beam(
+        &self,
+        energized: &mut EnergizedMap,
+        prev: Coord,
+        dir: Dir,
+        modified_tiles: &mut Vec<(Coord, Dir)>,
+    ) {
+        use Dir::*;
+        use Tile::*;
+        let cur = prev + dir;
+        // Mark the tile as gone through this way. If it already was, don't do anything else.
+        if !self.tiles.contains(cur) || !energized.0[dir as usize].set(cur) {
+            return;
+        }
+        // Push the next directions to check
+        match self.tiles[cur] {
+            Empty => modified_tiles.push((cur, dir)),
+            MirrorUp => modified_tiles.push((
+                cur,
+                match dir {
+                    North => East,
+                    South => West,
+                    East => North,
+                    West => South,
+                },
+            )),
+            MirrorDown => modified_tiles.push((
+                cur,
+                match dir {
+                    North => West,
+                    South => East,
+                    East => South,
+                    West => North,
+                },
+            )),
+            SplitterNorthSouth => match dir {
+                North | South => modified_tiles.push((cur, dir)),
+                East | West => {
+                    modified_tiles.push((cur, North));
+                    modified_tiles.push((cur, South));
+                }
+            },
+            SplitterEastWest => match dir {
+                East | West => modified_tiles.push((cur, dir)),
+                North | South => {
+                    modified_tiles.push((cur, East));
+                    modified_tiles.push((cur, West));
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(16, 1);
+
+#[test]
+fn test_count_energized() {
+    let grid = TEST_INPUT.parse::<Contraption>().unwrap();
+    aoc_core::assert_snapshot!("contraption", grid);
+
+    let energized = grid.get_energized_map(Contraption::PART1_START);
+    aoc_core::assert_snapshot!("energized", energized.frame());
+    assert_eq!(energized.count(), 46);
+}
+
+#[test]
+fn test_trace_beam() {
+    let grid = TEST_INPUT.parse::<Contraption>().unwrap();
+    let mut frames = vec![];
+    let energized = grid.trace_beam(Contraption::PART1_START, |map| frames.push(map.frame()));
+    assert_eq!(frames.last(), Some(&energized.frame()));
+    assert_eq!(frames[0].row(0), ">.........".chars().collect::<Vec<_>>());
+    assert_eq!(frames[1].row(0), ">>........".chars().collect::<Vec<_>>());
+    assert_eq!(frames[2].row(1), ".v........".chars().collect::<Vec<_>>());
+}
+
+pub fn part1(input: &str) -> usize {
+    let grid: Contraption = input.parse().unwrap();
+    grid.get_energized_map(Contraption::PART1_START).count()
+}
+
+pub fn part2(input: &str) -> usize {
+    input.parse::<Contraption>().unwrap().max_energized()
+}
+
+impl Solver for Contraption {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input.parse().map_err(SolveError::failed)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.get_energized_map(self.start).count().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.max_energized().to_string())
+    }
+
+    /// The tiles part 1's beam energizes.
+    fn picture(&self) -> Option<Svg> {
+        let mut svg = Svg::new();
+        svg.frame(&self.get_energized_map(self.start));
+        Some(svg)
+    }
+
+    /// `start_x`, `start_y` and `start_dir` (N, S, E or W) move part 1's beam, which starts
+    /// outside the grid.
+    fn configure(&mut self, params: &Params) -> Result<(), SolveError> {
+        params.only(&["start_x", "start_y", "start_dir"])?;
+        let (mut at, mut dir) = self.start;
+        at.x = params.get("start_x")?.unwrap_or(at.x);
+        at.y = params.get("start_y")?.unwrap_or(at.y);
+        dir = params.get("start_dir")?.unwrap_or(dir);
+        self.start = (at, dir);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), 51);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        ".|\n.x\n".parse::<Contraption>().err(),
+        Some(ParseGridError::InvalidCell {
+            at: Coord::new(1, 1),
+            ch: 'x',
+            error: ()
+        })
+    );
+    let empty = Contraption::parse("").unwrap();
+    assert_eq!(empty.part1(), Ok("0".to_string()));
+    assert_eq!(empty.part2(), Ok("0".to_string()));
+}
+
+#[test]
+fn test_configure() {
+    let mut contraption = Contraption::parse(TEST_INPUT).unwrap();
+    let mut params = Params::new();
+    params.set("start_x", 3);
+    params.set("start_y", -1);
+    params.set("start_dir", "S");
+    contraption.configure(&params).unwrap();
+    assert_eq!(contraption.part1(), Ok("51".to_string()));
+
+    params.set("start_dir", "sideways");
+    assert!(contraption.configure(&params).is_err());
+}
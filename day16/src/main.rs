@@ -1,273 +1,62 @@
-use std::{
-    collections::HashSet,
-    fmt::{Display, Write},
-    ops::Add,
-    ops::Index,
-    ops::IndexMut,
-    str::FromStr,
-};
+use std::{path::PathBuf, time::Duration};
 
-struct Grid {
-    tiles: Vec<String>,
-}
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
-enum Dir {
-    North,
-    South,
-    East,
-    West,
-}
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
-struct Coord {
-    x: isize,
-    y: isize,
-}
-
-impl Add<Dir> for Coord {
-    type Output = Coord;
-
-    fn add(self, rhs: Dir) -> Self::Output {
-        match rhs {
-            Dir::North => Self {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Dir::South => Self {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Dir::East => Self {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Dir::West => Self {
-                x: self.x - 1,
-                y: self.y,
-            },
-        }
-    }
-}
+use aoc_core::{CommonArgs, GifRecorder, Recorder, Terminal};
+use clap::Parser;
+use day16::{part1, part2, Contraption};
 
-impl Index<&Coord> for Grid {
-    type Output = u8;
+#[derive(Parser)]
+#[command(mut_arg("input", |input| input
+    .default_value("src/input.txt")
+    .help("Puzzle input to read instead of `src/input.txt`, or - for stdin")))]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
 
-    fn index(&self, index: &Coord) -> &Self::Output {
-        &self.tiles[index.y as usize].as_bytes()[index.x as usize]
-    }
-}
-
-#[derive(Default)]
-struct EnergizedMap(Vec<Vec<HashSet<Dir>>>);
+    /// Show part 1's beam spreading through the contraption instead of solving
+    #[arg(long)]
+    visualize: bool,
 
-impl Index<&Coord> for EnergizedMap {
-    type Output = HashSet<Dir>;
+    /// Write a GIF of part 1's beam spreading through the contraption to this file
+    #[arg(long, value_name = "FILE")]
+    animate: Option<PathBuf>,
 
-    fn index(&self, index: &Coord) -> &Self::Output {
-        &self.0[index.y as usize][index.x as usize]
-    }
-}
-
-impl IndexMut<&Coord> for EnergizedMap {
-    fn index_mut(&mut self, index: &Coord) -> &mut Self::Output {
-        &mut self.0[index.y as usize][index.x as usize]
-    }
-}
-
-impl EnergizedMap {
-    fn count(&self) -> usize {
-        self.0
-            .iter()
-            .map(|l| {
-                l.iter()
-                    .map(|t| if t.is_empty() { 0 } else { 1 })
-                    .sum::<usize>()
-            })
-            .sum()
-    }
-}
-
-impl Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for line in self.tiles.iter() {
-            f.write_str(line)?;
-            f.write_char('\n')?;
-        }
-        Ok(())
-    }
-}
-
-impl Display for EnergizedMap {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for line in self.0.iter() {
-            for tile in line.iter() {
-                f.write_char(match tile.len() {
-                    0 => '.',
-                    1 => match tile.iter().next().unwrap() {
-                        Dir::North => '^',
-                        Dir::South => 'v',
-                        Dir::East => '>',
-                        Dir::West => '<',
-                    },
-                    0..=9 => format!("{}", tile.len()).chars().next().unwrap(),
-                    _ => '@',
-                })?;
-            }
-            f.write_char('\n')?;
-        }
-        Ok(())
-    }
+    /// Milliseconds to show each step of --visualize or --animate for
+    #[arg(long, value_name = "MS", default_value_t = 50)]
+    delay: u64,
 }
 
-impl FromStr for Grid {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            tiles: s.lines().map(|l| l.to_string()).collect(),
-        })
-    }
+/// Sends the energized tiles after each step of part 1's beam to `recorder`.
+fn record_beam(contraption: &Contraption, recorder: &mut impl Recorder) {
+    let mut step = 0;
+    contraption.trace_beam(Contraption::PART1_START, |energized| {
+        step += 1;
+        let caption = format!("step {step}: {} tiles energized", energized.count());
+        recorder
+            .record(energized, &caption)
+            .expect("should record the beam");
+    });
 }
 
-impl Grid {
-    fn height(&self) -> usize {
-        self.tiles.len()
-    }
-    fn width(&self) -> usize {
-        self.tiles[0].len()
-    }
-    fn is_in_bounds(&self, coord: &Coord) -> bool {
-        coord.x >= 0
-            && coord.y >= 0
-            && (coord.x as usize) < self.width()
-            && (coord.y as usize) < self.height()
-    }
-
-    fn get_energized_map(&self, start_coord: Coord, start_dir: Dir) -> EnergizedMap {
-        use Dir::*;
-        let mut energized = EnergizedMap(vec![vec![HashSet::new(); self.width()]; self.height()]);
-        let mut modified_tiles = vec![(start_coord, start_dir)];
-
-        while let Some((prev, dir)) = modified_tiles.pop() {
-            let cur = prev + dir;
-            if !self.is_in_bounds(&cur) {
-                continue;
-            }
-            // Insert incoming direction into the set. If it was already there, don't do anything else.
-            if !energized[&cur].insert(dir) {
-                continue;
-            }
-            // Push the next directions to check
-            match self[&cur] {
-                b'.' => modified_tiles.push((cur, dir)),
-                b'/' => modified_tiles.push((
-                    cur,
-                    match dir {
-                        North => East,
-                        South => West,
-                        East => North,
-                        West => South,
-                    },
-                )),
-                b'\\' => modified_tiles.push((
-                    cur,
-                    match dir {
-                        North => West,
-                        South => East,
-                        East => South,
-                        West => North,
-                    },
-                )),
-                b'|' => match dir {
-                    North | South => modified_tiles.push((cur, dir)),
-                    East | West => {
-                        modified_tiles.push((cur, North));
-                        modified_tiles.push((cur, South));
-                    }
-                },
-                b'-' => match dir {
-                    East | West => modified_tiles.push((cur, dir)),
-                    North | South => {
-                        modified_tiles.push((cur, East));
-                        modified_tiles.push((cur, West));
-                    }
-                },
-                ch => panic!("Unexpected grid element {}", ch),
+fn main() {
+    let args = Args::parse();
+    aoc_core::init_logging(args.common.verbose);
+    let input = &args.common.input.read_or_exit();
+    if args.visualize || args.animate.is_some() {
+        let contraption: Contraption = input.parse().unwrap_or_else(|e| {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        });
+        let delay = Duration::from_millis(args.delay);
+        match args.animate {
+            Some(path) => {
+                let mut gif = GifRecorder::create(&path, 4, delay).expect("should create the GIF");
+                record_beam(&contraption, &mut gif);
+                gif.finish().expect("should finish the GIF");
             }
+            None => record_beam(&contraption, &mut Terminal::stdout(delay)),
         }
-        energized
+        return;
     }
-}
-
-const TEST_INPUT: &str = r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....";
-
-#[test]
-fn test_count_energized() {
-    let grid = TEST_INPUT.parse::<Grid>().unwrap();
-
-    println!("{}", grid);
-
-    let energized = grid.get_energized_map(Coord { x: -1, y: 0 }, Dir::East);
-    println!("{}", energized);
-    assert_eq!(energized.count(), 46);
-}
-
-fn part1(input: &str) -> usize {
-    let grid: Grid = input.parse().unwrap();
-    grid.get_energized_map(Coord { x: -1, y: 0 }, Dir::East)
-        .count()
-}
-
-fn part2(input: &str) -> usize {
-    let grid: Grid = input.parse().unwrap();
-
-    (0..grid.width() as isize)
-        .flat_map(|i| {
-            [
-                (
-                    Coord {
-                        x: i,
-                        y: grid.height() as isize,
-                    },
-                    Dir::North,
-                ),
-                (Coord { x: i, y: -1 }, Dir::South),
-            ]
-        })
-        .chain((0..grid.height() as isize).flat_map(|i| {
-            [
-                (
-                    Coord {
-                        x: grid.width() as isize,
-                        y: i,
-                    },
-                    Dir::West,
-                ),
-                (Coord { x: -1, y: i }, Dir::East),
-            ]
-        }))
-        .map(|(coord, dir)| grid.get_energized_map(coord, dir).count())
-        .max()
-        .unwrap()
-}
-
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 51);
-}
-
-fn main() {
-    let input = include_str!("input.txt");
     println!("part 1: {}", part1(input));
     println!("part 2: {}", part2(input))
 }
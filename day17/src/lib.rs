@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use aoc_core::{pathfinding::astar, Coord, Dir, Grid, ParseGridError, SolveError, Solver};
+
+use Dir::*;
+
+/// Where a crucible is and how it got there, which limits where it can go next.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct Crucible {
+    coord: Coord,
+    dir: Dir,
+    steps_in_dir: u8,
+}
+
+/// Heat lost entering each block.
+#[derive(Clone)]
+pub struct Map(Grid<u8>);
+
+impl FromStr for Map {
+    type Err = ParseGridError<()>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Grid::parse(s, |ch| {
+            Ok(ch.to_digit(10).ok_or(())? as u8)
+        })?))
+    }
+}
+
+impl Map {
+    fn find_min_basic(&self) -> Option<u64> {
+        self.find_min(0, 3)
+    }
+
+    /// Least heat lost getting from the top left to the bottom right with a crucible that has
+    /// to go at least `min_dist` blocks before turning or stopping, and at most `max_dist`.
+    fn find_min(&self, min_dist: u8, max_dist: u8) -> Option<u64> {
+        let end = Coord::new(self.0.width() as i64 - 1, self.0.height() as i64 - 1);
+        // Every block left to go loses at least this much heat
+        let min_loss = u64::from(self.0.iter().copied().min()?);
+        let start = Crucible {
+            coord: Coord::new(0, 0),
+            dir: East,
+            steps_in_dir: 0,
+        };
+        let moves = |&Crucible {
+                         coord,
+                         dir,
+                         steps_in_dir,
+                     }: &Crucible| {
+            [dir, dir.left(), dir.right()]
+                .into_iter()
+                .filter_map(move |next_dir| {
+                    let is_straight = next_dir == dir;
+                    if !is_straight && steps_in_dir < min_dist {
+                        return None;
+                    }
+                    let next_steps = if is_straight { steps_in_dir + 1 } else { 1 };
+                    if next_steps > max_dist {
+                        return None;
+                    }
+                    let next_coord = coord + next_dir;
+                    let loss = *self.0.get(next_coord)?;
+                    let next = Crucible {
+                        coord: next_coord,
+                        dir: next_dir,
+                        steps_in_dir: next_steps,
+                    };
+                    Some((next, u64::from(loss)))
+                })
+        };
+        let (_, loss) = astar(
+            start,
+            moves,
+            |crucible| (end - crucible.coord).manhattan() as u64 * min_loss,
+            |crucible| crucible.coord == end && crucible.steps_in_dir >= min_dist,
+        )?;
+        Some(loss)
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    let map: Map = input.parse().unwrap();
+
+    map.find_min_basic().unwrap()
+}
+
+#[test]
+fn test_find_min() {
+    // assert_eq!("1234".parse::<Map>().unwrap().find_min(), Some(9));
+    // assert_eq!("12345".parse::<Map>().unwrap().find_min(), None);
+    assert_eq!(
+        "191\n111\n991".parse::<Map>().unwrap().find_min_basic(),
+        Some(4)
+    );
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT1), 102);
+}
+
+pub fn part2(input: &str) -> u64 {
+    let map: Map = input.parse().unwrap();
+
+    map.find_min(4, 10).unwrap()
+}
+
+impl Solver for Map {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input.parse().map_err(SolveError::failed)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self
+            .find_min_basic()
+            .ok_or(SolveError::failed("no path to the bottom right"))?
+            .to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self
+            .find_min(4, 10)
+            .ok_or(SolveError::failed("no path to the bottom right"))?
+            .to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT1), 94);
+
+    assert_eq!(part2(TEST_INPUT2), 71);
+}
+
+#[cfg(test)]
+const TEST_INPUT1: &str = aoc_core::examples::get(17, 1);
+#[cfg(test)]
+const TEST_INPUT2: &str = aoc_core::examples::get(17, 2);
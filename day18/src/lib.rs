@@ -0,0 +1,336 @@
+use std::fmt::Display;
+
+use aoc_core::{
+    parse_lines, polygon, AocError, Coord, Dir, Grid, SolveError, Solver, Svg, Visualize,
+};
+use tracing::debug;
+
+#[derive(Debug, Clone)]
+struct UnrootedLine {
+    dir: Dir,
+    len: i64,
+}
+
+#[derive(Debug, Clone)]
+struct BoundaryLine {
+    left: i64,
+    right: i64,
+    top: i64,
+    bottom: i64,
+}
+
+#[derive(Debug)]
+struct Map {
+    boundaries: Vec<BoundaryLine>,
+    /// Where each line ends, in order around the trench
+    corners: Vec<Coord>,
+    bounds: Rectangle,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Rectangle {
+    left: i64,
+    right: i64,
+    top: i64,
+    bottom: i64,
+}
+
+impl Rectangle {
+    fn width(&self) -> i64 {
+        self.right - self.left + 1
+    }
+    fn height(&self) -> i64 {
+        self.bottom - self.top + 1
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Map {} x {} {:?}",
+            self.bounds.width(),
+            self.bounds.height(),
+            self.bounds
+        )?;
+        write!(f, "{}", self.frame())
+    }
+}
+
+impl Visualize for Map {
+    /// The trench, dug out with `#`.
+    fn frame(&self) -> Grid<char> {
+        let mut grid = self.blank_grid();
+        for BoundaryLine {
+            left,
+            right,
+            top,
+            bottom,
+        } in self.boundaries.iter()
+        {
+            for y in *top..=*bottom {
+                for x in *left..=*right {
+                    grid[self.grid_coord(x, y)] = '#';
+                }
+            }
+        }
+        grid
+    }
+}
+
+impl Map {
+    fn from_part1_str(input: &str) -> Result<Self, AocError> {
+        Self::from_lines(parse_lines(input, |l| {
+            let malformed = || AocError::malformed(l, "a direction and a length");
+            let mut split = l.split_whitespace();
+            let dir = split
+                .next()
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(malformed)?;
+            // Positive, and short enough that the lines can't go beyond i64's range
+            let len: u32 = split
+                .next()
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(malformed)?;
+            Ok(UnrootedLine {
+                dir,
+                len: len.into(),
+            })
+        })?)
+    }
+    fn from_part2_str(input: &str) -> Result<Self, AocError> {
+        Self::from_lines(parse_lines(input, |line| {
+            let malformed = || AocError::malformed(line, "a color like (#70c710)");
+            let color = line
+                .split_whitespace()
+                .nth(2)
+                .and_then(|c| c.strip_prefix("(#")?.strip_suffix(')'))
+                .filter(|c| c.len() == 6 && c.is_ascii())
+                .ok_or_else(malformed)?;
+            let len = i64::from_str_radix(&color[..5], 16).map_err(|_| malformed())?;
+            let dir = match color.as_bytes()[5] {
+                b'0' => Dir::East,
+                b'1' => Dir::South,
+                b'2' => Dir::West,
+                b'3' => Dir::North,
+                _ => return Err(malformed()),
+            };
+            Ok(UnrootedLine { dir, len })
+        })?)
+    }
+
+    fn from_lines(lines: Vec<UnrootedLine>) -> Result<Self, AocError> {
+        let mut current_coord = Coord { x: 0, y: 0 };
+        let mut max_x = 0;
+        let mut min_x = 0;
+        let mut max_y = 0;
+        let mut min_y = 0;
+        let mut corners = vec![];
+
+        let boundaries: Vec<BoundaryLine> = lines
+            .into_iter()
+            .map(|UnrootedLine { dir, len }| {
+                let prev_coord = current_coord;
+                current_coord += dir * len;
+
+                min_x = std::cmp::min(min_x, current_coord.x);
+                min_y = std::cmp::min(min_y, current_coord.y);
+                max_x = std::cmp::max(max_x, current_coord.x);
+                max_y = std::cmp::max(max_y, current_coord.y);
+                corners.push(current_coord);
+
+                BoundaryLine {
+                    left: std::cmp::min(prev_coord.x, current_coord.x),
+                    right: std::cmp::max(prev_coord.x, current_coord.x),
+                    top: std::cmp::min(prev_coord.y, current_coord.y),
+                    bottom: std::cmp::max(prev_coord.y, current_coord.y),
+                }
+            })
+            .collect();
+
+        if current_coord != (Coord { x: 0, y: 0 }) {
+            return Err(AocError::Invalid(
+                "the dig plan doesn't end where it started".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            boundaries,
+            corners,
+            bounds: Rectangle {
+                left: min_x,
+                right: max_x,
+                top: min_y,
+                bottom: max_y,
+            },
+        })
+    }
+
+    /// Number of tiles dug out: the trench itself and everything it encloses.
+    fn lagoon_size(&self) -> i64 {
+        polygon::interior_points(&self.corners) + polygon::perimeter(&self.corners)
+    }
+
+    /// The dug out lagoon, with the trench's tiles along its edge.
+    fn to_svg(&self) -> Svg {
+        let mut svg = Svg::new();
+        let center = |c: &Coord| (c.x as f64 + 0.5, c.y as f64 + 0.5);
+        let corners: Vec<_> = self.corners.iter().map(center).collect();
+        svg.polygon(&corners, "tan", "saddlebrown", 1.0);
+        svg
+    }
+
+    /// An empty grid covering the map's bounds.
+    fn blank_grid(&self) -> Grid<char> {
+        Grid::new(
+            self.bounds.width() as usize,
+            self.bounds.height() as usize,
+            '.',
+        )
+    }
+
+    /// Where a point on the map is in `blank_grid`.
+    fn grid_coord(&self, x: i64, y: i64) -> Coord {
+        Coord::new(x - self.bounds.left, y - self.bounds.top)
+    }
+}
+
+pub fn part1(input: &str) -> Result<i64, AocError> {
+    let map = Map::from_part1_str(input)?;
+
+    debug!("{map}");
+    Ok(map.lagoon_size())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1("D 10000\nR 10\nU 10000\nL 10"), Ok(10001 * 11));
+
+    assert_eq!(
+        part1("D 8\nR 4\nU 2\nL 1\nU 3\nR 1\nU 3\nL 4"),
+        Ok(5 * 9 - 2)
+    );
+
+    assert_eq!(part1(TEST_INPUT), Ok(62));
+}
+
+#[test]
+fn test_display() {
+    let map = Map::from_part1_str(TEST_INPUT).unwrap();
+    aoc_core::assert_snapshot!("map", map);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        part1("D 10\nR x"),
+        Err(AocError::malformed("R x", "a direction and a length").on_line(2))
+    );
+    assert_eq!(
+        part1("D 10\nR 10"),
+        Err(AocError::Invalid(
+            "the dig plan doesn't end where it started".to_string()
+        ))
+    );
+    assert_eq!(
+        part2("R 6 (#70c714)"),
+        Err(AocError::malformed("R 6 (#70c714)", "a color like (#70c710)").on_line(1))
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_rectangle_plans(width in 1..40i64, height in 1..40i64, clockwise: bool) {
+        let moves = if clockwise {
+            [('R', width), ('D', height), ('L', width), ('U', height)]
+        } else {
+            [('D', height), ('R', width), ('U', height), ('L', width)]
+        };
+        let code = |dir| "RDLU".find(dir).unwrap();
+        let plan: String = moves
+            .iter()
+            .map(|&(dir, len)| format!("{dir} {len} (#{len:05x}{})\n", code(dir)))
+            .collect();
+        let area = (width + 1) * (height + 1);
+        proptest::prop_assert_eq!(part1(&plan), Ok(area));
+        proptest::prop_assert_eq!(part2(&plan), Ok(area));
+    }
+
+    #[test]
+    fn test_parse_never_panics(
+        input in "([UDLRX] -?[0-9]{1,20} \\(#[0-9a-fA-F]{5,7}\\)\n){0,6}|\\PC*",
+    ) {
+        let _ = Map::from_part1_str(&input);
+        let _ = Map::from_part2_str(&input);
+    }
+}
+
+pub fn part2(input: &str) -> Result<i64, AocError> {
+    Ok(Map::from_part2_str(input)?.lagoon_size())
+}
+
+/// The trench part 1's reading of the dig plan digs, for drawing.
+pub fn part1_trench(input: &str) -> Result<impl Visualize, AocError> {
+    Map::from_part1_str(input)
+}
+
+/// A picture of the lagoon the dig plan digs, read the way `part` reads it.
+pub fn svg(input: &str, part: u8) -> Result<Svg, AocError> {
+    let map = match part {
+        1 => Map::from_part1_str(input)?,
+        _ => Map::from_part2_str(input)?,
+    };
+    Ok(map.to_svg())
+}
+
+#[test]
+fn test_svg() {
+    let svg = svg(
+        "R 2 (#000030)\nD 1 (#000021)\nL 2 (#000032)\nU 1 (#000013)\n",
+        1,
+    )
+    .unwrap()
+    .to_string();
+    assert!(svg.contains(r#"viewBox="0 0 3 2""#), "{svg}");
+    assert!(
+        svg.contains(r#"points="2.5,0.5 2.5,1.5 0.5,1.5 0.5,0.5""#),
+        "{svg}"
+    );
+}
+
+/// The dig plan, read both the way part 1 reads it and the way part 2 does.
+pub struct DigPlan {
+    part1: Map,
+    part2: Map,
+}
+
+impl Solver for DigPlan {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self {
+            part1: Map::from_part1_str(input)?,
+            part2: Map::from_part2_str(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.part1.lagoon_size().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.part2.lagoon_size().to_string())
+    }
+
+    /// Part 1's lagoon, since part 2's is too big to make out the trench in.
+    fn picture(&self) -> Option<Svg> {
+        Some(self.part1.to_svg())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(952408144115));
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(18, 1);
@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day19::{Part, Puzzle};
+
+fn bench_check_parts(c: &mut Criterion) {
+    let input = std::fs::read_to_string("input.txt").unwrap_or_else(|_| EXAMPLE.to_string());
+    let puzzle: Puzzle = input.parse().unwrap();
+    let compiled = puzzle.workflows.compile();
+
+    // A cheap LCG so the benchmark is deterministic without pulling in rand.
+    let mut seed: u64 = 0x2023_1219;
+    let mut next_rating = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        ((seed >> 33) % 4000 + 1) as i64
+    };
+    let parts: Vec<Part> = (0..100_000)
+        .map(|_| Part {
+            x: next_rating(),
+            m: next_rating(),
+            a: next_rating(),
+            s: next_rating(),
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("check_parts");
+    group.bench_function("interpreter", |b| {
+        b.iter(|| {
+            black_box(&parts)
+                .iter()
+                .filter(|part| puzzle.workflows.check_part(part))
+                .count()
+        })
+    });
+    group.bench_function("compiled", |b| {
+        b.iter(|| {
+            compiled
+                .check_parts_bulk(black_box(&parts))
+                .into_iter()
+                .filter(|&accepted| accepted)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_check_parts);
+criterion_main!(benches);
+
+const EXAMPLE: &str = aoc_core::examples::get(19, 1);
@@ -0,0 +1,1102 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use aoc_core::{Dot, Params, SolveError, Solver, StateDump};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Day19Error {
+    #[error("expected workflows and parts separated by a blank line")]
+    MissingSection,
+    #[error("malformed workflow {0:?}")]
+    MalformedWorkflow(String),
+    #[error("invalid rule {rule:?} in workflow {workflow}")]
+    InvalidRule { workflow: String, rule: String },
+    #[error("workflow {workflow} sends parts to unknown workflow {target}")]
+    UnknownWorkflow { workflow: String, target: String },
+    #[error("no workflow named \"in\"")]
+    MissingStart,
+    #[error("workflow {0} doesn't end with a rule that applies to every part")]
+    NoFallback(String),
+    #[error("workflow {0} can send parts back to itself")]
+    Cycle(String),
+    #[error("invalid part {0:?}")]
+    InvalidPart(String),
+    #[error("line {line}: {source}")]
+    OnLine {
+        line: usize,
+        source: Box<Day19Error>,
+    },
+}
+
+impl Day19Error {
+    fn on_line(self, line: usize) -> Self {
+        Day19Error::OnLine {
+            line,
+            source: Box::new(self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl FromStr for Category {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Category::*;
+        Ok(match s {
+            "x" => X,
+            "m" => M,
+            "a" => A,
+            "s" => S,
+            _ => return Err(()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum Inequality {
+    Less,
+    Greater,
+}
+
+impl FromStr for Inequality {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            ">" => Inequality::Greater,
+            "<" => Inequality::Less,
+            _ => return Err(()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct RuleCondition {
+    category: Category,
+    inequality: Inequality,
+    compare_val: i64,
+}
+
+impl FromStr for RuleCondition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let category = s.get(0..1).ok_or(())?.parse()?;
+        let inequality = s.get(1..2).ok_or(())?.parse()?;
+        let compare_val = s[2..].parse().map_err(|_| ())?;
+
+        Ok(Self {
+            category,
+            inequality,
+            compare_val,
+        })
+    }
+}
+
+/// Written the same way it's parsed, like `a<2006`.
+impl fmt::Display for RuleCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let category = match self.category {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        };
+        let inequality = match self.inequality {
+            Inequality::Less => '<',
+            Inequality::Greater => '>',
+        };
+        write!(f, "{category}{inequality}{}", self.compare_val)
+    }
+}
+
+impl RuleCondition {
+    fn is_applicable(&self, part: &Part) -> bool {
+        let op = |n: i64| match self.inequality {
+            Inequality::Greater => n > self.compare_val,
+            Inequality::Less => n < self.compare_val,
+        };
+
+        match self.category {
+            Category::X => op(part.x),
+            Category::M => op(part.m),
+            Category::A => op(part.a),
+            Category::S => op(part.s),
+        }
+    }
+
+    fn get_relevant_num_range_mut<'a>(&'a self, part_range: &'a mut PartRange) -> &'a mut Range {
+        match self.category {
+            Category::X => &mut part_range.x,
+            Category::M => &mut part_range.m,
+            Category::A => &mut part_range.a,
+            Category::S => &mut part_range.s,
+        }
+    }
+    fn get_relevant_num_range<'a>(&'a self, part_range: &'a PartRange) -> &'a Range {
+        match self.category {
+            Category::X => &part_range.x,
+            Category::M => &part_range.m,
+            Category::A => &part_range.a,
+            Category::S => &part_range.s,
+        }
+    }
+
+    fn split_range(&self, part_range: &PartRange) -> (Option<PartRange>, Option<PartRange>) {
+        let relevant_num_range = self.get_relevant_num_range(part_range);
+
+        let (matching_range, nonmatching_range) = match self.inequality {
+            Inequality::Less => (
+                Range::try_new(
+                    relevant_num_range.min,
+                    std::cmp::min(self.compare_val - 1, relevant_num_range.max),
+                ),
+                Range::try_new(
+                    std::cmp::max(self.compare_val, relevant_num_range.min),
+                    relevant_num_range.max,
+                ),
+            ),
+            Inequality::Greater => (
+                Range::try_new(
+                    std::cmp::max(self.compare_val + 1, relevant_num_range.min),
+                    relevant_num_range.max,
+                ),
+                Range::try_new(
+                    relevant_num_range.min,
+                    std::cmp::min(self.compare_val, relevant_num_range.max),
+                ),
+            ),
+        };
+
+        let matching_part_range = if let Some(matching_range) = matching_range {
+            let mut matching_part_range = part_range.clone();
+            *self.get_relevant_num_range_mut(&mut matching_part_range) = matching_range;
+            Some(matching_part_range)
+        } else {
+            None
+        };
+
+        let nonmatching_part_range = if let Some(nonmatching_range) = nonmatching_range {
+            let mut nonmatching_part_range = part_range.clone();
+            *self.get_relevant_num_range_mut(&mut nonmatching_part_range) = nonmatching_range;
+            Some(nonmatching_part_range)
+        } else {
+            None
+        };
+
+        (matching_part_range, nonmatching_part_range)
+    }
+}
+
+#[test]
+fn test_split_range() {
+    let range = Range { min: 1, max: 100 };
+    let part_range = PartRange {
+        workflow_name: "foo".to_string(),
+        x: range,
+        m: range,
+        a: range,
+        s: range,
+    };
+    assert_eq!(
+        "m<5"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            Some(PartRange {
+                m: Range { min: 1, max: 4 },
+                ..part_range.clone()
+            }),
+            Some(PartRange {
+                m: Range { min: 5, max: 100 },
+                ..part_range.clone()
+            })
+        )
+    );
+
+    assert_eq!(
+        "m<1"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            None,
+            Some(PartRange {
+                m: Range { min: 1, max: 100 },
+                ..part_range.clone()
+            })
+        )
+    );
+    assert_eq!(
+        "m<0"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            None,
+            Some(PartRange {
+                m: Range { min: 1, max: 100 },
+                ..part_range.clone()
+            })
+        )
+    );
+    assert_eq!(
+        "m>0"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            Some(PartRange {
+                m: Range { min: 1, max: 100 },
+                ..part_range.clone()
+            }),
+            None,
+        )
+    );
+    assert_eq!(
+        "m>1"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            Some(PartRange {
+                m: Range { min: 2, max: 100 },
+                ..part_range.clone()
+            }),
+            Some(PartRange {
+                m: Range { min: 1, max: 1 },
+                ..part_range.clone()
+            })
+        )
+    );
+    assert_eq!(
+        "m>99"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            Some(PartRange {
+                m: Range { min: 100, max: 100 },
+                ..part_range.clone()
+            }),
+            Some(PartRange {
+                m: Range { min: 1, max: 99 },
+                ..part_range.clone()
+            })
+        )
+    );
+    assert_eq!(
+        "m>100"
+            .parse::<RuleCondition>()
+            .unwrap()
+            .split_range(&part_range),
+        (
+            None,
+            Some(PartRange {
+                m: Range { min: 1, max: 100 },
+                ..part_range.clone()
+            })
+        )
+    );
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum Action {
+    Accept,
+    Reject,
+    NextWorkflow(String),
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "R" => Action::Reject,
+            "A" => Action::Accept,
+            s if !s.is_empty() && s.chars().all(|c| c.is_ascii_lowercase()) => {
+                Action::NextWorkflow(s.to_string())
+            }
+            _ => return Err(()),
+        })
+    }
+}
+
+impl Action {
+    /// Sends `part_range` wherever this action points: onto the queue of ranges still to be
+    /// processed, into the accepted list, or nowhere if rejected.
+    fn apply(
+        &self,
+        mut part_range: PartRange,
+        pending: &mut Vec<PartRange>,
+        accepted: &mut Vec<PartRange>,
+    ) {
+        match self {
+            Action::Accept => {
+                part_range.workflow_name = "A".to_string();
+                accepted.push(part_range);
+            }
+            Action::Reject => (),
+            Action::NextWorkflow(next_name) => {
+                part_range.workflow_name = next_name.clone();
+                pending.push(part_range);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Rule {
+    condition: Option<RuleCondition>,
+    action: Action,
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (condition, action) = if let Some((condition, action)) = s.split_once(':') {
+            (Some(condition.parse()?), action.parse()?)
+        } else {
+            (None, s.parse()?)
+        };
+
+        Ok(Self { condition, action })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Part {
+    pub x: i64,
+    pub m: i64,
+    pub a: i64,
+    pub s: i64,
+}
+
+impl FromStr for Part {
+    type Err = Day19Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let err = || Day19Error::InvalidPart(input.to_string());
+        let re = Regex::new(r"^\{x=(\d*),m=(\d*),a=(\d*),s=(\d*)\}$").unwrap();
+        let Some((_, [x, m, a, s])) = re.captures(input).map(|c| c.extract()) else {
+            return Err(err());
+        };
+        Ok(Self {
+            x: x.parse().map_err(|_| err())?,
+            m: m.parse().map_err(|_| err())?,
+            a: a.parse().map_err(|_| err())?,
+            s: s.parse().map_err(|_| err())?,
+        })
+    }
+}
+
+impl Part {
+    fn get_rating(&self) -> i64 {
+        self.x + self.m + self.a + self.s
+    }
+}
+
+/// Each workflow's rules by its name. Deserialized workflows are checked the same way as parsed
+/// ones.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(try_from = "HashMap<String, Vec<Rule>>")]
+pub struct Workflows(HashMap<String, Vec<Rule>>);
+
+impl FromStr for Workflows {
+    type Err = Day19Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut workflows: HashMap<String, Vec<Rule>> = HashMap::new();
+        let mut lines = HashMap::new();
+        for (line, workflow_str) in (1..).zip(s.lines()) {
+            let malformed =
+                || Day19Error::MalformedWorkflow(workflow_str.to_string()).on_line(line);
+            let (workflow_name, rules_str) = workflow_str.split_once('{').ok_or_else(malformed)?;
+            let rules_str = rules_str.strip_suffix('}').ok_or_else(malformed)?;
+            if workflow_name.is_empty() || rules_str.contains(['{', '}']) {
+                return Err(malformed());
+            }
+
+            let rules: Vec<Rule> = rules_str
+                .split(',')
+                .map(|rule| {
+                    rule.parse().map_err(|()| {
+                        Day19Error::InvalidRule {
+                            workflow: workflow_name.to_string(),
+                            rule: rule.to_string(),
+                        }
+                        .on_line(line)
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+
+            workflows.insert(workflow_name.to_string(), rules);
+            lines.insert(workflow_name, line);
+        }
+
+        Self::try_from(workflows).map_err(|e| {
+            let line = match &e {
+                Day19Error::NoFallback(name)
+                | Day19Error::UnknownWorkflow { workflow: name, .. }
+                | Day19Error::Cycle(name) => lines[name.as_str()],
+                _ => return e,
+            };
+            e.on_line(line)
+        })
+    }
+}
+
+impl TryFrom<HashMap<String, Vec<Rule>>> for Workflows {
+    type Error = Day19Error;
+
+    /// Checks every workflow ends with a rule that always applies, only sends parts to
+    /// workflows that exist, and can't send them around in circles, and that there's a
+    /// workflow named `in` to start from.
+    fn try_from(workflows: HashMap<String, Vec<Rule>>) -> Result<Self, Self::Error> {
+        for (name, rules) in &workflows {
+            if !matches!(
+                rules.last(),
+                Some(Rule {
+                    condition: None,
+                    ..
+                })
+            ) {
+                return Err(Day19Error::NoFallback(name.clone()));
+            }
+            for rule in rules {
+                if let Action::NextWorkflow(target) = &rule.action {
+                    if !workflows.contains_key(target) {
+                        return Err(Day19Error::UnknownWorkflow {
+                            workflow: name.clone(),
+                            target: target.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        if !workflows.contains_key("in") {
+            return Err(Day19Error::MissingStart);
+        }
+        let workflows = Self(workflows);
+        if let Some(name) = workflows.find_cycle() {
+            return Err(Day19Error::Cycle(name.to_string()));
+        }
+
+        Ok(workflows)
+    }
+}
+
+impl Workflows {
+    /// The workflows in Graphviz format, with an edge for each rule labelled by its condition
+    /// and ending at the workflow it sends parts to, or at A or R.
+    pub fn to_dot(&self) -> Dot {
+        let mut dot = Dot::digraph("workflows");
+        dot.node("A", &[("color", "green")]);
+        dot.node("R", &[("color", "red")]);
+        let mut names: Vec<&String> = self.0.keys().collect();
+        names.sort();
+        for name in names {
+            if name == "in" {
+                dot.node(name, &[("shape", "box")]);
+            }
+            for rule in &self.0[name] {
+                let target = match &rule.action {
+                    Action::Accept => "A",
+                    Action::Reject => "R",
+                    Action::NextWorkflow(target) => target,
+                };
+                match rule.condition {
+                    Some(condition) => dot.edge(name, target, &[("label", &condition.to_string())]),
+                    None => dot.edge(name, target, &[]),
+                }
+            }
+        }
+        dot
+    }
+
+    /// A workflow that parts can be sent around in circles through, if there is one.
+    fn find_cycle(&self) -> Option<&str> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Visit {
+            InProgress,
+            Done,
+        }
+        fn visit<'a>(
+            workflows: &'a Workflows,
+            name: &'a str,
+            visits: &mut HashMap<&'a str, Visit>,
+        ) -> Option<&'a str> {
+            match visits.get(name) {
+                Some(Visit::InProgress) => return Some(name),
+                Some(Visit::Done) => return None,
+                None => {}
+            }
+            visits.insert(name, Visit::InProgress);
+            for rule in &workflows.0[name] {
+                if let Action::NextWorkflow(next) = &rule.action {
+                    if let Some(cycle) = visit(workflows, next, visits) {
+                        return Some(cycle);
+                    }
+                }
+            }
+            visits.insert(name, Visit::Done);
+            None
+        }
+
+        let mut visits = HashMap::new();
+        self.0
+            .keys()
+            .find_map(|name| visit(self, name, &mut visits))
+    }
+
+    pub fn check_part(&self, part: &Part) -> bool {
+        // println!("Checking {part:?}");
+        let mut workflow_name = "in";
+        loop {
+            // println!("In workflow {workflow_name}");
+            let workflow = self
+                .0
+                .get(workflow_name)
+                .expect("No workflow with name {workflow_name}");
+
+            match &workflow
+                .iter()
+                .find(|rule| {
+                    rule.condition
+                        .as_ref()
+                        .map(|r| r.is_applicable(part))
+                        .unwrap_or(true)
+                })
+                .expect("Part doesn't match any rule in workflow")
+                .action
+            {
+                Action::Accept => return true,
+                Action::Reject => return false,
+                Action::NextWorkflow(next_name) => workflow_name = next_name,
+            }
+        }
+    }
+
+    /// Flattens the workflows into a single jump table so parts can be checked without any
+    /// string lookups.
+    pub fn compile(&self) -> CompiledWorkflows {
+        let mut workflow_starts = HashMap::new();
+        let mut num_rules = 0;
+        for (name, rules) in &self.0 {
+            workflow_starts.insert(name.as_str(), num_rules);
+            num_rules += rules.len();
+        }
+
+        let mut rules = vec![None; num_rules];
+        for (name, workflow) in &self.0 {
+            let start = workflow_starts[name.as_str()];
+            for (i, rule) in workflow.iter().enumerate() {
+                let target = match &rule.action {
+                    Action::Accept => Target::Accept,
+                    Action::Reject => Target::Reject,
+                    Action::NextWorkflow(next_name) => Target::Rule(
+                        *workflow_starts
+                            .get(next_name.as_str())
+                            .unwrap_or_else(|| panic!("No workflow with name {next_name}")),
+                    ),
+                };
+                rules[start + i] = Some(CompiledRule {
+                    condition: rule.condition,
+                    target,
+                });
+            }
+        }
+
+        CompiledWorkflows {
+            rules: rules.into_iter().map(Option::unwrap).collect(),
+            start: *workflow_starts.get("in").expect("No workflow named in"),
+        }
+    }
+
+    /// Returns the disjoint hypercubes of parts that end up accepted.
+    pub fn accepted_regions(&self) -> Vec<PartRange> {
+        self.accepted_regions_within(RATINGS)
+    }
+
+    /// Like [`Workflows::accepted_regions`], for parts with every rating in `ratings`.
+    pub fn accepted_regions_within(&self, ratings: Range) -> Vec<PartRange> {
+        let mut part_ranges = vec![PartRange {
+            workflow_name: "in".to_string(),
+            x: ratings,
+            m: ratings,
+            a: ratings,
+            s: ratings,
+        }];
+
+        let mut accepted = vec![];
+        while let Some(mut part_range) = part_ranges.pop() {
+            let workflow = self
+                .0
+                .get(&part_range.workflow_name)
+                .expect("No workflow with name {workflow_name}");
+            for rule in workflow {
+                if let Some(condition) = &rule.condition {
+                    let (matching_part_range, nonmatching_part_range) =
+                        condition.split_range(&part_range);
+
+                    if let Some(matching_part_range) = matching_part_range {
+                        rule.action
+                            .apply(matching_part_range, &mut part_ranges, &mut accepted);
+                    }
+
+                    if let Some(nonmatching_part_range) = nonmatching_part_range {
+                        part_range = nonmatching_part_range;
+                    } else {
+                        break;
+                    }
+                } else {
+                    rule.action
+                        .apply(part_range, &mut part_ranges, &mut accepted);
+                    break;
+                }
+            }
+        }
+
+        accepted
+    }
+
+    pub fn solve_part2(&self) -> i64 {
+        self.solve_part2_within(RATINGS)
+    }
+
+    /// How many distinct parts with every rating in `ratings` are accepted.
+    pub fn solve_part2_within(&self, ratings: Range) -> i64 {
+        self.accepted_regions_within(ratings)
+            .iter()
+            .map(PartRange::num_distinct_parts)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Accept,
+    Reject,
+    Rule(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CompiledRule {
+    condition: Option<RuleCondition>,
+    target: Target,
+}
+
+/// Workflows flattened into one jump table. Every workflow's rules are stored contiguously, so a
+/// rule whose condition doesn't apply falls through to the next index, and jumping to a workflow
+/// means jumping to the index of its first rule.
+#[derive(Debug)]
+pub struct CompiledWorkflows {
+    rules: Vec<CompiledRule>,
+    start: usize,
+}
+
+impl CompiledWorkflows {
+    pub fn check_part(&self, part: &Part) -> bool {
+        let mut i = self.start;
+        loop {
+            let rule = &self.rules[i];
+            if rule
+                .condition
+                .map(|c| c.is_applicable(part))
+                .unwrap_or(true)
+            {
+                match rule.target {
+                    Target::Accept => return true,
+                    Target::Reject => return false,
+                    Target::Rule(next) => i = next,
+                }
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    pub fn check_parts_bulk(&self, parts: &[Part]) -> Vec<bool> {
+        parts.iter().map(|part| self.check_part(part)).collect()
+    }
+}
+
+#[test]
+fn test_compiled_matches_interpreter() {
+    let puzzle: Puzzle = TEST_INPUT.parse().unwrap();
+    let compiled = puzzle.workflows.compile();
+
+    let parts: Vec<Part> = (0..4000)
+        .step_by(37)
+        .flat_map(|x| {
+            (0..4000).step_by(413).map(move |m| Part {
+                x,
+                m,
+                a: (x * 7 + m) % 4000,
+                s: (m * 3 + x) % 4000,
+            })
+        })
+        .collect();
+    let expected: Vec<bool> = parts
+        .iter()
+        .map(|part| puzzle.workflows.check_part(part))
+        .collect();
+    assert_eq!(compiled.check_parts_bulk(&parts), expected);
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub workflows: Workflows,
+    pub parts: Vec<Part>,
+    /// The range every rating is in, for part 2
+    pub ratings: Range,
+}
+
+impl FromStr for Puzzle {
+    type Err = Day19Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (workflows_str, parts_str) = s.split_once("\n\n").ok_or(Day19Error::MissingSection)?;
+
+        let workflows = workflows_str.parse()?;
+
+        // Parts start after the workflows and the blank line separating them.
+        let first_part_line = workflows_str.lines().count() + 2;
+        let parts = (first_part_line..)
+            .zip(parts_str.lines())
+            .map(|(line, part)| part.parse().map_err(|e: Day19Error| e.on_line(line)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Puzzle {
+            workflows,
+            parts,
+            ratings: RATINGS,
+        })
+    }
+}
+
+impl Puzzle {
+    pub fn solve_part1(&self) -> i64 {
+        let compiled = self.workflows.compile();
+        self.parts
+            .iter()
+            .zip(compiled.check_parts_bulk(&self.parts))
+            .filter(|(_, accepted)| *accepted)
+            .map(|(part, _)| part.get_rating())
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub min: i64,
+    pub max: i64,
+}
+
+/// The range every rating is in, inclusive.
+pub const RATINGS: Range = Range { min: 1, max: 4000 };
+
+impl Range {
+    fn len(&self) -> i64 {
+        assert!(self.max >= self.min);
+        self.max - self.min + 1
+    }
+
+    pub fn try_new(min: i64, max: i64) -> Option<Self> {
+        if max >= min {
+            Some(Self { min, max })
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        self.min <= n && n <= self.max
+    }
+
+    pub fn intersect(&self, other: &Range) -> Option<Range> {
+        Range::try_new(
+            std::cmp::max(self.min, other.min),
+            std::cmp::min(self.max, other.max),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartRange {
+    pub workflow_name: String,
+    pub x: Range,
+    pub m: Range,
+    pub a: Range,
+    pub s: Range,
+}
+
+impl PartRange {
+    pub fn num_distinct_parts(&self) -> i64 {
+        self.x.len() * self.m.len() * self.a.len() * self.s.len()
+    }
+
+    pub fn contains(&self, part: &Part) -> bool {
+        self.x.contains(part.x)
+            && self.m.contains(part.m)
+            && self.a.contains(part.a)
+            && self.s.contains(part.s)
+    }
+
+    /// Restricts this range to the ratings allowed by `bounds`, ignoring its workflow name.
+    pub fn intersect(&self, bounds: &PartRange) -> Option<PartRange> {
+        Some(PartRange {
+            workflow_name: self.workflow_name.clone(),
+            x: self.x.intersect(&bounds.x)?,
+            m: self.m.intersect(&bounds.m)?,
+            a: self.a.intersect(&bounds.a)?,
+            s: self.s.intersect(&bounds.s)?,
+        })
+    }
+}
+
+#[test]
+fn test_accepted_regions() {
+    let puzzle: Puzzle = TEST_INPUT.parse().unwrap();
+    let regions = puzzle.workflows.accepted_regions();
+
+    for part in &puzzle.parts {
+        assert_eq!(
+            regions.iter().filter(|r| r.contains(part)).count(),
+            usize::from(puzzle.workflows.check_part(part)),
+            "{part:?}"
+        );
+    }
+
+    let bounds = PartRange {
+        workflow_name: "bounds".to_string(),
+        x: Range { min: 1, max: 100 },
+        m: Range { min: 1, max: 100 },
+        a: Range { min: 1, max: 1 },
+        s: Range { min: 1, max: 1 },
+    };
+    let volume: i64 = regions
+        .iter()
+        .filter_map(|r| r.intersect(&bounds))
+        .map(|r| r.num_distinct_parts())
+        .sum();
+    let brute_force = (1..=100)
+        .flat_map(|x| (1..=100).map(move |m| Part { x, m, a: 1, s: 1 }))
+        .filter(|part| puzzle.workflows.check_part(part))
+        .count();
+    assert_eq!(volume, brute_force as i64);
+}
+
+pub fn part1(input: &str) -> Result<i64, Day19Error> {
+    let puzzle: Puzzle = input.parse()?;
+    Ok(puzzle.solve_part1())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), Ok(19114));
+}
+
+pub fn part2(input: &str) -> Result<i64, Day19Error> {
+    let puzzle: Puzzle = input.parse()?;
+    Ok(puzzle.workflows.solve_part2())
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input.parse().map_err(SolveError::failed)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.solve_part1().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.workflows.solve_part2_within(self.ratings).to_string())
+    }
+
+    /// `rating_min` and `rating_max` change the range of ratings part 2 counts parts in.
+    fn configure(&mut self, params: &Params) -> Result<(), SolveError> {
+        params.only(&["rating_min", "rating_max"])?;
+        let min = params.get("rating_min")?.unwrap_or(self.ratings.min);
+        let max = params.get("rating_max")?.unwrap_or(self.ratings.max);
+        if min > max {
+            return Err(SolveError::failed(format!(
+                "the ratings go from {min} to {max}, which is backwards"
+            )));
+        }
+        self.ratings = Range { min, max };
+        Ok(())
+    }
+
+    /// The ranges of ratings the workflows split the parts into and accept, which part 2 adds
+    /// up.
+    fn dump_state(&self, dump: &StateDump) -> Result<(), SolveError> {
+        let accepted = self.workflows.accepted_regions_within(self.ratings);
+        dump.write("accepted_ranges", &accepted)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_dump_state() {
+    let dir = std::env::temp_dir().join(format!("day19-dump-test-{}", std::process::id()));
+    let puzzle = Puzzle::parse(TEST_INPUT).unwrap();
+    puzzle.dump_state(&StateDump::new(&dir)).unwrap();
+    let json = std::fs::read_to_string(dir.join("accepted_ranges.json")).unwrap();
+    let accepted: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let regions = puzzle.workflows.accepted_regions();
+    assert_eq!(accepted.as_array().unwrap().len(), regions.len());
+    assert_eq!(accepted[0]["x"]["min"], regions[0].x.min);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(167409079868000));
+}
+
+#[test]
+fn test_configure() {
+    let mut puzzle = Puzzle::parse(TEST_INPUT).unwrap();
+    let mut params = Params::new();
+    params.set("rating_max", 20);
+    puzzle.configure(&params).unwrap();
+    let brute_force = (1..=20)
+        .flat_map(|x| (1..=20).map(move |m| (x, m)))
+        .flat_map(|(x, m)| (1..=20).map(move |a| (x, m, a)))
+        .flat_map(|(x, m, a)| (1..=20).map(move |s| Part { x, m, a, s }))
+        .filter(|part| puzzle.workflows.check_part(part))
+        .count();
+    assert_eq!(puzzle.part2(), Ok(brute_force.to_string()));
+
+    params.set("rating_min", 21);
+    assert!(puzzle.configure(&params).is_err());
+}
+
+#[test]
+fn test_to_dot() {
+    let workflows: Workflows = "in{s<1351:px,R}\npx{a<2006:A,m>2090:A,R}".parse().unwrap();
+    assert_eq!(
+        workflows.to_dot().to_string(),
+        r#"digraph workflows {
+    A [color=green];
+    R [color=red];
+    in [shape=box];
+    in -> px [label="s<1351"];
+    in -> R;
+    px -> A [label="a<2006"];
+    px -> A [label="m>2090"];
+    px -> R;
+}
+"#
+    );
+}
+
+#[test]
+fn test_serde() {
+    let puzzle = Puzzle::parse(TEST_INPUT).unwrap();
+    let json = serde_json::to_string(&puzzle).unwrap();
+    let reloaded: Puzzle = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.solve_part1(), 19114);
+    assert_eq!(reloaded.parts.len(), puzzle.parts.len());
+
+    let unknown = r#"{"in": [{"condition": null, "action": {"NextWorkflow": "px"}}]}"#;
+    assert_eq!(
+        serde_json::from_str::<Workflows>(unknown)
+            .unwrap_err()
+            .to_string(),
+        "workflow in sends parts to unknown workflow px"
+    );
+}
+
+#[test]
+fn test_solver() {
+    assert_eq!(Puzzle::solve(TEST_INPUT, 1), Ok("19114".to_string()));
+    assert_eq!(
+        Puzzle::solve("in{A}", 1),
+        Err(SolveError::failed(Day19Error::MissingSection))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let on_line = |line, source| Day19Error::OnLine {
+        line,
+        source: Box::new(source),
+    };
+
+    assert_eq!(
+        "in{x<10:A,R}".parse::<Puzzle>().unwrap_err(),
+        Day19Error::MissingSection
+    );
+    assert_eq!(
+        "px{a<2006:qkq,R}\nin{x<10:A,R}\n\n{x=1,m=2,a=3,s=4}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        on_line(
+            1,
+            Day19Error::UnknownWorkflow {
+                workflow: "px".to_string(),
+                target: "qkq".to_string()
+            }
+        )
+    );
+    assert_eq!(
+        "in{x<10:A,R}\npx{a<:R,A}\n\n{x=1,m=2,a=3,s=4}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        on_line(
+            2,
+            Day19Error::InvalidRule {
+                workflow: "px".to_string(),
+                rule: "a<:R".to_string()
+            }
+        )
+    );
+    assert_eq!(
+        "in{x<10:A,R\n\n{x=1,m=2,a=3,s=4}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        on_line(1, Day19Error::MalformedWorkflow("in{x<10:A,R".to_string()))
+    );
+    assert_eq!(
+        "in{x<10:A,R}\n\n{x=1,m=2,a=3,s=4}\n{x=1,m=2,a=3}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        on_line(4, Day19Error::InvalidPart("{x=1,m=2,a=3}".to_string()))
+    );
+    assert_eq!(
+        "px{x<10:A,R}\n\n{x=1,m=2,a=3,s=4}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        Day19Error::MissingStart
+    );
+    assert_eq!(
+        "in{x<10:A,m>5:R}\n\n{x=1,m=2,a=3,s=4}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        on_line(1, Day19Error::NoFallback("in".to_string()))
+    );
+    assert_eq!(
+        "in{x<10:A,px}\npx{m>5:px,R}\n\n{x=1,m=2,a=3,s=4}"
+            .parse::<Puzzle>()
+            .unwrap_err(),
+        on_line(2, Day19Error::Cycle("px".to_string()))
+    );
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(19, 1);
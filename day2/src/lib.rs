@@ -0,0 +1,319 @@
+use std::{cmp, io::BufRead, num::ParseIntError};
+
+use aoc_core::{for_each_line, SolveError, Solver};
+
+#[derive(Default, PartialEq, Eq, Debug)]
+struct Colors {
+    red: u32,
+    blue: u32,
+    green: u32,
+}
+
+impl Colors {
+    fn maxes(self, other: &Colors) -> Colors {
+        Colors {
+            red: cmp::max(self.red, other.red),
+            blue: cmp::max(self.blue, other.blue),
+            green: cmp::max(self.green, other.green),
+        }
+    }
+
+    fn can_be_played_by(&self, other: &Colors) -> bool {
+        other.red >= self.red && other.blue >= self.blue && other.green >= self.green
+    }
+
+    fn power(&self) -> u32 {
+        self.red * self.blue * self.green
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Game {
+    colors: Colors,
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AocError<'a> {
+    InvalidNumColorFormat,
+    DoesntHaveOneColon,
+    DoesntStartWithGame,
+    UnknownColor(&'a str),
+    TooManyCubes,
+    ParseIntError(ParseIntError),
+}
+
+impl From<ParseIntError> for AocError<'_> {
+    fn from(value: ParseIntError) -> Self {
+        AocError::ParseIntError(value)
+    }
+}
+
+fn parse_numcol<'a>(c: &mut Colors, numcol: &'a str) -> Result<(), AocError<'a>> {
+    let numcol = numcol.trim();
+
+    let [num, col]: [&str; 2] = numcol
+        .split(' ')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| AocError::InvalidNumColorFormat)?;
+
+    let num: u32 = num.parse()?;
+    let count = match col {
+        "red" => &mut c.red,
+        "blue" => &mut c.blue,
+        "green" => &mut c.green,
+        _ => return Err(AocError::UnknownColor(col)),
+    };
+    *count = count.checked_add(num).ok_or(AocError::TooManyCubes)?;
+    Ok(())
+}
+
+#[test]
+fn test_parse_numcol() {
+    let mut colors = Colors::default();
+    assert_eq!(parse_numcol(&mut colors, " 1 red  "), Ok(()));
+    assert_eq!(colors.red, 1);
+
+    assert_eq!(parse_numcol(&mut colors, " 5 blue  "), Ok(()));
+    assert_eq!(colors.blue, 5);
+
+    assert_eq!(
+        parse_numcol(&mut colors, "4294967295 blue"),
+        Err(AocError::TooManyCubes)
+    );
+}
+
+fn parse_roll(roll: &str) -> Result<Colors, AocError<'_>> {
+    let mut colors = Colors::default();
+    for roll in roll.split(',') {
+        parse_numcol(&mut colors, roll)?;
+    }
+
+    Ok(colors)
+}
+
+#[test]
+fn test_parse_roll() {
+    assert!(matches!(
+        parse_roll("  3 blue, 4 red  "),
+        Ok(Colors {
+            red: 4,
+            blue: 3,
+            green: 0
+        })
+    ));
+}
+
+fn parse_line(line: &str) -> Result<Game, AocError<'_>> {
+    let [gameinfo, rolls]: [&str; 2] = line
+        .trim()
+        .split(':')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| AocError::DoesntHaveOneColon)?;
+    let gametext = "Game ";
+    if !gameinfo.starts_with(gametext) {
+        return Err(AocError::DoesntStartWithGame);
+    }
+    let gamenum = &gameinfo[gametext.len()..];
+    let gamenum: u32 = gamenum.parse().map_err(AocError::ParseIntError)?;
+
+    let colors = rolls
+        .split(';')
+        .map(parse_roll)
+        .reduce(|mc1, mc2| Ok(Colors::maxes(mc1?, &mc2?)))
+        .unwrap_or(Ok(Colors::default()))?;
+
+    Ok(Game {
+        colors,
+        id: gamenum,
+    })
+}
+
+#[test]
+fn test_parse_line() {
+    assert_eq!(
+        parse_line("   Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n"),
+        Ok(Game {
+            colors: Colors {
+                red: 1,
+                green: 3,
+                blue: 4
+            },
+            id: 2
+        })
+    )
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_line_round_trip(
+        id in 0..1000u32,
+        rolls in proptest::collection::vec(
+            proptest::collection::vec((0..3usize, 0..100u32), 1..4),
+            1..5,
+        ),
+    ) {
+        const NAMES: [&str; 3] = ["red", "green", "blue"];
+        let mut expected = Colors::default();
+        let mut roll_texts = vec![];
+        for roll in &rolls {
+            let mut counts = [0; 3];
+            for &(color, num) in roll {
+                counts[color] += num;
+            }
+            expected = expected.maxes(&Colors {
+                red: counts[0],
+                green: counts[1],
+                blue: counts[2],
+            });
+            let texts: Vec<String> = roll
+                .iter()
+                .map(|&(color, num)| format!("{num} {}", NAMES[color]))
+                .collect();
+            roll_texts.push(texts.join(", "));
+        }
+        let line = format!("Game {id}: {}", roll_texts.join("; "));
+        proptest::prop_assert_eq!(
+            parse_line(&line),
+            Ok(Game {
+                colors: expected,
+                id
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_line_never_panics(
+        line in "(Game [0-9]{1,11}:)?(( ?[0-9]{1,11} (red|green|blue|pink))[,;]?){0,6}|\\PC*",
+    ) {
+        let _ = parse_line(&line);
+    }
+}
+
+fn aoc_part_1(s: &str, available: Colors) -> Result<u32, AocError<'_>> {
+    let mut id_sum = 0;
+    for line in s.lines() {
+        let game = parse_line(line)?;
+        if game.colors.can_be_played_by(&available) {
+            id_sum += game.id;
+        }
+    }
+    Ok(id_sum)
+}
+
+/// The cubes part 1 asks about.
+const BAG: Colors = Colors {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+/// Sum of the IDs of the games that could have been played with 12 red, 13 green and 14 blue
+/// cubes.
+pub fn part1(s: &str) -> Result<u32, AocError<'_>> {
+    aoc_part_1(s, BAG)
+}
+
+#[test]
+fn test_aoc_part_1() {
+    assert_eq!(
+        aoc_part_1(
+            r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green    "#,
+            Colors {
+                red: 12,
+                green: 13,
+                blue: 14
+            }
+        ),
+        Ok(8)
+    );
+}
+
+/// Sum of the power of the fewest cubes each game could have been played with.
+pub fn part2(s: &str) -> Result<u32, AocError<'_>> {
+    let mut power_sum = 0;
+    for line in s.lines() {
+        let game = parse_line(line)?;
+        power_sum += game.colors.power();
+    }
+    Ok(power_sum)
+}
+
+/// Both parts' answers, reading the record a game at a time.
+pub fn solve_streaming(reader: impl BufRead) -> Result<(u32, u32), aoc_core::AocError> {
+    let (mut id_sum, mut power_sum) = (0, 0);
+    for_each_line(reader, |line| {
+        let game = parse_line(line).map_err(|e| aoc_core::AocError::Invalid(format!("{e:?}")))?;
+        if game.colors.can_be_played_by(&BAG) {
+            id_sum += game.id;
+        }
+        power_sum += game.colors.power();
+        Ok(())
+    })?;
+    Ok((id_sum, power_sum))
+}
+
+#[test]
+fn test_solve_streaming() {
+    assert_eq!(solve_streaming(TEST_INPUT.as_bytes()), Ok((8, 2286)));
+    assert_eq!(
+        solve_streaming("Game 1: 3 blue\nGame 2: 3 pink".as_bytes())
+            .unwrap_err()
+            .to_string(),
+        "line 2: UnknownColor(\"pink\")"
+    );
+}
+
+/// The record of games played, one per line.
+pub struct GameRecord(Vec<Game>);
+
+impl Solver for GameRecord {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input
+            .lines()
+            .map(|line| parse_line(line).map_err(|e| SolveError::failed(format!("{e:?}"))))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let games = self
+            .0
+            .iter()
+            .filter(|game| game.colors.can_be_played_by(&BAG));
+        Ok(games.map(|game| game.id).sum::<u32>().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self
+            .0
+            .iter()
+            .map(|game| game.colors.power())
+            .sum::<u32>()
+            .to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(
+        part2(
+            r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green    "#
+        ),
+        Ok(2286)
+    );
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(2, 1);
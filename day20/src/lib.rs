@@ -0,0 +1,1315 @@
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{BufRead, Write},
+    ops::{Add, Sub},
+    str::FromStr,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use aoc_core::{
+    cycle, number, parse_lines, AocError, Cancel, Dot, Graph, NodeId, SolveError, Solver,
+};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use smallvec::SmallVec;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Day20Error {
+    #[error(transparent)]
+    Parse(#[from] AocError),
+    #[error("more modules than a module id can number")]
+    TooManyModules,
+    #[error("expected exactly one module to feed rx, found {0}")]
+    NoSingleRxFeeder(usize),
+    #[error("module {0} feeding rx isn't a conjunction")]
+    RxFeederNotConjunction(String),
+    #[error("{input} didn't send three high pulses to {feeder} within {presses} presses")]
+    NoCycleFound {
+        input: String,
+        feeder: String,
+        presses: u64,
+    },
+    #[error(
+        "{input} first sent high to {feeder} on presses {presses:?}, which aren't evenly spaced"
+    )]
+    IrregularCycle {
+        input: String,
+        feeder: String,
+        presses: [u64; 3],
+    },
+    #[error("the inputs to {0} never all send it high on the same press")]
+    NeverTogether(String),
+    #[error(
+        "gave up after {presses} presses, with the cycles of {found} of {inputs} inputs found"
+    )]
+    TimedOut {
+        presses: u64,
+        found: usize,
+        inputs: usize,
+    },
+}
+
+/// What one module sends another.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Pulse {
+    High,
+    Low,
+}
+
+impl Pulse {
+    /// High for low and low for high.
+    pub fn invert(self) -> Self {
+        use Pulse::*;
+        match self {
+            High => Low,
+            Low => High,
+        }
+    }
+}
+
+/// Index of a module in `Puzzle::modules`, which is also its node in `Puzzle::wiring`.
+pub type ModuleId = NodeId;
+
+/// Pulses a module sends in response to receiving one, and which module each goes to.
+pub type Sent = SmallVec<[(ModuleId, Pulse); 4]>;
+
+/// A kind of module, which reacts to each pulse it receives by sending pulses of its own.
+///
+/// Besides the flip-flops and conjunctions of the puzzle, kinds like inverters or counters can
+/// be given a name prefix with [`ModuleKinds::register`] and then used in variant puzzles
+/// parsed with [`Puzzle::parse_with`].
+pub trait Module: Any + Debug + Send {
+    /// Handles `pulse` arriving from `input`, returning the pulses to send in response.
+    fn receive_pulse(&mut self, input: ModuleId, pulse: Pulse) -> Sent;
+
+    /// The modules it sends pulses to, as it was created with.
+    fn outputs(&self) -> &[ModuleId];
+
+    /// Called once during parsing for each module that sends pulses to this one.
+    fn connect_input(&mut self, _input: ModuleId) {}
+
+    /// Puts whatever the module remembers back the way it was when it was parsed.
+    fn reset(&mut self) {}
+
+    /// Feeds whatever state affects future pulses into `hasher`.
+    fn hash_state(&self, hasher: &mut dyn Hasher);
+
+    /// Human-readable summary of the module's state, if it has any.
+    fn describe_state(&self, _wiring: &Graph<String>) -> Option<String> {
+        None
+    }
+
+    /// The module as it's saved, if it's one of the kinds that can be.
+    fn save(&self, _wiring: &Graph<String>) -> Option<SavedModule> {
+        None
+    }
+}
+
+/// A module as it's saved: what kind it is, the modules it sends pulses to and what it
+/// remembers, all by name. Only the puzzle's own kinds can be saved.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SavedModule {
+    FlipFlop {
+        state: Pulse,
+        outputs: Vec<String>,
+    },
+    Conjunction {
+        inputs: BTreeMap<String, Pulse>,
+        outputs: Vec<String>,
+    },
+    Broadcast {
+        outputs: Vec<String>,
+    },
+    Dud,
+}
+
+fn output_names(outputs: &[ModuleId], wiring: &Graph<String>) -> Vec<String> {
+    outputs.iter().map(|&o| wiring.label(o).clone()).collect()
+}
+
+/// `pulse` sent to each of `outputs`, which is what most kinds of module do.
+pub fn send_all(outputs: &[ModuleId], pulse: Pulse) -> Sent {
+    outputs.iter().map(|&o| (o, pulse)).collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct FlipFlop {
+    state: Pulse,
+    outputs: Vec<ModuleId>,
+}
+
+impl Module for FlipFlop {
+    fn receive_pulse(&mut self, _input: ModuleId, pulse: Pulse) -> Sent {
+        match pulse {
+            Pulse::High => Sent::new(),
+            Pulse::Low => {
+                self.state = self.state.invert();
+                send_all(&self.outputs, self.state)
+            }
+        }
+    }
+
+    fn outputs(&self) -> &[ModuleId] {
+        &self.outputs
+    }
+
+    fn reset(&mut self) {
+        self.state = Pulse::Low;
+    }
+
+    fn hash_state(&self, mut hasher: &mut dyn Hasher) {
+        self.state.hash(&mut hasher);
+    }
+
+    fn describe_state(&self, _wiring: &Graph<String>) -> Option<String> {
+        Some(match self.state {
+            Pulse::High => "on".to_string(),
+            Pulse::Low => "off".to_string(),
+        })
+    }
+
+    fn save(&self, wiring: &Graph<String>) -> Option<SavedModule> {
+        Some(SavedModule::FlipFlop {
+            state: self.state,
+            outputs: output_names(&self.outputs, wiring),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Conjunction {
+    inputs: BTreeMap<ModuleId, Pulse>,
+    outputs: Vec<ModuleId>,
+}
+
+impl Module for Conjunction {
+    fn receive_pulse(&mut self, input: ModuleId, pulse: Pulse) -> Sent {
+        *self
+            .inputs
+            .get_mut(&input)
+            .unwrap_or_else(|| panic!("Didn't expect input from {input}")) = pulse;
+        let output = if self.inputs.values().all(|v| *v == Pulse::High) {
+            Pulse::Low
+        } else {
+            Pulse::High
+        };
+        send_all(&self.outputs, output)
+    }
+
+    fn outputs(&self) -> &[ModuleId] {
+        &self.outputs
+    }
+
+    fn connect_input(&mut self, input: ModuleId) {
+        self.inputs.entry(input).or_insert(Pulse::Low);
+    }
+
+    fn reset(&mut self) {
+        self.inputs
+            .values_mut()
+            .for_each(|pulse| *pulse = Pulse::Low);
+    }
+
+    fn hash_state(&self, mut hasher: &mut dyn Hasher) {
+        self.inputs.hash(&mut hasher);
+    }
+
+    fn describe_state(&self, wiring: &Graph<String>) -> Option<String> {
+        Some(
+            self.inputs
+                .iter()
+                .map(|(&input, pulse)| format!("{}={pulse:?}", wiring.label(input)))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
+    fn save(&self, wiring: &Graph<String>) -> Option<SavedModule> {
+        Some(SavedModule::Conjunction {
+            inputs: self
+                .inputs
+                .iter()
+                .map(|(&input, &pulse)| (wiring.label(input).clone(), pulse))
+                .collect(),
+            outputs: output_names(&self.outputs, wiring),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Broadcast {
+    outputs: Vec<ModuleId>,
+}
+
+impl Module for Broadcast {
+    fn receive_pulse(&mut self, _input: ModuleId, pulse: Pulse) -> Sent {
+        send_all(&self.outputs, pulse)
+    }
+
+    fn outputs(&self) -> &[ModuleId] {
+        &self.outputs
+    }
+
+    fn hash_state(&self, _hasher: &mut dyn Hasher) {}
+
+    fn save(&self, wiring: &Graph<String>) -> Option<SavedModule> {
+        Some(SavedModule::Broadcast {
+            outputs: output_names(&self.outputs, wiring),
+        })
+    }
+}
+
+/// A module that only ever receives pulses, like rx.
+#[derive(Debug, PartialEq, Eq)]
+struct Dud;
+
+impl Module for Dud {
+    fn receive_pulse(&mut self, _input: ModuleId, _pulse: Pulse) -> Sent {
+        Sent::new()
+    }
+
+    fn outputs(&self) -> &[ModuleId] {
+        &[]
+    }
+
+    fn hash_state(&self, _hasher: &mut dyn Hasher) {}
+
+    fn save(&self, _wiring: &Graph<String>) -> Option<SavedModule> {
+        Some(SavedModule::Dud)
+    }
+}
+
+/// Creates a module that sends pulses to `outputs`.
+pub type ModuleConstructor = fn(outputs: Vec<ModuleId>) -> Box<dyn Module>;
+
+/// Which kind of module each name prefix (the `%` in `%a -> b`) creates. The default has the
+/// puzzle's flip-flops (`%`) and conjunctions (`&`); a name without a prefix is a module that
+/// only receives pulses, or the broadcaster.
+pub struct ModuleKinds(HashMap<char, ModuleConstructor>);
+
+impl Default for ModuleKinds {
+    fn default() -> Self {
+        let mut kinds = Self(HashMap::new());
+        kinds.register('%', |outputs| {
+            Box::new(FlipFlop {
+                state: Pulse::Low,
+                outputs,
+            })
+        });
+        kinds.register('&', |outputs| {
+            Box::new(Conjunction {
+                inputs: BTreeMap::new(),
+                outputs,
+            })
+        });
+        kinds
+    }
+}
+
+impl ModuleKinds {
+    /// Makes names starting with `prefix` create modules with `constructor`, instead of
+    /// whatever they created before.
+    pub fn register(&mut self, prefix: char, constructor: ModuleConstructor) {
+        self.0.insert(prefix, constructor);
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    /// Every module by name, with an edge to each of its outputs
+    wiring: Graph<String>,
+    modules: Vec<Box<dyn Module>>,
+    button: ModuleId,
+    broadcaster: ModuleId,
+    rx: Option<ModuleId>,
+    /// Kept around between presses so its allocation gets reused.
+    queue: VecDeque<DirectedPulse>,
+}
+
+impl FromStr for Puzzle {
+    type Err = Day20Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(input, &ModuleKinds::default())
+    }
+}
+
+impl Puzzle {
+    /// Parses a machine whose modules can be any of `kinds`.
+    pub fn parse_with(input: &str, kinds: &ModuleKinds) -> Result<Self, Day20Error> {
+        let mut wiring = Graph::new();
+        let mut intern = |name: &str| wiring.try_add_node(name).ok_or(Day20Error::TooManyModules);
+
+        let button = intern("button")?;
+        let broadcaster = intern("broadcaster")?;
+
+        let parsed = parse_lines(input, |line| {
+            let malformed = || AocError::malformed(line, "a module, ` -> ` and its outputs");
+            let (left, right) = line.split_once(" -> ").ok_or_else(malformed)?;
+            let mut chars = left.chars();
+            let (constructor, name) = match chars.next().and_then(|c| kinds.0.get(&c)) {
+                Some(constructor) => (Some(constructor), chars.as_str()),
+                None => (None, left),
+            };
+            if name.is_empty() || right.split(',').any(|o| o.trim().is_empty()) {
+                return Err(malformed());
+            }
+            Ok((constructor, name, right))
+        })?;
+        let parsed = parsed
+            .into_iter()
+            .map(|(constructor, name, right)| Ok((constructor, intern(name)?, right)))
+            .collect::<Result<Vec<_>, Day20Error>>()?;
+
+        let mut modules_by_id: HashMap<ModuleId, Box<dyn Module>> = HashMap::new();
+        for (constructor, id, right) in parsed {
+            let outputs = right
+                .split(',')
+                .map(|o| intern(o.trim()))
+                .collect::<Result<_, _>>()?;
+            let module: Box<dyn Module> = match constructor {
+                Some(constructor) => constructor(outputs),
+                None if id == broadcaster => Box::new(Broadcast { outputs }),
+                None => Box::new(Dud),
+            };
+            modules_by_id.insert(id, module);
+        }
+
+        let modules: Vec<Box<dyn Module>> = wiring
+            .nodes()
+            .map(|id| modules_by_id.remove(&id).unwrap_or_else(|| Box::new(Dud)))
+            .collect();
+        Ok(Self::connect(wiring, modules, button, broadcaster))
+    }
+
+    /// Wires up `modules`, one for each node of `wiring` so far, by adding an edge to each of
+    /// their outputs and connecting them to each of their inputs.
+    fn connect(
+        mut wiring: Graph<String>,
+        mut modules: Vec<Box<dyn Module>>,
+        button: ModuleId,
+        broadcaster: ModuleId,
+    ) -> Self {
+        for input in wiring.nodes() {
+            for output in modules[input as usize].outputs().to_vec() {
+                wiring.add_edge(input, output);
+                modules[output as usize].connect_input(input);
+            }
+        }
+
+        Self {
+            rx: wiring.id("rx"),
+            wiring,
+            modules,
+            button,
+            broadcaster,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Rebuilds a machine from its saved modules, with the same state they were saved in. Any
+    /// output that wasn't saved is a module that only receives pulses, as when parsing.
+    fn load(saved: BTreeMap<String, SavedModule>) -> Result<Self, String> {
+        let too_many = || Day20Error::TooManyModules.to_string();
+        let mut wiring = Graph::new();
+        let button = wiring.try_add_node("button").ok_or_else(too_many)?;
+        let broadcaster = wiring.try_add_node("broadcaster").ok_or_else(too_many)?;
+        for name in saved.keys() {
+            wiring.try_add_node(name.as_str()).ok_or_else(too_many)?;
+        }
+        let mut modules_by_id: HashMap<ModuleId, Box<dyn Module>> = HashMap::new();
+        let ids = |wiring: &mut Graph<String>, names: &[String]| -> Result<Vec<ModuleId>, String> {
+            names
+                .iter()
+                .map(|n| wiring.try_add_node(n.as_str()).ok_or_else(too_many))
+                .collect()
+        };
+        for (name, module) in &saved {
+            let module: Box<dyn Module> = match module {
+                SavedModule::FlipFlop { state, outputs } => Box::new(FlipFlop {
+                    state: *state,
+                    outputs: ids(&mut wiring, outputs)?,
+                }),
+                SavedModule::Conjunction { inputs, outputs } => {
+                    for input in inputs.keys() {
+                        let sends_here = |m: &SavedModule| match m {
+                            SavedModule::FlipFlop { outputs, .. }
+                            | SavedModule::Conjunction { outputs, .. }
+                            | SavedModule::Broadcast { outputs } => outputs.contains(name),
+                            SavedModule::Dud => false,
+                        };
+                        if !saved.get(input).is_some_and(sends_here) {
+                            return Err(format!("{input} doesn't send pulses to {name}"));
+                        }
+                    }
+                    Box::new(Conjunction {
+                        inputs: inputs
+                            .iter()
+                            .map(|(input, &pulse)| (wiring.id(input.as_str()).unwrap(), pulse))
+                            .collect(),
+                        outputs: ids(&mut wiring, outputs)?,
+                    })
+                }
+                SavedModule::Broadcast { outputs } => Box::new(Broadcast {
+                    outputs: ids(&mut wiring, outputs)?,
+                }),
+                SavedModule::Dud => Box::new(Dud),
+            };
+            modules_by_id.insert(wiring.id(name.as_str()).unwrap(), module);
+        }
+        let modules: Vec<Box<dyn Module>> = wiring
+            .nodes()
+            .map(|id| modules_by_id.remove(&id).unwrap_or_else(|| Box::new(Dud)))
+            .collect();
+        Ok(Self::connect(wiring, modules, button, broadcaster))
+    }
+}
+
+/// Saved as every module by name, which can be loaded again to carry on pressing the button
+/// from the same state.
+impl Serialize for Puzzle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut saved = BTreeMap::new();
+        for id in self.wiring.nodes() {
+            let name = self.name(id);
+            let module = self.modules[id as usize]
+                .save(&self.wiring)
+                .ok_or_else(|| ser::Error::custom(format!("module {name} can't be saved")))?;
+            saved.insert(name, module);
+        }
+        saved.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Puzzle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let saved = BTreeMap::deserialize(deserializer)?;
+        Self::load(saved).map_err(de::Error::custom)
+    }
+}
+
+#[test]
+fn test_puzzle_parse() {
+    let puzzle: Puzzle = TEST_STR1.parse().unwrap();
+    let id = |name| puzzle.id(name).unwrap();
+    assert_eq!(
+        puzzle.module::<Broadcast>("broadcaster"),
+        Some(&Broadcast {
+            outputs: vec![id("a"), id("b"), id("c")]
+        })
+    );
+    assert_eq!(
+        puzzle.module::<FlipFlop>("a"),
+        Some(&FlipFlop {
+            state: Pulse::Low,
+            outputs: vec![id("b")]
+        })
+    );
+    assert_eq!(
+        puzzle.module::<FlipFlop>("b"),
+        Some(&FlipFlop {
+            state: Pulse::Low,
+            outputs: vec![id("c")]
+        })
+    );
+    assert_eq!(
+        puzzle.module::<FlipFlop>("c"),
+        Some(&FlipFlop {
+            state: Pulse::Low,
+            outputs: vec![id("inv")]
+        })
+    );
+    assert_eq!(
+        puzzle.module::<Conjunction>("inv"),
+        Some(&Conjunction {
+            inputs: BTreeMap::from([(id("c"), Pulse::Low)]),
+            outputs: vec![id("a")]
+        })
+    );
+    assert_eq!(puzzle.module::<Dud>("button"), Some(&Dud));
+}
+
+#[test]
+fn test_parse_errors() {
+    let malformed = |line, s: &str| {
+        Day20Error::Parse(AocError::malformed(s, "a module, ` -> ` and its outputs").on_line(line))
+    };
+    assert_eq!(
+        "broadcaster -> a\n%a b".parse::<Puzzle>().unwrap_err(),
+        malformed(2, "%a b")
+    );
+    assert_eq!(
+        "broadcaster -> a\n% -> b".parse::<Puzzle>().unwrap_err(),
+        malformed(2, "% -> b")
+    );
+    assert_eq!(
+        "broadcaster -> a,".parse::<Puzzle>().unwrap_err(),
+        malformed(1, "broadcaster -> a,")
+    );
+}
+
+#[test]
+fn test_custom_module_kind() {
+    /// Sends the opposite of every pulse it receives.
+    #[derive(Debug)]
+    struct Inverter {
+        outputs: Vec<ModuleId>,
+    }
+
+    impl Module for Inverter {
+        fn receive_pulse(&mut self, _input: ModuleId, pulse: Pulse) -> Sent {
+            send_all(&self.outputs, pulse.invert())
+        }
+
+        fn outputs(&self) -> &[ModuleId] {
+            &self.outputs
+        }
+
+        fn hash_state(&self, _hasher: &mut dyn Hasher) {}
+    }
+
+    let mut kinds = ModuleKinds::default();
+    kinds.register('!', |outputs| Box::new(Inverter { outputs }));
+    let mut puzzle = Puzzle::parse_with("broadcaster -> x\n!x -> rx", &kinds).unwrap();
+    assert!(!puzzle.push_button(1, &mut ()).1);
+
+    let mut puzzle = Puzzle::parse_with("broadcaster -> x, y\n!x -> y\n!y -> rx", &kinds).unwrap();
+    assert!(puzzle.push_button(1, &mut ()).1);
+
+    // Inverters can't be saved, so there's no telling when the machine's state repeats
+    let mut puzzle = Puzzle::parse_with("broadcaster -> x, a\n!x -> a\n%a -> rx", &kinds).unwrap();
+    assert_eq!(
+        puzzle.pulse_counts_after(1000),
+        PulseCount {
+            high: 1500,
+            low: 3500
+        }
+    );
+}
+
+#[test]
+fn test_to_dot() {
+    let puzzle: Puzzle = TEST_STR2.parse().unwrap();
+    let dot = puzzle.to_dot().to_string();
+    assert!(dot.starts_with("digraph modules {\n    0 [label=\"button\"];\n"));
+    assert!(dot.contains(&format!(
+        "    {} [label=\"&inv\"];\n",
+        puzzle.id("inv").unwrap()
+    )));
+    assert!(dot.contains(&format!(
+        "    {} -> {};\n",
+        puzzle.id("a").unwrap(),
+        puzzle.id("con").unwrap()
+    )));
+    assert_eq!(dot.matches(" -> ").count(), 6);
+}
+
+#[test]
+fn test_serde() {
+    let mut puzzle: Puzzle = TEST_STR2.parse().unwrap();
+    puzzle.push_button(1, &mut ());
+    let json = serde_json::to_string(&puzzle).unwrap();
+    assert!(json.contains(r#""inv":{"kind":"conjunction","inputs":{"a":"High"},"#));
+    let mut reloaded: Puzzle = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.describe_state(), puzzle.describe_state());
+    assert_eq!(
+        reloaded.pulse_counts_after(10),
+        puzzle.pulse_counts_after(10)
+    );
+
+    let wrong_input =
+        r#"{"a":{"kind":"dud"},"b":{"kind":"conjunction","inputs":{"a":"Low"},"outputs":[]}}"#;
+    assert_eq!(
+        serde_json::from_str::<Puzzle>(wrong_input)
+            .unwrap_err()
+            .to_string(),
+        "a doesn't send pulses to b"
+    );
+
+    /// A module kind that doesn't say how to save it.
+    #[derive(Debug)]
+    struct Unsaved;
+    impl Module for Unsaved {
+        fn receive_pulse(&mut self, _input: ModuleId, _pulse: Pulse) -> Sent {
+            Sent::new()
+        }
+
+        fn outputs(&self) -> &[ModuleId] {
+            &[]
+        }
+
+        fn hash_state(&self, _hasher: &mut dyn Hasher) {}
+    }
+    let mut kinds = ModuleKinds::default();
+    kinds.register('?', |_| Box::new(Unsaved));
+    let puzzle = Puzzle::parse_with("broadcaster -> x\n?x -> rx", &kinds).unwrap();
+    assert_eq!(
+        serde_json::to_string(&puzzle).unwrap_err().to_string(),
+        "module x can't be saved"
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct PulseCount {
+    high: u64,
+    low: u64,
+}
+
+impl Add for PulseCount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            high: self.high + rhs.high,
+            low: self.low + rhs.low,
+        }
+    }
+}
+
+impl Sub for PulseCount {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            high: self.high - rhs.high,
+            low: self.low - rhs.low,
+        }
+    }
+}
+
+impl PulseCount {
+    fn times(self, n: u64) -> Self {
+        Self {
+            high: self.high * n,
+            low: self.low * n,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DirectedPulse {
+    from: ModuleId,
+    to: ModuleId,
+    pulse: Pulse,
+}
+
+/// Hook into the simulation that gets to see every pulse as it's delivered.
+trait PulseObserver {
+    fn on_pulse(&mut self, press: u64, pulse: &DirectedPulse);
+}
+
+impl PulseObserver for () {
+    fn on_pulse(&mut self, _press: u64, _pulse: &DirectedPulse) {}
+}
+
+impl<F: FnMut(u64, &DirectedPulse)> PulseObserver for F {
+    fn on_pulse(&mut self, press: u64, pulse: &DirectedPulse) {
+        self(press, pulse)
+    }
+}
+
+/// Records every pulse delivered to a set of named modules.
+struct Watcher {
+    names: Vec<String>,
+    watched: HashSet<ModuleId>,
+    deliveries: Vec<(u64, DirectedPulse)>,
+}
+
+impl Watcher {
+    fn new(puzzle: &Puzzle, names: impl IntoIterator<Item = String>) -> Self {
+        let mut names: Vec<String> = names.into_iter().collect();
+        names.sort();
+        Self {
+            watched: names.iter().filter_map(|name| puzzle.id(name)).collect(),
+            names,
+            deliveries: vec![],
+        }
+    }
+
+    fn report(&self, puzzle: &Puzzle) -> String {
+        let mut report = String::new();
+        for name in &self.names {
+            report += &format!("{name}:\n");
+            let Some(id) = puzzle.id(name) else {
+                report += "  no such module\n";
+                continue;
+            };
+            for (press, pulse) in self.deliveries.iter().filter(|(_, p)| p.to == id) {
+                report += &format!(
+                    "  press {press}: {:?} from {}\n",
+                    pulse.pulse,
+                    puzzle.name(pulse.from)
+                );
+            }
+        }
+        report
+    }
+}
+
+impl PulseObserver for Watcher {
+    fn on_pulse(&mut self, press: u64, pulse: &DirectedPulse) {
+        if self.watched.contains(&pulse.to) {
+            self.deliveries.push((press, *pulse));
+        }
+    }
+}
+
+#[test]
+fn test_watcher() {
+    let mut puzzle: Puzzle = TEST_STR2.parse().unwrap();
+    let mut watcher = Watcher::new(&puzzle, ["output".to_string()]);
+    for press in 1..=2 {
+        puzzle.push_button(press, &mut watcher);
+    }
+    assert_eq!(
+        watcher.report(&puzzle),
+        "output:
+  press 1: High from con
+  press 1: Low from con
+  press 2: High from con
+"
+    );
+}
+
+impl Puzzle {
+    fn id(&self, name: &str) -> Option<ModuleId> {
+        self.wiring.id(name)
+    }
+
+    fn name(&self, id: ModuleId) -> &str {
+        self.wiring.label(id)
+    }
+
+    #[cfg(test)]
+    fn module<M: Module>(&self, name: &str) -> Option<&M> {
+        let module: &dyn Any = self.modules[self.id(name)? as usize].as_ref();
+        module.downcast_ref()
+    }
+
+    /// Presses the button `presses` times, reporting every pulse delivered to the named modules.
+    pub fn watch(&mut self, names: impl IntoIterator<Item = String>, presses: u64) -> String {
+        let mut watcher = Watcher::new(self, names);
+        for press in 1..=presses {
+            self.push_button(press, &mut watcher);
+        }
+        watcher.report(self)
+    }
+
+    /// Puts every module back the way it was before the button was first pressed.
+    fn reset(&mut self) {
+        self.modules.iter_mut().for_each(|module| module.reset());
+        self.queue.clear();
+    }
+
+    /// Presses the button once, showing `observer` every pulse as it's delivered.
+    fn push_button(&mut self, press: u64, observer: &mut impl PulseObserver) -> (PulseCount, bool) {
+        let mut sent_low_to_rx = false;
+        let mut counts = PulseCount::default();
+        self.queue.push_back(DirectedPulse {
+            from: self.button,
+            to: self.broadcaster,
+            pulse: Pulse::Low,
+        });
+
+        while let Some(directed_pulse) = self.queue.pop_front() {
+            observer.on_pulse(press, &directed_pulse);
+            let DirectedPulse { from, to, pulse } = directed_pulse;
+
+            match pulse {
+                Pulse::High => counts.high += 1,
+                Pulse::Low => counts.low += 1,
+            }
+            if Some(to) == self.rx && pulse == Pulse::Low {
+                sent_low_to_rx = true;
+            }
+            let sent = self.modules[to as usize].receive_pulse(from, pulse);
+            self.queue
+                .extend(sent.into_iter().map(|(dest, pulse)| DirectedPulse {
+                    from: to,
+                    to: dest,
+                    pulse,
+                }));
+        }
+
+        (counts, sent_low_to_rx)
+    }
+
+    /// The module's name, with a `%` before it if it's a flip-flop or a `&` if it's a
+    /// conjunction, like in the input.
+    fn prefixed_name(&self, id: ModuleId) -> String {
+        let module: &dyn Any = self.modules[id as usize].as_ref();
+        let prefix = if module.is::<FlipFlop>() {
+            "%"
+        } else if module.is::<Conjunction>() {
+            "&"
+        } else {
+            ""
+        };
+        format!("{prefix}{}", self.name(id))
+    }
+
+    /// One line per stateful module, sorted by name, e.g. `%a: on` or `&inv: c=Low`.
+    fn describe_state(&self) -> String {
+        let mut lines: Vec<String> = self
+            .wiring
+            .nodes()
+            .filter_map(|id| {
+                let state = self.modules[id as usize].describe_state(&self.wiring)?;
+                Some(format!("{}: {state}", self.prefixed_name(id)))
+            })
+            .collect();
+        lines.sort_by(|a, b| {
+            a.trim_start_matches(['%', '&'])
+                .cmp(b.trim_start_matches(['%', '&']))
+        });
+        lines.join("\n")
+    }
+
+    /// The modules in Graphviz format, with an edge to each module they send pulses to.
+    pub fn to_dot(&self) -> Dot {
+        let mut dot = Dot::digraph("modules");
+        dot.add_graph(&self.wiring, |id| self.prefixed_name(id), |_, _| None);
+        dot
+    }
+
+    /// Hash of every flip-flop's state and every conjunction's memory. Machines built from the
+    /// same input in the same state hash equally, but different states can collide too, so
+    /// equal hashes don't prove two machines will react the same to the next press.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for module in &self.modules {
+            module.hash_state(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Every module as it's saved, which is `None` for those that can't be.
+    fn saved_modules(&self) -> Vec<Option<SavedModule>> {
+        self.modules
+            .iter()
+            .map(|module| module.save(&self.wiring))
+            .collect()
+    }
+
+    /// Total pulses sent over `presses` button presses. Once the machine returns to a state it
+    /// was in before, the rest of the presses are extrapolated from that cycle rather than
+    /// simulated. Machines with modules that can't be saved don't say what state they're in,
+    /// so every press is simulated.
+    fn pulse_counts_after(&mut self, presses: u64) -> PulseCount {
+        let start = self.saved_modules();
+        if start.contains(&None) {
+            return (1..=presses).fold(PulseCount::default(), |total, press| {
+                total + self.push_button(press, &mut ()).0
+            });
+        }
+
+        // totals[i] is the number of pulses sent in the first i presses
+        let mut totals = vec![PulseCount::default()];
+        let simulation = cycle::simulate_up_to(
+            start,
+            |_| {
+                let press = totals.len() as u64;
+                let counts = self.push_button(press, &mut ()).0;
+                totals.push(totals[totals.len() - 1] + counts);
+                self.saved_modules()
+            },
+            presses as usize,
+        );
+        if !simulation.repeats() {
+            return totals[presses as usize];
+        }
+
+        let (offset, period) = (simulation.offset(), simulation.period());
+        let n = presses as usize;
+        let leftover = totals[cycle::shortcut(n, (offset, period))];
+        if n < offset {
+            return leftover;
+        }
+        let per_cycle = totals[offset + period] - totals[offset];
+        leftover + per_cycle.times(((n - offset) / period) as u64)
+    }
+
+    /// Finds the first press on which rx receives a low pulse.
+    ///
+    /// This relies on rx being fed by a single conjunction whose inputs are each driven by
+    /// an independent counter that sends a high pulse every N presses, starting from some
+    /// press. Each input is watched until it's sent high three times, to check the second gap
+    /// matches the first. The conjunction only fires once all of those line up, which the
+    /// Chinese remainder theorem finds. Gives up at `deadline` or once `cancel` is cancelled,
+    /// checking every so many presses.
+    fn solve_part2(
+        &mut self,
+        deadline: Option<Instant>,
+        cancel: &Cancel,
+    ) -> Result<u64, Day20Error> {
+        const MAX_PRESSES: u64 = 1 << 16;
+        const PRESSES_PER_CHECK: u64 = 1 << 10;
+
+        let feeders: Vec<ModuleId> = self
+            .wiring
+            .nodes()
+            .filter(|&id| {
+                self.wiring
+                    .successors(id)
+                    .iter()
+                    .any(|&o| Some(o) == self.rx)
+            })
+            .collect();
+        let [feeder] = feeders[..] else {
+            return Err(Day20Error::NoSingleRxFeeder(feeders.len()));
+        };
+        let feeder_module: &dyn Any = self.modules[feeder as usize].as_ref();
+        let Some(Conjunction { inputs, .. }) = feeder_module.downcast_ref() else {
+            return Err(Day20Error::RxFeederNotConjunction(
+                self.name(feeder).to_string(),
+            ));
+        };
+
+        let mut high_presses: BTreeMap<ModuleId, Vec<u64>> =
+            inputs.keys().map(|&input| (input, vec![])).collect();
+        for press in 1..=MAX_PRESSES {
+            self.push_button(press, &mut |press, p: &DirectedPulse| {
+                if p.to == feeder && p.pulse == Pulse::High {
+                    let presses = high_presses.get_mut(&p.from).unwrap();
+                    if presses.last() != Some(&press) {
+                        presses.push(press);
+                    }
+                }
+            });
+            if high_presses.values().all(|presses| presses.len() >= 3) {
+                break;
+            }
+            let out_of_time =
+                || cancel.is_cancelled() || deadline.is_some_and(|d| Instant::now() >= d);
+            if press % PRESSES_PER_CHECK == 0 && out_of_time() {
+                return Err(Day20Error::TimedOut {
+                    presses: press,
+                    found: high_presses.values().filter(|p| p.len() >= 3).count(),
+                    inputs: high_presses.len(),
+                });
+            }
+        }
+
+        let feeder = self.name(feeder).to_string();
+        // Each input sends high on the press it first does and every period after that
+        let mut cycles = vec![];
+        for (input, presses) in high_presses {
+            let &[first, second, third, ..] = &presses[..] else {
+                return Err(Day20Error::NoCycleFound {
+                    input: self.name(input).to_string(),
+                    feeder,
+                    presses: MAX_PRESSES,
+                });
+            };
+            if third - second != second - first {
+                return Err(Day20Error::IrregularCycle {
+                    input: self.name(input).to_string(),
+                    feeder,
+                    presses: [first, second, third],
+                });
+            }
+            cycles.push((first, second - first));
+        }
+        let (first_together, period) = number::crt(
+            cycles
+                .iter()
+                .map(|&(first, period)| (first as u128, period as u128)),
+        )
+        .ok_or_else(|| Day20Error::NeverTogether(feeder.clone()))?;
+        // The press they line up on can't be before they've all started
+        let latest_start = cycles
+            .iter()
+            .map(|&(first, _)| first as u128)
+            .max()
+            .unwrap_or(1);
+        let answer =
+            first_together + latest_start.saturating_sub(first_together).div_ceil(period) * period;
+        answer
+            .try_into()
+            .map_err(|_| Day20Error::NeverTogether(feeder))
+    }
+}
+
+pub fn part1(input: &str, presses: u64) -> Result<u64, Day20Error> {
+    let mut puzzle: Puzzle = input.parse()?;
+    let counts = puzzle.pulse_counts_after(presses);
+    Ok(counts.high * counts.low)
+}
+
+#[test]
+fn test_pulse_counts_after() {
+    for input in [TEST_STR1, TEST_STR2] {
+        for presses in [0, 1, 3, 1000, 4321] {
+            let mut brute_force: Puzzle = input.parse().unwrap();
+            let expected = (1..=presses).fold(PulseCount::default(), |total, press| {
+                total + brute_force.push_button(press, &mut ()).0
+            });
+
+            let mut puzzle: Puzzle = input.parse().unwrap();
+            assert_eq!(puzzle.pulse_counts_after(presses), expected, "{presses}");
+        }
+    }
+
+    let mut puzzle: Puzzle = TEST_STR1.parse().unwrap();
+    let initial = puzzle.state_hash();
+    puzzle.push_button(1, &mut ());
+    assert_eq!(puzzle.state_hash(), initial);
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_STR1, 1000), Ok(32000000));
+    assert_eq!(part1(TEST_STR2, 1000), Ok(11687500));
+}
+
+/// The first press on which rx receives a low pulse, giving up at `deadline` if there is one.
+pub fn part2(input: &str, deadline: Option<Instant>) -> Result<u64, Day20Error> {
+    let mut puzzle: Puzzle = input.parse()?;
+    puzzle.solve_part2(deadline, &Cancel::new())
+}
+
+/// The module configuration, reset before each part since pressing the button changes the
+/// modules' state.
+pub struct ModuleConfiguration {
+    puzzle: Mutex<Puzzle>,
+    /// How long part 2 can take before giving up
+    time_limit: Option<Duration>,
+}
+
+impl ModuleConfiguration {
+    /// The machine as it was parsed, for one part to press the button on.
+    fn fresh_puzzle(&self) -> MutexGuard<'_, Puzzle> {
+        // A part that panicked partway leaves nothing that reset doesn't put right
+        let mut puzzle = self.puzzle.lock().unwrap_or_else(PoisonError::into_inner);
+        puzzle.reset();
+        puzzle
+    }
+}
+
+impl Solver for ModuleConfiguration {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self {
+            puzzle: Mutex::new(input.parse().map_err(SolveError::failed)?),
+            time_limit: None,
+        })
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let counts = self.fresh_puzzle().pulse_counts_after(1000);
+        Ok((counts.high * counts.low).to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        self.solve_cancellable(2, &Cancel::new())
+    }
+
+    fn solve_cancellable(&self, part: u8, cancel: &Cancel) -> Result<String, SolveError> {
+        if part == 1 {
+            return self.part1();
+        }
+        let deadline = self.time_limit.map(|limit| Instant::now() + limit);
+        match self.fresh_puzzle().solve_part2(deadline, cancel) {
+            Ok(answer) => Ok(answer.to_string()),
+            Err(Day20Error::TimedOut {
+                presses,
+                found,
+                inputs,
+            }) => Err(SolveError::TimedOut {
+                progress: Some(format!(
+                    "{presses} presses done and {found} of {inputs} cycles found"
+                )),
+            }),
+            Err(e) => Err(SolveError::failed(e)),
+        }
+    }
+
+    fn set_time_limit(&mut self, limit: Duration) {
+        self.time_limit = Some(limit);
+    }
+}
+
+#[test]
+fn test_part2() {
+    // &x sends high on every 2nd press and &y on every 4th, so &f first fires on press 4.
+    assert_eq!(part2(TEST_STR_RX, None), Ok(4));
+
+    let mut puzzle: Puzzle = TEST_STR_RX.parse().unwrap();
+    let brute_force = (1..).find(|&i| puzzle.push_button(i, &mut ()).1);
+    assert_eq!(brute_force, Some(4));
+
+    // &a first sends high on press 1 and then every other press, so its cycle is offset
+    assert_eq!(part2(TEST_STR_RX_OFFSET, None), Ok(1));
+    let mut puzzle: Puzzle = TEST_STR_RX_OFFSET.parse().unwrap();
+    assert_eq!((1..).find(|&i| puzzle.push_button(i, &mut ()).1), Some(1));
+    assert_eq!(
+        part2(TEST_STR_RX_NEVER_TOGETHER, None),
+        Err(Day20Error::NeverTogether("f".to_string()))
+    );
+    assert_eq!(
+        part2(TEST_STR_RX_IRREGULAR, None),
+        Err(Day20Error::IrregularCycle {
+            input: "x".to_string(),
+            feeder: "f".to_string(),
+            presses: [1, 2, 4]
+        })
+    );
+    assert_eq!(part2(TEST_STR1, None), Err(Day20Error::NoSingleRxFeeder(0)));
+}
+
+#[test]
+fn test_solver_resets_between_parts() {
+    let config = ModuleConfiguration::parse(TEST_STR2).unwrap();
+    assert_eq!(config.part1(), Ok("11687500".to_string()));
+    assert_eq!(config.part1(), Ok("11687500".to_string()));
+}
+
+#[test]
+fn test_time_limit() {
+    // &f's inputs never send high, so without a deadline this presses until MAX_PRESSES
+    let mut puzzle = ModuleConfiguration::parse(TEST_STR_RX_NEVER).unwrap();
+    puzzle.set_time_limit(Duration::ZERO);
+    assert_eq!(
+        puzzle.part2(),
+        Err(SolveError::TimedOut {
+            progress: Some("1024 presses done and 0 of 1 cycles found".to_string())
+        })
+    );
+
+    // Cancelled by whoever's waiting, with no deadline of its own
+    let puzzle = ModuleConfiguration::parse(TEST_STR_RX_NEVER).unwrap();
+    let cancel = Cancel::new();
+    cancel.cancel();
+    assert_eq!(
+        puzzle.solve_cancellable(2, &cancel),
+        Err(SolveError::TimedOut {
+            progress: Some("1024 presses done and 0 of 1 cycles found".to_string())
+        })
+    );
+}
+
+/// Presses the button whenever a line is read from `input`, printing the machine's state after
+/// each press. A number presses that many times at once and `q` quits.
+pub fn step_mode(
+    puzzle: &mut Puzzle,
+    trace: bool,
+    input: impl BufRead,
+    mut out: impl Write,
+) -> std::io::Result<()> {
+    let mut press = 0;
+    let mut total = PulseCount::default();
+    writeln!(out, "{}", puzzle.describe_state())?;
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line == "q" {
+            break;
+        }
+        let times = if line.is_empty() {
+            1
+        } else if let Ok(times) = line.parse::<u64>() {
+            times
+        } else {
+            writeln!(out, "expected a number of presses, or q to quit")?;
+            continue;
+        };
+
+        for _ in 0..times {
+            press += 1;
+            let mut pulses = vec![];
+            let (counts, _) = puzzle.push_button(press, &mut |_, p: &DirectedPulse| {
+                if trace {
+                    pulses.push(*p)
+                }
+            });
+            total = total + counts;
+            for p in pulses {
+                writeln!(
+                    out,
+                    "{} -{:?}-> {}",
+                    puzzle.name(p.from),
+                    p.pulse,
+                    puzzle.name(p.to)
+                )?;
+            }
+            writeln!(
+                out,
+                "press {press}: {} high, {} low (total {} high, {} low)",
+                counts.high, counts.low, total.high, total.low
+            )?;
+        }
+        writeln!(out, "{}", puzzle.describe_state())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_step_mode() {
+    let mut puzzle: Puzzle = TEST_STR2.parse().unwrap();
+    let mut out = vec![];
+    step_mode(&mut puzzle, false, "\n2\nq\n".as_bytes(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "%a: off
+%b: off
+&con: a=Low, b=Low
+&inv: a=Low
+press 1: 4 high, 4 low (total 4 high, 4 low)
+%a: on
+%b: on
+&con: a=High, b=High
+&inv: a=High
+press 2: 2 high, 4 low (total 6 high, 8 low)
+press 3: 3 high, 5 low (total 9 high, 13 low)
+%a: on
+%b: off
+&con: a=High, b=Low
+&inv: a=High
+"
+    );
+}
+
+#[cfg(test)]
+const TEST_STR1: &str = aoc_core::examples::get(20, 1);
+
+#[cfg(test)]
+const TEST_STR2: &str = aoc_core::examples::get(20, 2);
+
+#[cfg(test)]
+const TEST_STR_RX: &str = r"broadcaster -> a
+%a -> x, b
+&x -> f
+%b -> y
+&y -> f
+&f -> rx";
+
+#[cfg(test)]
+const TEST_STR_RX_OFFSET: &str = r"broadcaster -> a
+%a -> f
+&f -> rx";
+
+/// %a sends high on odd presses and %b on every fourth starting from the second.
+#[cfg(test)]
+const TEST_STR_RX_NEVER_TOGETHER: &str = r"broadcaster -> a
+%a -> f, b
+%b -> f
+&f -> rx";
+
+/// %a and %b count presses in binary, and &x sends high unless both are on, so on presses 1, 2,
+/// 4, 5, 6, 8 and so on.
+#[cfg(test)]
+const TEST_STR_RX_IRREGULAR: &str = r"broadcaster -> a
+%a -> b, x
+%b -> x
+&x -> f
+&f -> rx";
+
+#[cfg(test)]
+const TEST_STR_RX_NEVER: &str = r"broadcaster -> c
+&c -> a
+%a -> f
+&f -> rx";
@@ -0,0 +1,989 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    convert::Infallible,
+    fmt,
+};
+
+use aoc_core::{
+    pathfinding::bfs, BitGrid, Params, ParseGridError, Probe, ProbeArgs, SolveError, Solver,
+    StateDump, Tile, Tiled, Visualize,
+};
+pub use aoc_core::{Coord, Grid};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Day21Error {
+    #[error(transparent)]
+    Malformed(#[from] ParseGridError<Infallible>),
+    #[error("the garden has no start S")]
+    NoStart,
+    #[error("the garden has a second start at ({x},{y})")]
+    SecondStart { x: i64, y: i64 },
+}
+
+fn or_row(dst: &mut [u64], src: &[u64]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d |= s;
+    }
+}
+
+/// Shifts every bit of `src` one cell west (towards x = 0) and ORs it into `dst`. The bit
+/// shifted out of x = 0 is returned.
+fn shift_row_west(src: &[u64], dst: &mut [u64]) -> bool {
+    for i in 0..src.len() {
+        let carry = src.get(i + 1).map_or(0, |next| next << 63);
+        dst[i] |= (src[i] >> 1) | carry;
+    }
+    src[0] & 1 != 0
+}
+
+/// Shifts every bit of `src` one cell east and ORs it into `dst`. The bit shifted out of
+/// x = `width` - 1 is returned.
+fn shift_row_east(src: &[u64], dst: &mut [u64], width: usize) -> bool {
+    for i in 0..src.len() {
+        let carry = if i == 0 { 0 } else { src[i - 1] >> 63 };
+        dst[i] |= (src[i] << 1) | carry;
+    }
+    let last = width - 1;
+    let overflowed = src[last / 64] & (1 << (last % 64)) != 0;
+    // Clear the bit that was shifted past the last column
+    if !width.is_multiple_of(64) {
+        dst[width / 64] &= !(1 << (width % 64));
+    }
+    overflowed
+}
+
+/// Which copy of the garden a coordinate is in, counting from the original at (0, 0).
+pub type Subgarden = Tile;
+
+/// How many plots are an even and odd number of steps away from somewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParityCounts {
+    pub even: usize,
+    pub odd: usize,
+}
+
+/// What to draw for one cell of a picture of the garden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Rock,
+    Plot,
+    Reached,
+    Boundary,
+}
+
+impl Cell {
+    fn symbol(self) -> char {
+        match self {
+            Cell::Rock => '#',
+            Cell::Plot => '.',
+            Cell::Reached => 'O',
+            Cell::Boundary => '+',
+        }
+    }
+
+    pub fn color(self) -> [u8; 3] {
+        match self {
+            Cell::Rock => [60, 60, 60],
+            Cell::Plot => [225, 220, 190],
+            Cell::Reached => [40, 150, 60],
+            Cell::Boundary => [180, 50, 50],
+        }
+    }
+}
+
+/// A picture from `Garden::draw_positions`.
+pub struct Picture(pub Grid<Cell>);
+
+impl Visualize for Picture {
+    fn frame(&self) -> Grid<char> {
+        self.0.map(|cell| cell.symbol())
+    }
+
+    fn color(&self, ch: char) -> [u8; 3] {
+        let cell = match ch {
+            '#' => Cell::Rock,
+            '.' => Cell::Plot,
+            'O' => Cell::Reached,
+            _ => Cell::Boundary,
+        };
+        cell.color()
+    }
+}
+
+/// A property of real puzzle inputs that the closed-form part 2 relies on but which this
+/// garden lacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureIssue {
+    NotSquare,
+    StartNotCentered,
+    BlockedStartRow,
+    BlockedStartColumn,
+    BlockedBorder,
+}
+
+impl fmt::Display for StructureIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StructureIssue::NotSquare => "garden is not square",
+            StructureIssue::StartNotCentered => "start is not in the center of the garden",
+            StructureIssue::BlockedStartRow => "row through the start has rocks",
+            StructureIssue::BlockedStartColumn => "column through the start has rocks",
+            StructureIssue::BlockedBorder => "border of the garden has rocks",
+        })
+    }
+}
+
+/// Saved as its map, start and whether it's infinite, with the plots worked out again when
+/// it's loaded.
+#[derive(Serialize, Deserialize)]
+#[serde(from = "SavedGarden")]
+pub struct Garden {
+    map: Grid<char>,
+    /// Bit set for every garden plot (as opposed to rock)
+    #[serde(skip)]
+    plots: BitGrid,
+    start: Coord,
+    infinite: bool,
+}
+
+#[derive(Deserialize)]
+struct SavedGarden {
+    map: Grid<char>,
+    start: Coord,
+    infinite: bool,
+}
+
+impl From<SavedGarden> for Garden {
+    fn from(saved: SavedGarden) -> Self {
+        Self::new(saved.map, saved.start, saved.infinite)
+    }
+}
+
+impl Garden {
+    pub fn width(&self) -> i64 {
+        self.map.width() as i64
+    }
+    pub fn height(&self) -> i64 {
+        self.map.height() as i64
+    }
+    /// What's at `coord`, wrapping around into the original garden.
+    fn tile_at(&self, coord: &Coord) -> char {
+        *self.map.get_wrapped(*coord)
+    }
+
+    /// An empty bitset for each copy of the garden.
+    fn bit_tiles(&self) -> Tiled<BitGrid> {
+        Tiled::new(self.map.width(), self.map.height())
+    }
+
+    /// The bits of `subgarden` in `grids`, which start out clear.
+    fn bits_in<'a>(&self, grids: &'a mut Tiled<BitGrid>, subgarden: Subgarden) -> &'a mut BitGrid {
+        grids.get_or_insert_with(subgarden, || {
+            BitGrid::new(self.width() as usize, self.height() as usize)
+        })
+    }
+    fn plots_around(&self, coord: &Coord) -> Vec<Coord> {
+        if self.infinite {
+            coord
+                .neighbors4()
+                .filter(|c| self.tile_at(c) == '.')
+                .collect()
+        } else {
+            self.map
+                .neighbors4(*coord)
+                .filter(|&c| self.map[c] == '.')
+                .collect()
+        }
+    }
+
+    pub fn reachable_from<'a>(
+        &self,
+        prev_points: impl Iterator<Item = &'a Coord>,
+    ) -> HashSet<Coord> {
+        prev_points.flat_map(|c| self.plots_around(c)).collect()
+    }
+
+    fn parity_bfs(&self) -> ParityBfs<'_> {
+        let mut start = BitGrid::new(self.width() as usize, self.height() as usize);
+        start.set(self.start);
+        let mut frontier = self.bit_tiles();
+        frontier.insert((0, 0), start);
+        ParityBfs {
+            garden: self,
+            reached: [frontier.clone(), self.bit_tiles()],
+            frontier,
+            steps: -1,
+            by_parity: [1, 0],
+        }
+    }
+
+    /// Row `y` of `subgarden` in `grids`, or None if that subgarden is outside a finite garden.
+    fn row_in<'a>(
+        &self,
+        grids: &'a mut Tiled<BitGrid>,
+        subgarden: Subgarden,
+        y: usize,
+    ) -> Option<&'a mut [u64]> {
+        if !self.infinite && subgarden != (0, 0) {
+            return None;
+        }
+        Some(self.bits_in(grids, subgarden).row_mut(y))
+    }
+
+    /// Every plot next to a cell in `cells`.
+    fn expand(&self, cells: &Tiled<BitGrid>) -> Tiled<BitGrid> {
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        let mut next = self.bit_tiles();
+
+        for ((sx, sy), grid) in cells.iter() {
+            // Neighbors within the same subgarden, plus the rows and columns that spill over
+            // into the four adjacent ones
+            let mut inside = BitGrid::new(width, height);
+            let mut north_overflow: &[u64] = &[];
+            let mut south_overflow: &[u64] = &[];
+            let mut west_overflow = vec![];
+            let mut east_overflow = vec![];
+            for y in 0..height {
+                let row = grid.row(y);
+                if row.iter().all(|&w| w == 0) {
+                    continue;
+                }
+
+                if y == 0 {
+                    north_overflow = row;
+                } else {
+                    or_row(inside.row_mut(y - 1), row);
+                }
+                if y == height - 1 {
+                    south_overflow = row;
+                } else {
+                    or_row(inside.row_mut(y + 1), row);
+                }
+
+                let dst = inside.row_mut(y);
+                if shift_row_west(row, dst) {
+                    west_overflow.push(y);
+                }
+                if shift_row_east(row, dst, width) {
+                    east_overflow.push(y);
+                }
+            }
+
+            self.bits_in(&mut next, (sx, sy)).or(&inside);
+            if !north_overflow.is_empty() {
+                if let Some(dst) = self.row_in(&mut next, (sx, sy - 1), height - 1) {
+                    or_row(dst, north_overflow);
+                }
+            }
+            if !south_overflow.is_empty() {
+                if let Some(dst) = self.row_in(&mut next, (sx, sy + 1), 0) {
+                    or_row(dst, south_overflow);
+                }
+            }
+            for y in west_overflow {
+                if let Some(dst) = self.row_in(&mut next, (sx - 1, sy), y) {
+                    dst[(width - 1) / 64] |= 1 << ((width - 1) % 64);
+                }
+            }
+            for y in east_overflow {
+                if let Some(dst) = self.row_in(&mut next, (sx + 1, sy), y) {
+                    dst[0] |= 1;
+                }
+            }
+        }
+
+        for (_, grid) in next.iter_mut() {
+            grid.and(&self.plots);
+        }
+        next
+    }
+
+    pub fn reachable_from_start_after_steps(&self, steps: i64) -> HashSet<Coord> {
+        let mut bfs = self.parity_bfs();
+        bfs.nth(steps as usize);
+        let [even, odd] = bfs.reached;
+        let reached = &if steps % 2 == 0 { even } else { odd };
+        reached
+            .iter()
+            .flat_map(|(subgarden, grid)| {
+                grid.iter().map(move |local| reached.join(subgarden, local))
+            })
+            .collect()
+    }
+
+    pub fn num_reachable_after_steps(&self, steps: i64) -> usize {
+        self.parity_bfs().nth(steps as usize).unwrap()
+    }
+
+    pub fn start(&self) -> &Coord {
+        &self.start
+    }
+
+    /// Checks for the structure that makes the reachable area grow as a clean diamond of
+    /// repeating subgardens: a square garden with the start in the middle and open lanes
+    /// through the start and around the edge.
+    pub fn structure_issues(&self) -> Vec<StructureIssue> {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        let is_plot = |x: usize, y: usize| self.map[(x, y)] == '.';
+        let (start_x, start_y) = (self.start.x as usize, self.start.y as usize);
+        let mut issues = vec![];
+        if width != height {
+            issues.push(StructureIssue::NotSquare);
+        }
+        if width % 2 == 0 || height % 2 == 0 || start_x != width / 2 || start_y != height / 2 {
+            issues.push(StructureIssue::StartNotCentered);
+        }
+        if !(0..width).all(|x| is_plot(x, start_y)) {
+            issues.push(StructureIssue::BlockedStartRow);
+        }
+        if !(0..height).all(|y| is_plot(start_x, y)) {
+            issues.push(StructureIssue::BlockedStartColumn);
+        }
+        if !(0..width).all(|x| is_plot(x, 0) && is_plot(x, height - 1))
+            || !(0..height).all(|y| is_plot(0, y) && is_plot(width - 1, y))
+        {
+            issues.push(StructureIssue::BlockedBorder);
+        }
+        issues
+    }
+
+    /// Fewest steps from `start` to every plot in a single copy of the garden, or None for
+    /// rocks and plots that can't be reached.
+    pub fn distances_from(&self, start: &Coord) -> Grid<Option<u32>> {
+        let distances = bfs(*start, |&coord| {
+            self.map
+                .neighbors4(coord)
+                .filter(|&next| self.map[next] == '.')
+        });
+        Grid::from_fn(self.map.width(), self.map.height(), |coord| {
+            distances.get(&coord).map(|&d| d as u32)
+        })
+    }
+
+    /// Number of plots in a single copy of the garden reachable from the start in an even and
+    /// odd number of steps. A copy that the reachable area has long since covered alternates
+    /// between these two counts.
+    pub fn counts_by_parity(&self) -> ParityCounts {
+        let distances = self.distances_from(&self.start);
+        let reachable = || distances.iter().flatten();
+        ParityCounts {
+            even: reachable().filter(|&&d| d % 2 == 0).count(),
+            odd: reachable().filter(|&&d| d % 2 == 1).count(),
+        }
+    }
+
+    /// Number of plots reachable in each subgarden once the reachable area has spread `radius`
+    /// subgardens out from the start in every direction, i.e. after
+    /// `start.x + radius * width` steps. These are the pieces the infinite garden's count is
+    /// built out of: filled subgardens in the middle, plus partially filled ones along the
+    /// edges of the diamond.
+    pub fn subgarden_counts(&self, radius: i64) -> BTreeMap<Subgarden, usize> {
+        let steps = self.start.x + radius * self.width();
+        let mut bfs = self.parity_bfs();
+        bfs.nth(steps as usize);
+        let [even, odd] = bfs.reached;
+        let reached = if steps % 2 == 0 { even } else { odd };
+        reached
+            .into_iter()
+            .map(|(subgarden, grid)| (subgarden, grid.count()))
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    fn from_str(input: &str, infinite: bool) -> Result<Self, Day21Error> {
+        let mut map: Grid<char> = input.parse()?;
+        let start = map.position(|&ch| ch == 'S').ok_or(Day21Error::NoStart)?;
+        map[start] = '.';
+        if let Some(Coord { x, y }) = map.position(|&ch| ch == 'S') {
+            return Err(Day21Error::SecondStart { x, y });
+        }
+        Ok(Self::new(map, start, infinite))
+    }
+
+    fn new(map: Grid<char>, start: Coord, infinite: bool) -> Self {
+        let plots = BitGrid::from_fn(map.width(), map.height(), |c| map[c] == '.');
+        Garden {
+            map,
+            plots,
+            start,
+            infinite,
+        }
+    }
+
+    pub fn points_in_subgarden(
+        &self,
+        points: &HashSet<Coord>,
+        subgarden_x: i64,
+        subgarden_y: i64,
+    ) -> BTreeSet<Coord> {
+        let tiles = self.bit_tiles();
+        points
+            .iter()
+            .map(|&c| tiles.split(c))
+            .filter(|&(subgarden, _)| subgarden == (subgarden_x, subgarden_y))
+            .map(|(_, local)| local)
+            .collect()
+    }
+
+    pub fn finite_from_str(input: &str) -> Result<Self, Day21Error> {
+        Self::from_str(input, false)
+    }
+
+    pub fn infinite_from_str(input: &str) -> Result<Self, Day21Error> {
+        Self::from_str(input, true)
+    }
+
+    /// Enough subgardens in every direction to hold everything reachable after `steps`.
+    pub fn radius_for(&self, steps: i64) -> i64 {
+        let start = self.start;
+        [
+            (start.x - steps).div_euclid(self.width()).abs(),
+            (start.x + steps).div_euclid(self.width()),
+            (start.y - steps).div_euclid(self.height()).abs(),
+            (start.y + steps).div_euclid(self.height()),
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    /// Draws every subgarden within `radius` of the middle one, marking `coords` as reached,
+    /// with a line of `Cell::Boundary` between neighbouring subgardens.
+    pub fn draw_positions(&self, coords: &HashSet<Coord>, radius: i64) -> Grid<Cell> {
+        let (width, height) = (self.width(), self.height());
+        let tiles = 2 * radius + 1;
+        let picture_width = (tiles * (width + 1) - 1) as usize;
+        let picture_height = (tiles * (height + 1) - 1) as usize;
+        let mut picture = Grid::new(picture_width, picture_height, Cell::Boundary);
+        // Position in the picture of a coordinate, or None if it's outside the drawn subgardens
+        let to_picture = |c: &Coord| {
+            let x = c.x + radius * width;
+            let y = c.y + radius * height;
+            if x < 0 || y < 0 || x >= tiles * width || y >= tiles * height {
+                return None;
+            }
+            Some(((x + x / width) as usize, (y + y / height) as usize))
+        };
+        for y in -radius * height..(radius + 1) * height {
+            for x in -radius * width..(radius + 1) * width {
+                let c = Coord { x, y };
+                picture[to_picture(&c).unwrap()] = if self.tile_at(&c) == '.' {
+                    Cell::Plot
+                } else {
+                    Cell::Rock
+                };
+            }
+        }
+        for at in coords.iter().filter_map(to_picture) {
+            picture[at] = Cell::Reached;
+        }
+        picture
+    }
+}
+
+/// Breadth-first search outward from the start, one step per call to `next`, which returns the
+/// number of plots reachable after exactly that many steps.
+///
+/// A plot first reached after d steps can be revisited every second step from then on, so
+/// the plots reachable after exactly n steps are the ones at distance <= n with the same
+/// parity as n. That means each plot only has to be expanded once.
+///
+/// Cells are tracked as one bitset per subgarden so a whole row of the frontier expands with a
+/// few shifts.
+struct ParityBfs<'a> {
+    garden: &'a Garden,
+    /// Plots reached so far at even and odd distances
+    reached: [Tiled<BitGrid>; 2],
+    frontier: Tiled<BitGrid>,
+    steps: i64,
+    /// Number of plots found so far at even and odd distances
+    by_parity: [usize; 2],
+}
+
+impl Iterator for ParityBfs<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.steps >= 0 {
+            let parity = ((self.steps + 1) % 2) as usize;
+            let mut next_frontier = self.garden.expand(&self.frontier);
+            // Every neighbor of a cell at distance d - 1 is at distance d - 2 or d, so anything
+            // not already reached with the same parity is new.
+            for (subgarden, grid) in next_frontier.iter_mut() {
+                if let Some(reached) = self.reached[parity].get(subgarden) {
+                    grid.and_not(reached);
+                }
+            }
+            next_frontier.retain(|_, grid| !grid.is_empty());
+
+            for (subgarden, grid) in next_frontier.iter() {
+                self.by_parity[parity] += grid.count();
+                self.garden
+                    .bits_in(&mut self.reached[parity], subgarden)
+                    .or(grid);
+            }
+            self.frontier = next_frontier;
+        }
+        self.steps += 1;
+        Some(self.by_parity[(self.steps % 2) as usize])
+    }
+}
+
+#[test]
+fn test_shift_rows() {
+    let width = 70;
+    let mut src = [0; 2];
+    src[0] = 1 | (1 << 63);
+    src[1] = 1 << 5;
+
+    let mut west = [0; 2];
+    assert!(shift_row_west(&src, &mut west));
+    assert_eq!(west, [1 << 62, 1 << 4]);
+
+    let mut east = [0; 2];
+    assert!(shift_row_east(&src, &mut east, width));
+    assert_eq!(east, [1 << 1, 1]);
+}
+
+#[test]
+fn test_reachable_after_steps() {
+    let garden = Garden::finite_from_str(TEST_STR).unwrap();
+    assert_eq!(garden.reachable_from_start_after_steps(1).len(), 2);
+    assert_eq!(garden.reachable_from_start_after_steps(2).len(), 4);
+    assert_eq!(garden.reachable_from_start_after_steps(3).len(), 6);
+    assert_eq!(garden.reachable_from_start_after_steps(6).len(), 16);
+    assert_eq!(garden.num_reachable_after_steps(6), 16);
+}
+
+#[test]
+fn test_distances_from() {
+    let garden = Garden::finite_from_str(TEST_STR).unwrap();
+    let distances = garden.distances_from(garden.start());
+    assert_eq!(distances[(5, 5)], Some(0));
+    assert_eq!(distances[(5, 4)], Some(1));
+    assert_eq!(distances[(5, 1)], None, "rock");
+    assert_eq!(
+        distances
+            .iter()
+            .flatten()
+            .filter(|&&d| d <= 6 && d % 2 == 0)
+            .count(),
+        16
+    );
+
+    let counts = garden.counts_by_parity();
+    assert_eq!(
+        counts.even,
+        garden.num_reachable_after_steps(2 * garden.width())
+    );
+    assert_eq!(
+        counts.odd,
+        garden.num_reachable_after_steps(2 * garden.width() + 1)
+    );
+}
+
+#[test]
+fn test_subgarden_counts() {
+    let garden = Garden::infinite_from_str(TEST_STR).unwrap();
+    for radius in 0..4 {
+        let counts = garden.subgarden_counts(radius);
+        assert_eq!(
+            counts.values().sum::<usize>(),
+            garden.num_reachable_after_steps(garden.start().x + radius * garden.width())
+        );
+        assert!(counts.keys().all(|&(x, y)| x.abs() + y.abs() <= radius + 1));
+    }
+}
+
+#[test]
+fn test_serde() {
+    let garden = Garden::infinite_from_str(TEST_STR).unwrap();
+    let json = serde_json::to_string(&garden).unwrap();
+    assert!(!json.contains("plots"));
+    let reloaded: Garden = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.start(), garden.start());
+    assert_eq!(reloaded.plots, garden.plots);
+    assert_eq!(reloaded.num_reachable_after_steps(50), 1594);
+}
+
+pub fn part1(input: &str, steps: i64) -> Result<usize, Day21Error> {
+    Ok(Garden::finite_from_str(input)?.num_reachable_after_steps(steps))
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_STR, 6), Ok(16));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(part1("...\n.#.", 1), Err(Day21Error::NoStart));
+    assert_eq!(
+        part1("S.\n.S", 1),
+        Err(Day21Error::SecondStart { x: 1, y: 1 })
+    );
+    assert_eq!(
+        part1("S..\n..", 1).unwrap_err().to_string(),
+        "row 1 is 2 cells wide but the first row is 3"
+    );
+}
+
+#[test]
+fn test_reachable_after_steps_infinite() {
+    let garden = Garden::infinite_from_str(TEST_STR).unwrap();
+    assert_eq!(garden.reachable_from_start_after_steps(6).len(), 16);
+    assert_eq!(garden.reachable_from_start_after_steps(10).len(), 50);
+    assert_eq!(garden.num_reachable_after_steps(50), 1594);
+    assert_eq!(garden.num_reachable_after_steps(100), 6536);
+    assert_eq!(garden.num_reachable_after_steps(500), 167004);
+    assert_eq!(garden.num_reachable_after_steps(1000), 668697);
+    assert_eq!(garden.num_reachable_after_steps(5000), 16733044);
+}
+
+/// Uses the quadratic extrapolation when the garden has the structure it relies on, and
+/// otherwise warns and simulates every step.
+pub fn part2(input: &str, steps: i64) -> Result<i64, Day21Error> {
+    Ok(reachable_in_infinite_garden(
+        &Garden::infinite_from_str(input)?,
+        steps,
+    ))
+}
+
+fn reachable_in_infinite_garden(garden: &Garden, steps: i64) -> i64 {
+    let issues = garden.structure_issues();
+    if issues.is_empty() {
+        return num_reachable_quadratic(garden, steps);
+    }
+    for issue in &issues {
+        warn!("{issue}; simulating all {steps} steps instead of extrapolating");
+    }
+    garden.num_reachable_after_steps(steps) as i64
+}
+
+/// How many steps each part takes.
+pub const STEPS: [i64; 2] = [64, 26501365];
+
+/// How many plots can be reached in one subgarden, for dumping.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubgardenCount {
+    pub x: i64,
+    pub y: i64,
+    pub reachable: usize,
+}
+
+/// The [`Garden::subgarden_counts`] for one radius, for dumping.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubgardenCounts {
+    pub radius: i64,
+    pub counts: Vec<SubgardenCount>,
+}
+
+/// How many subgardens out from the start the dumped counts go, which is enough to see every
+/// kind of partly filled subgarden the extrapolation relies on.
+const DUMP_RADII: std::ops::RangeInclusive<i64> = 0..=2;
+
+/// A number of steps or subgardens given to a probe, which can't be negative.
+fn count_arg(args: &ProbeArgs, name: &str) -> Result<i64, SolveError> {
+    let n: i64 = args.get(0, name)?;
+    if n < 0 {
+        return Err(SolveError::failed(format!("{name} can't be negative")));
+    }
+    Ok(n)
+}
+
+/// The garden map, which part 1 reads as a single garden and part 2 as tiled forever.
+pub struct GardenMap {
+    finite: Garden,
+    infinite: Garden,
+    steps: [i64; 2],
+}
+
+impl Solver for GardenMap {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self {
+            finite: Garden::finite_from_str(input).map_err(SolveError::failed)?,
+            infinite: Garden::infinite_from_str(input).map_err(SolveError::failed)?,
+            steps: STEPS,
+        })
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let steps = self.steps[0];
+        Ok(self.finite.num_reachable_after_steps(steps).to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(reachable_in_infinite_garden(&self.infinite, self.steps[1]).to_string())
+    }
+
+    /// `part1_steps` and `part2_steps` change how many steps each part takes.
+    fn configure(&mut self, params: &Params) -> Result<(), SolveError> {
+        params.only(&["part1_steps", "part2_steps"])?;
+        for (steps, name) in self.steps.iter_mut().zip(["part1_steps", "part2_steps"]) {
+            *steps = params.get(name)?.unwrap_or(*steps);
+            if *steps < 0 {
+                return Err(SolveError::failed(format!("{name} can't be negative")));
+            }
+        }
+        Ok(())
+    }
+
+    fn probes() -> Vec<Probe<Self>> {
+        vec![
+            Probe {
+                name: "reachable",
+                usage: "<steps>",
+                help: "plots reachable in the infinite garden after walking every step",
+                run: |map, args| {
+                    let steps = count_arg(args, "steps")?;
+                    let garden = &map.infinite;
+                    Ok(garden.num_reachable_after_steps(steps).to_string())
+                },
+            },
+            Probe {
+                name: "quadratic",
+                usage: "<steps>",
+                help: "plots reachable in the infinite garden, extrapolated like part 2",
+                run: |map, args| {
+                    let steps = count_arg(args, "steps")?;
+                    let garden = &map.infinite;
+                    if garden.width() != garden.height() {
+                        return Err(SolveError::failed(StructureIssue::NotSquare));
+                    }
+                    Ok(num_reachable_quadratic(garden, steps).to_string())
+                },
+            },
+            Probe {
+                name: "subgardens",
+                usage: "<radius>",
+                help: "plots reached in each subgarden once the area spreads radius subgardens out",
+                run: |map, args| {
+                    let radius = count_arg(args, "radius")?;
+                    let garden = &map.infinite;
+                    let counts = garden.subgarden_counts(radius);
+                    let mut table = format!("{:>10} {:>9}\n", "subgarden", "reachable");
+                    for ((x, y), count) in counts {
+                        table += &format!("{:>10} {count:>9}\n", format!("({x},{y})"));
+                    }
+                    Ok(table)
+                },
+            },
+            Probe {
+                name: "show",
+                usage: "<steps>",
+                help: "draw the plots reachable after steps, across every subgarden they reach",
+                run: |map, args| {
+                    let steps = count_arg(args, "steps")?;
+                    let garden = &map.infinite;
+                    let reachable = garden.reachable_from_start_after_steps(steps);
+                    let picture = garden.draw_positions(&reachable, garden.radius_for(steps));
+                    Ok(Picture(picture).frame().to_string())
+                },
+            },
+            Probe {
+                name: "issues",
+                usage: "",
+                help: "what the garden lacks for part 2 to extrapolate instead of simulating",
+                run: |map, _| {
+                    let issues = map.finite.structure_issues();
+                    if issues.is_empty() {
+                        return Ok("none".to_string());
+                    }
+                    Ok(issues.iter().map(|issue| format!("{issue}\n")).collect())
+                },
+            },
+        ]
+    }
+
+    /// How many plots part 2 reaches in each subgarden of the infinite garden once it's spread
+    /// a few subgardens out.
+    fn dump_state(&self, dump: &StateDump) -> Result<(), SolveError> {
+        let garden = &self.infinite;
+        let counts: Vec<SubgardenCounts> = DUMP_RADII
+            .map(|radius| SubgardenCounts {
+                radius,
+                counts: garden
+                    .subgarden_counts(radius)
+                    .into_iter()
+                    .map(|((x, y), reachable)| SubgardenCount { x, y, reachable })
+                    .collect(),
+            })
+            .collect();
+        dump.write("subgarden_counts", &counts)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_configure() {
+    let mut garden = GardenMap::parse(TEST_STR).unwrap();
+    let mut params = Params::new();
+    params.set("part1_steps", 6);
+    params.set("part2_steps", 100);
+    garden.configure(&params).unwrap();
+    assert_eq!(garden.part1(), Ok("16".to_string()));
+    assert_eq!(garden.part2(), Ok("6536".to_string()));
+
+    params.set("part2_steps", -1);
+    assert!(garden.configure(&params).is_err());
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_STR, 1000), Ok(668697));
+}
+
+#[test]
+fn test_dump_state() {
+    let dir = std::env::temp_dir().join(format!("day21-dump-test-{}", std::process::id()));
+    let garden = GardenMap::parse(TEST_STR).unwrap();
+    garden.dump_state(&StateDump::new(&dir)).unwrap();
+    let json = std::fs::read_to_string(dir.join("subgarden_counts.json")).unwrap();
+    let dumped: Vec<SubgardenCounts> = serde_json::from_str(&json).unwrap();
+    assert_eq!(dumped.len(), 3);
+    let expected = Garden::infinite_from_str(TEST_STR)
+        .unwrap()
+        .subgarden_counts(1);
+    assert_eq!(dumped[1].counts.len(), expected.len());
+    for count in &dumped[1].counts {
+        assert_eq!(count.reachable, expected[&(count.x, count.y)]);
+    }
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_draw_positions() {
+    let garden = Garden::infinite_from_str("...\n.S#\n...").unwrap();
+    let coords = HashSet::from([Coord { x: 1, y: 1 }, Coord { x: -1, y: 0 }]);
+    let picture = garden.draw_positions(&coords, 1);
+    assert_eq!((picture.width(), picture.height()), (11, 11));
+    assert_eq!(picture[(5, 5)], Cell::Reached);
+    assert_eq!(picture[(6, 5)], Cell::Rock);
+    assert_eq!(picture[(4, 5)], Cell::Plot);
+    assert_eq!(picture[(3, 5)], Cell::Boundary);
+    assert_eq!(picture[(2, 4)], Cell::Reached);
+    assert_eq!(picture[(2, 5)], Cell::Rock);
+
+    let picture = Picture(garden.draw_positions(&coords, 0));
+    assert_eq!(picture.frame().to_string(), "...\n.O#\n...\n");
+    assert_eq!(picture.color('O'), Cell::Reached.color());
+}
+
+#[test]
+fn test_probes() {
+    let map = GardenMap::parse(TEST_STR).unwrap();
+    let probe = |name: &str, args: &[&str]| {
+        let probe = GardenMap::probes().into_iter().find(|p| p.name == name);
+        (probe.unwrap().run)(&map, &ProbeArgs::new(args))
+    };
+    assert_eq!(probe("reachable", &["100"]), Ok("6536".to_string()));
+    assert_eq!(
+        probe("reachable", &["-1"]),
+        Err(SolveError::failed("steps can't be negative"))
+    );
+    let counts = probe("subgardens", &["1"]).unwrap();
+    assert!(counts.starts_with(" subgarden reachable\n"), "{counts}");
+    assert!(counts.contains("     (0,0)        "), "{counts}");
+    let shown = probe("show", &["1"]).unwrap();
+    assert_eq!(shown.lines().nth(5), Some(".##.O.####."));
+    assert_eq!(
+        probe("issues", &[]),
+        Ok("row through the start has rocks\ncolumn through the start has rocks\n".to_string())
+    );
+}
+
+#[test]
+fn test_structure_issues() {
+    assert_eq!(
+        Garden::finite_from_str(TEST_STR)
+            .unwrap()
+            .structure_issues(),
+        [
+            StructureIssue::BlockedStartRow,
+            StructureIssue::BlockedStartColumn
+        ]
+    );
+    let open = ".....\n.#.#.\n..S..\n.#.#.\n.....";
+    assert_eq!(
+        Garden::finite_from_str(open).unwrap().structure_issues(),
+        []
+    );
+    let off_center = "......\n.#.#..\n..S...\n.#.#..\n......";
+    assert_eq!(
+        Garden::finite_from_str(off_center)
+            .unwrap()
+            .structure_issues(),
+        [StructureIssue::NotSquare, StructureIssue::StartNotCentered]
+    );
+    assert_eq!(
+        part2(open, 102),
+        Ok(Garden::infinite_from_str(open)
+            .unwrap()
+            .num_reachable_after_steps(102) as i64)
+    );
+}
+
+/// Counts the plots reachable after `steps` in the infinite garden without simulating all of
+/// them.
+///
+/// Once the reachable diamond spans several subgardens, every extra garden-width of steps adds
+/// another ring of (repeating) subgardens, so the counts at steps that are congruent modulo
+/// the width grow quadratically. Samples are simulated until their second differences settle
+/// down, then the quadratic through the last three samples is evaluated at `steps`.
+pub fn num_reachable_quadratic(garden: &Garden, steps: i64) -> i64 {
+    assert_eq!(
+        garden.width(),
+        garden.height(),
+        "Extrapolation needs a square garden"
+    );
+    if steps == 0 {
+        return 1;
+    }
+    let period = garden.width();
+    let first_sample = steps % period;
+
+    let mut samples: Vec<i64> = vec![];
+    for (step, num_reachable) in (0..=steps).zip(garden.parity_bfs()) {
+        if step >= first_sample && (step - first_sample) % period == 0 {
+            samples.push(num_reachable as i64);
+        }
+        if samples.len() >= 4 {
+            let second_diff = |i: usize| samples[i + 2] - 2 * samples[i + 1] + samples[i];
+            let n = samples.len();
+            if second_diff(n - 4) == second_diff(n - 3) {
+                break;
+            }
+        }
+    }
+    let last_sample_steps = first_sample + period * (samples.len() as i64 - 1);
+    if last_sample_steps == steps {
+        return *samples.last().unwrap();
+    }
+
+    // Newton's forward differences from the third-to-last sample
+    let n = samples.len();
+    let f0 = samples[n - 3];
+    let d1 = samples[n - 2] - f0;
+    let d2 = samples[n - 1] - 2 * samples[n - 2] + f0;
+    let t = (steps - last_sample_steps) / period + 2;
+    f0 + t * d1 + t * (t - 1) / 2 * d2
+}
+
+#[test]
+fn test_num_reachable_quadratic() {
+    let garden = Garden::infinite_from_str(TEST_STR).unwrap();
+    assert_eq!(num_reachable_quadratic(&garden, 0), 1);
+    assert_eq!(num_reachable_quadratic(&garden, 6), 16);
+    assert_eq!(num_reachable_quadratic(&garden, 50), 1594);
+    assert_eq!(num_reachable_quadratic(&garden, 100), 6536);
+    assert_eq!(num_reachable_quadratic(&garden, 500), 167004);
+    assert_eq!(num_reachable_quadratic(&garden, 1000), 668697);
+    assert_eq!(num_reachable_quadratic(&garden, 5000), 16733044);
+}
+
+#[cfg(test)]
+const TEST_STR: &str = aoc_core::examples::get(21, 1);
@@ -1,415 +1,220 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Coord {
-    x: i64,
-    y: i64,
-}
-
-impl Coord {
-    fn around(&self) -> [Coord; 4] {
-        [
-            Coord {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Coord {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Coord {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Coord {
-                x: self.x + 1,
-                y: self.y,
-            },
-        ]
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::Path,
+    time::Duration,
+};
+
+use aoc_core::{CommonArgs, GifRecorder, Recorder, Svg};
+use clap::{Parser, Subcommand};
+use day21::{num_reachable_quadratic, part1, part2, Coord, Garden, Picture, STEPS};
+
+/// Plots reachable in one garden width of steps, entering a copy of the garden from the start,
+/// each corner and the middle of each edge.
+fn print_entry_point_table(garden: &Garden) {
+    let (start, width, height) = (garden.start(), garden.width(), garden.height());
+    let entry_points = [
+        ("middle", start.x, start.y),
+        ("top left", 0, 0),
+        ("top right", width - 1, 0),
+        ("bottom left", 0, height - 1),
+        ("bottom right", width - 1, height - 1),
+        ("top middle", start.x, 0),
+        ("bottom middle", start.x, height - 1),
+        ("middle left", 0, start.y),
+        ("middle right", width - 1, start.y),
+    ];
+
+    let steps = width as u32;
+    println!("Plots reachable in {steps} steps from each entry point");
+    println!("{:<15} {:>9}", "entry point", "reachable");
+    for (name, x, y) in entry_points {
+        let reachable = garden
+            .distances_from(&Coord { x, y })
+            .iter()
+            .flatten()
+            .filter(|&&d| d <= steps && d % 2 == steps % 2)
+            .count();
+        println!("{name:<15} {reachable:>9}");
     }
 }
 
-struct Garden {
-    map: Vec<Vec<u8>>,
-    start: Coord,
-    infinite: bool,
-}
-
-impl Garden {
-    fn width(&self) -> i64 {
-        self.map[0].len() as i64
-    }
-    fn height(&self) -> i64 {
-        self.map.len() as i64
-    }
-    fn is_in_bounds(&self, coord: &Coord) -> bool {
-        coord.x >= 0 && coord.y >= 0 && coord.x < self.width() && coord.y < self.height()
-    }
-    fn plots_around(&self, coord: &Coord) -> Vec<Coord> {
-        if self.infinite {
-            coord
-                .around()
-                .into_iter()
-                .filter(|c| {
-                    self.map[c.y.rem_euclid(self.height()) as usize]
-                        [c.x.rem_euclid(self.width()) as usize]
-                        == b'.'
-                })
-                .collect()
-        } else {
-            coord
-                .around()
-                .into_iter()
-                .filter(|c| self.is_in_bounds(c))
-                .filter(|c| self.map[c.y as usize][c.x as usize] == b'.')
-                .collect()
-        }
-    }
-
-    fn reachable_from<'a>(&self, prev_points: impl Iterator<Item = &'a Coord>) -> HashSet<Coord> {
-        prev_points.flat_map(|c| self.plots_around(c)).collect()
+/// For each subgarden within `box_size` of the middle, which distinct sets of reachable plots
+/// it goes through over `iterations` steps from `start_iteration` on. A subgarden the reachable
+/// area has fully covered alternates between two sets.
+fn print_subgarden_cycle_table(
+    garden: &Garden,
+    box_size: i64,
+    start_iteration: i64,
+    iterations: i64,
+) {
+    let mut coords_after = vec![garden.reachable_from_start_after_steps(start_iteration)];
+    for _ in 1..iterations {
+        let next = garden.reachable_from(coords_after.last().unwrap().iter());
+        coords_after.push(next);
     }
 
-    fn reachable_from_start_after_steps(&self, steps: i64) -> HashSet<Coord> {
-        let mut coords = HashSet::from([self.start.clone()]);
-        for i in 0..steps {
-            coords = self.reachable_from(coords.iter());
-            // println!("Iteration {i}");
-            // self.display_positions(&coords);
-        }
-        coords
-    }
-
-    fn from_str(input: &str, infinite: bool) -> Self {
-        let mut map: Vec<Vec<u8>> = input.lines().map(|l| l.into()).collect();
-        let mut start = None;
-        for (y, line) in map.iter_mut().enumerate() {
-            for (x, ch) in line.iter_mut().enumerate() {
-                if *ch == b'S' {
-                    *ch = b'.';
-                    assert!(start.is_none());
-                    start = Some(Coord {
-                        x: x as i64,
-                        y: y as i64,
-                    });
-                }
+    println!(
+        "Subgarden states over steps {start_iteration}..{}",
+        start_iteration + iterations
+    );
+    println!(
+        "{:>10} {:>6}  (plots, first steps) per state",
+        "subgarden", "states"
+    );
+    for x_off in -box_size..=box_size {
+        for y_off in -box_size..=box_size {
+            let mut states: HashMap<BTreeSet<Coord>, Vec<i64>> = HashMap::new();
+            for (i, coords) in coords_after.iter().enumerate() {
+                states
+                    .entry(garden.points_in_subgarden(coords, x_off, y_off))
+                    .or_default()
+                    .push(start_iteration + i as i64);
             }
-        }
-        Garden {
-            map,
-            start: start.unwrap(),
-            infinite,
-        }
-    }
-
-    fn points_in_subgarden(
-        &self,
-        points: &HashSet<Coord>,
-        subgarden_x: i64,
-        subgarden_y: i64,
-    ) -> BTreeSet<Coord> {
-        let min_x = subgarden_x * self.width();
-        let max_x = min_x + self.width();
-        let min_y = subgarden_y * self.height();
-        let max_y = min_y + self.height();
-        points
-            .iter()
-            // .filter(|p| p.x >= min_x && p.x < max_x && p.y >= min_y && p.y < max_y)
-            .map(|c| Coord {
-                x: c.x - min_x,
-                y: c.y - min_y,
-            })
-            .filter(|c| self.is_in_bounds(c))
-            .collect()
-    }
-
-    fn finite_from_str(input: &str) -> Self {
-        Self::from_str(input, false)
-    }
-
-    fn infinite_from_str(input: &str) -> Self {
-        Self::from_str(input, true)
-    }
-
-    fn display_positions(&self, coords: &HashSet<Coord>) {
-        let mut map = self.map.clone();
-
-        for c in self.points_in_subgarden(coords, 0, 0) {
-            map[c.y as usize][c.x as usize] = b'O';
-        }
-
-        // for c in coords.iter() {
-        //     let c = &Coord {
-        //         x: c.x - 3 * self.width(),
-        //         y: c.y - self.width(),
-        //     };
-        //     if self.is_in_bounds(c) {
-        //         map[c.y as usize][c.x as usize] = b'O';
-        //     }
-        // }
-        for line in map {
-            println!("{}", std::str::from_utf8(&line).unwrap());
+            let mut lens: Vec<(usize, &[i64])> = states
+                .iter()
+                .map(|(set, iters)| (set.len(), &iters[..2.min(iters.len())]))
+                .collect();
+            lens.sort_by_key(|(_len, iters)| *iters);
+            println!(
+                "{:>10} {:>6}  {lens:?}",
+                format!("({x_off},{y_off})"),
+                states.len()
+            );
         }
     }
 }
 
-#[test]
-fn test_reachable_after_steps() {
-    let garden = Garden::finite_from_str(TEST_STR);
-    assert_eq!(garden.reachable_from_start_after_steps(1).len(), 2);
-    assert_eq!(garden.reachable_from_start_after_steps(2).len(), 4);
-    assert_eq!(garden.reachable_from_start_after_steps(3).len(), 6);
-    assert_eq!(garden.reachable_from_start_after_steps(6).len(), 16);
-}
-
-fn part1(input: &str) -> usize {
-    Garden::finite_from_str(input)
-        .reachable_from_start_after_steps(64)
-        .len()
-}
-
-#[test]
-fn test_reachable_after_steps_infinite() {
-    let garden = Garden::infinite_from_str(TEST_STR);
-    assert_eq!(garden.reachable_from_start_after_steps(6).len(), 16);
-    assert_eq!(garden.reachable_from_start_after_steps(10).len(), 50);
-    assert_eq!(garden.reachable_from_start_after_steps(50).len(), 1594);
-    assert_eq!(garden.reachable_from_start_after_steps(100).len(), 6536);
-    assert_eq!(garden.reachable_from_start_after_steps(500).len(), 167004);
-    assert_eq!(garden.reachable_from_start_after_steps(1000).len(), 668697);
-    assert_eq!(
-        garden.reachable_from_start_after_steps(5000).len(),
-        16733044
+/// Compares brute-force counts with the quadratic extrapolation at a few whole numbers of
+/// garden widths past the start.
+fn print_quadratic_table(garden: &Garden) {
+    println!("Brute force against quadratic extrapolation");
+    println!(
+        "{:>6} {:>8} {:>12} {:>12}",
+        "radius", "steps", "brute force", "quadratic"
     );
+    for radius in 1..=5 {
+        let steps = garden.start().x + garden.width() * radius;
+        println!(
+            "{radius:>6} {steps:>8} {:>12} {:>12}",
+            garden.num_reachable_after_steps(steps),
+            num_reachable_quadratic(garden, steps)
+        );
+    }
 }
 
-fn part2(input: &str) -> usize {
-    Garden::infinite_from_str(input)
-        .reachable_from_start_after_steps(26501365)
-        .len()
+/// Draws the plots reachable after `steps` to an SVG file, in the animation's colors.
+fn draw_svg(garden: &Garden, steps: i64, path: &Path) -> io::Result<()> {
+    let reachable = garden.reachable_from_start_after_steps(steps);
+    let picture = garden.draw_positions(&reachable, garden.radius_for(steps));
+    let mut svg = Svg::new();
+    svg.grid(&picture, |cell| {
+        let [r, g, b] = cell.color();
+        Some(format!("#{r:02x}{g:02x}{b:02x}"))
+    });
+    svg.save(path)
 }
 
-fn play_with(input: &str) {
-    let garden = Garden::infinite_from_str(input);
-
-    for (start_point, name) in [
-        (garden.start.clone(), "middle"),
-        (Coord { x: 0, y: 0 }, "top left"),
-        (
-            Coord {
-                x: garden.width() - 1,
-                y: 0,
-            },
-            "top right",
-        ),
-        (
-            Coord {
-                x: 0,
-                y: garden.height() - 1,
-            },
-            "bottm left",
-        ),
-        (
-            Coord {
-                x: garden.width() - 1,
-                y: garden.height() - 1,
-            },
-            "bottom right",
-        ),
-        (
-            Coord {
-                x: garden.start.x,
-                y: 0,
-            },
-            "top middle",
-        ),
-        (
-            Coord {
-                x: garden.start.x,
-                y: garden.height() - 1,
-            },
-            "bottom middle",
-        ),
-        (
-            Coord {
-                x: 0,
-                y: garden.start.y,
-            },
-            "middle left",
-        ),
-        (
-            Coord {
-                x: garden.width() - 1,
-                y: garden.start.y,
-            },
-            "middle right",
-        ),
-    ] {
-        let mut coords = HashSet::from([start_point]);
-        for i in 0..131 {
+/// Writes a GIF with one frame per step showing the reachable plots spreading out across
+/// subgardens.
+fn animate(garden: &Garden, steps: i64, path: &Path) -> io::Result<()> {
+    let radius = garden.radius_for(steps);
+    let mut gif = GifRecorder::create(path, 1, Duration::from_millis(100))?;
+    let mut coords = HashSet::from([*garden.start()]);
+    for step in 0..=steps {
+        if step > 0 {
             coords = garden.reachable_from(coords.iter());
         }
-        println!("Starting from {name:15} gives {}", coords.len());
+        let picture = Picture(garden.draw_positions(&coords, radius));
+        gif.record(&picture, &format!("step {step}"))?;
     }
+    gif.finish()?;
+    Ok(())
+}
 
-    let start_iteration = 1400;
-    let mut coords_after = HashMap::from([(
-        start_iteration,
-        garden.reachable_from_start_after_steps(start_iteration),
-    )]);
-
-    // let samples = [
-    //     (0, 0, "start"),
-    //     (0, 1, "below"),
-    //     (0, 3, "far below"),
-    //     (0, -1, "above"),
-    //     (0, -3, "far above"),
-    //     (1, 0, "right"),
-    //     (3, 0, "far right"),
-    //     (-1, 0, "left"),
-    //     (-3, 0, "far left"),
-    //     (2, 3, "bottom right"),
-    //     (3, -4, "top right"),
-    //     (-2, 1, "top left"),
-    //     (-4, -2, "bottom left"),
-    // ];
-
-    let box_size = 5;
-    let samples = (-box_size..=box_size).flat_map(|x| (-box_size..=box_size).map(move |y| (x, y)));
-
-    for (x_off, y_off) in samples {
-        // println!("Checking {name}");
-        let mut cache: HashMap<BTreeSet<Coord>, Vec<i64>> = HashMap::new();
-
-        for i in 0..100 {
-            let iteration = start_iteration + i;
-
-            let next = if let Some(next) = coords_after.get(&iteration) {
-                next
-            } else {
-                let next =
-                    garden.reachable_from(coords_after.get(&(iteration - 1)).unwrap().iter());
-                coords_after.entry(iteration).or_insert(next)
-            };
-
-            let next_in_bounds = garden.points_in_subgarden(&next, x_off, y_off);
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
 
-            // if next_in_bounds.is_empty() {
-            //     println!("Don't have enough data yet for ({x_off:3},{y_off:3})");
-            //     break;
-            // }
-            cache
-                .entry(next_in_bounds)
-                .and_modify(|iters| iters.push(iteration))
-                .or_insert(vec![iteration]);
+    /// Number of steps to take in both parts, instead of 64 and 26501365
+    #[arg(long)]
+    steps: Option<i64>,
 
-            //let next = coords_after.entry(start_iteration + i).or_insert_with
-            // cache.entry();
-        }
-        let mut lens: Vec<(usize, &[i64])> = cache
-            .iter()
-            .map(|(set, iters)| (set.len(), &iters[0..(2.min(iters.len()))]))
-            .collect();
-        lens.sort_by_key(|(_set_len, iters)| *iters);
-        println!(
-            "Cache length {} for ({x_off:3},{y_off:3}). Lens are {lens:?}",
-            cache.len()
-        );
+    /// Write a GIF of the reachable plots spreading out over the part 1 steps to this file
+    #[arg(long, value_name = "FILE")]
+    animate: Option<std::path::PathBuf>,
 
-        // let n_in_bounds = garden.points_in_subgarden(&coords_after_n, x_off, y_off);
-        // let np1_in_bounds = garden.points_in_subgarden(&coords_after_np1, x_off, y_off);
-        // let np2_in_bounds = garden.points_in_subgarden(&coords_after_np2, x_off, y_off);
-        // let np3_in_bounds = garden.points_in_subgarden(&coords_after_np3, x_off, y_off);
-        // let np4_in_bounds = garden.points_in_subgarden(&coords_after_np4, x_off, y_off);
-        // let np5_in_bounds = garden.points_in_subgarden(&coords_after_np5, x_off, y_off);
+    /// Draw the plots reachable after the part 1 steps to an SVG file
+    #[arg(long, value_name = "FILE")]
+    svg: Option<std::path::PathBuf>,
 
-        // println!(
-        //     "Checking {name}. Lens: {} {} {} {}",
-        //     n_in_bounds.len(),
-        //     np2_in_bounds.len(),
-        //     np2_in_bounds.len(),
-        //     np3_in_bounds.len()
-        // );
-        // assert_eq!(n_in_bounds, np2_in_bounds);
-        // assert_eq!(np1_in_bounds, np3_in_bounds);
-    }
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-fn num_reachable_after_steps_bruteforce(input: &str, steps: i64) -> u64 {
-    Garden::infinite_from_str(input)
-        .reachable_from_start_after_steps(steps)
-        .len() as u64
+#[derive(Subcommand)]
+enum Command {
+    /// Print tables about how the reachable area grows through subgardens, and check the
+    /// quadratic extrapolation against brute force
+    Analyze {
+        /// How many subgardens out from the middle to look at in each direction
+        #[arg(long, default_value_t = 5)]
+        box_size: i64,
+
+        /// Step to start looking for repeating subgarden states at
+        #[arg(long, default_value_t = 1400)]
+        start_iteration: i64,
+
+        /// Number of steps to look for repeating subgarden states over
+        #[arg(long, default_value_t = 100)]
+        iterations: i64,
+    },
 }
 
-fn num_reachable_after_maps_mathy(diamond_size: u64) -> u64 {
-    // let diamond_size = 202300u64;
-    // let diamond_size = 3u64;
-    let inner_diamond_size = diamond_size - 1;
-    let num_squares_in_inner_diamond = (inner_diamond_size + 1) * (inner_diamond_size + 1)
-        + inner_diamond_size * inner_diamond_size;
-    let mut num_even = 1;
-    let mut num_odd = 0;
-    let mut ring = 1;
-    while num_even + num_odd < num_squares_in_inner_diamond {
-        if ring % 2 == 0 {
-            num_even += ring * 4;
-        } else {
-            num_odd += ring * 4;
-        }
-        ring += 1;
-    }
-    assert!(num_even + num_odd == num_squares_in_inner_diamond);
-    // dbg!(num_even);
-    // dbg!(num_odd);
-
-    let inner_even_val = 7265;
-    let inner_odd_val = 7325;
-
-    let total_inner = num_even * inner_even_val + num_odd * inner_odd_val;
-
-    let outer_corner_val = 14853 * 2 + 14852 * 2;
-    let side_val = inner_diamond_size * (14790 + 14795 + 14793 + 14786);
-    // println!("part 2: {}", outer_corner_val + side_val + total_inner);
-    outer_corner_val + side_val + total_inner
+fn exit_with_error(e: impl Display) -> ! {
+    eprintln!("error: {e}");
+    std::process::exit(1);
 }
 
 fn main() {
-    let input = &std::fs::read_to_string("input.txt").expect("input.txt should exist");
-    // println!("part 1: {}", part1(input));
-    // println!("part 2: {}", part2(input));
-    // asdf();
-    // play_with(input);
-
-    // println!("part 1 bruteforce: {}", num_reachable_after_steps_bruteforce(input, 64));
-    // println!("part 1 mathy: {}", num_reachable_after_maps_mathy(1));
+    let args = Args::parse();
+    aoc_core::init_logging(args.common.verbose);
+    let input = &args.common.input.read_or_exit();
+    let infinite_garden =
+        || Garden::infinite_from_str(input).unwrap_or_else(|e| exit_with_error(e));
+    if let Some(Command::Analyze {
+        box_size,
+        start_iteration,
+        iterations,
+    }) = args.command
+    {
+        let garden = infinite_garden();
+        print_entry_point_table(&garden);
+        println!();
+        print_subgarden_cycle_table(&garden, box_size, start_iteration, iterations);
+        println!();
+        print_quadratic_table(&garden);
+        return;
+    }
 
-    println!(
-        "1x1 bruteforce: {}",
-        num_reachable_after_steps_bruteforce(input, 65 + 131 * 1)
-    );
-    println!("1x1 mathy: {}", num_reachable_after_maps_mathy(1));
+    if let Some(path) = args.animate {
+        let garden = infinite_garden();
+        animate(&garden, args.steps.unwrap_or(64), &path).expect("should write animation");
+        return;
+    }
 
-    println!(
-        "2x2 bruteforce: {}",
-        num_reachable_after_steps_bruteforce(input, 65 + 131 * 2)
-    );
-    println!("2x2 mathy: {}", num_reachable_after_maps_mathy(2));
+    if let Some(path) = args.svg {
+        let garden = infinite_garden();
+        draw_svg(&garden, args.steps.unwrap_or(64), &path).expect("should write SVG");
+        return;
+    }
 
-    println!(
-        "3x3 bruteforce: {}",
-        num_reachable_after_steps_bruteforce(input, 65 + 131 * 3)
-    );
-    println!("3x3 mathy: {}", num_reachable_after_maps_mathy(3));
+    let answer =
+        part1(input, args.steps.unwrap_or(STEPS[0])).unwrap_or_else(|e| exit_with_error(e));
+    println!("part 1: {answer}");
+    let answer =
+        part2(input, args.steps.unwrap_or(STEPS[1])).unwrap_or_else(|e| exit_with_error(e));
+    println!("part 2: {answer}");
 }
-
-const TEST_STR: &str = r"...........
-.....###.#.
-.###.##..#.
-..#.#...#..
-....#.#....
-.##..S####.
-.##..#...#.
-.......##..
-.##.#.####.
-.##..##.##.
-...........";
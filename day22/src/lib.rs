@@ -0,0 +1,873 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::Write as _,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc_core::{parse_lines, AocError, SolveError, Solver, StateDump};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Day22Error {
+    #[error(transparent)]
+    Parse(#[from] AocError),
+    #[error("no brick named {0}")]
+    NoSuchBrick(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Brick {
+    name: String,
+    north: i64,
+    south: i64,
+    east: i64,
+    west: i64,
+    top: i64,
+    bottom: i64,
+}
+
+/// Bricks have to fit in this many columns along x and along y, so the heightmap stays small.
+const MAX_XY: i64 = 1000;
+
+impl FromStr for Brick {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || AocError::malformed(s, "x,y,z~x,y,z");
+        let parse_end = |end: &str| -> Result<[i64; 3], AocError> {
+            let coords: Vec<i64> = end
+                .split(',')
+                .map(|v| v.trim().parse().map_err(|_| malformed()))
+                .collect::<Result<_, _>>()?;
+            coords.try_into().map_err(|_| malformed())
+        };
+        let (a, b) = s.split_once('~').ok_or_else(malformed)?;
+        let (a, b) = (parse_end(a)?, parse_end(b)?);
+        if (0..3).filter(|&axis| a[axis] != b[axis]).count() > 1 {
+            return Err(AocError::Invalid(format!(
+                "brick {s:?} extends along more than one axis"
+            )));
+        }
+        let in_range = |end: [i64; 3]| {
+            (0..MAX_XY).contains(&end[0]) && (0..MAX_XY).contains(&end[1]) && end[2] >= 1
+        };
+        if !in_range(a) || !in_range(b) {
+            return Err(AocError::Invalid(format!(
+                "brick {s:?} isn't above the ground within {MAX_XY} of the origin"
+            )));
+        }
+        Ok(Brick {
+            east: std::cmp::min(a[0], b[0]),
+            west: std::cmp::max(a[0], b[0]),
+            north: std::cmp::min(a[1], b[1]),
+            south: std::cmp::max(a[1], b[1]),
+            bottom: std::cmp::min(a[2], b[2]),
+            top: std::cmp::max(a[2], b[2]),
+            name: "?".to_string(),
+        })
+    }
+}
+
+impl Brick {
+    /// Letters naming the brick, from its line of the input: A, B, ..., Z, AA, AB, and so on.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The (x, y, z) cells at either end of the brick, the one nearest the origin first.
+    pub fn ends(&self) -> ([i64; 3], [i64; 3]) {
+        (
+            [self.east, self.north, self.bottom],
+            [self.west, self.south, self.top],
+        )
+    }
+
+    /// Every (x, y, z) cell the brick occupies
+    fn cells(&self) -> impl Iterator<Item = [i64; 3]> + '_ {
+        (self.bottom..=self.top).flat_map(move |z| {
+            (self.north..=self.south)
+                .flat_map(move |y| (self.east..=self.west).map(move |x| [x, y, z]))
+        })
+    }
+
+    fn overlaps_xy(&self, other: &Brick) -> bool {
+        self.east <= other.west
+            && self.west >= other.east
+            && self.north <= other.south
+            && self.south >= other.north
+    }
+
+    fn supports(&self, other: &Brick) -> bool {
+        (other.bottom == (self.top + 1)) && self.overlaps_xy(other)
+    }
+}
+
+#[test]
+fn test_overlaps_xy() {
+    let bricks: Vec<Brick> = TEST_INPUT.lines().map(|l| l.parse().unwrap()).collect();
+
+    let should_overlap = |a: usize, b: usize| {
+        assert!(bricks[a].overlaps_xy(&bricks[b]));
+        assert!(bricks[b].overlaps_xy(&bricks[a]));
+    };
+
+    should_overlap(0, 1);
+    should_overlap(0, 2);
+    should_overlap(2, 3);
+    should_overlap(2, 4);
+    should_overlap(3, 5);
+    should_overlap(4, 5);
+    should_overlap(5, 6);
+}
+
+/// Spreadsheet column style name for the `i`th brick: A to Z, then AA, AB, and so on.
+fn brick_name(mut i: usize) -> String {
+    let mut name = vec![];
+    loop {
+        name.push(b'A' + (i % 26) as u8);
+        if i < 26 {
+            break;
+        }
+        i = i / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+pub fn parse_bricks(input: &str) -> Result<Vec<Brick>, AocError> {
+    let mut bricks: Vec<Brick> = parse_lines(input, str::parse)?;
+
+    // Stacking the bricks where they are from the bottom up, a brick overlaps one already
+    // stacked exactly when the highest of those under it reaches its bottom
+    let mut bottom_up: Vec<usize> = (0..bricks.len()).collect();
+    bottom_up.sort_by_key(|&i| bricks[i].bottom);
+    let mut heightmap = Heightmap::new(&bricks);
+    for i in bottom_up {
+        if let Some((top, j)) = heightmap.highest_under(&bricks[i]) {
+            if top >= bricks[i].bottom {
+                return Err(
+                    AocError::Invalid(format!("brick overlaps the one on line {}", j + 1))
+                        .on_line(i + 1),
+                );
+            }
+        }
+        heightmap.place(i, &bricks[i]);
+    }
+    for (i, b) in bricks.iter_mut().enumerate() {
+        b.name = brick_name(i);
+    }
+    Ok(bricks)
+}
+
+#[test]
+fn test_parse_errors() {
+    let malformed = |line, s| AocError::malformed(s, "x,y,z~x,y,z").on_line(line);
+    assert_eq!(
+        parse_bricks("1,0,1~1,2,1\n0,0,2~2,x,2").unwrap_err(),
+        malformed(2, "0,0,2~2,x,2")
+    );
+    assert_eq!(
+        parse_bricks("1,0,1~1,2").unwrap_err(),
+        malformed(1, "1,0,1~1,2")
+    );
+    assert_eq!(parse_bricks("1,0,1").unwrap_err(), malformed(1, "1,0,1"));
+    assert_eq!(
+        parse_bricks("1,1,8~1,1,9\n0,0,1~1,2,1")
+            .unwrap_err()
+            .to_string(),
+        "line 2: brick \"0,0,1~1,2,1\" extends along more than one axis"
+    );
+    assert_eq!(
+        parse_bricks("1,1,0~1,1,2").unwrap_err().to_string(),
+        "line 1: brick \"1,1,0~1,1,2\" isn't above the ground within 1000 of the origin"
+    );
+    assert!(parse_bricks("0,-1,1~0,0,1").is_err());
+    assert_eq!(
+        parse_bricks("1,1,1~1,0,1\n0,0,3~0,0,5\n0,1,1~3,1,1")
+            .unwrap_err()
+            .to_string(),
+        "line 3: brick overlaps the one on line 1"
+    );
+    assert!(parse_bricks("0,0,5~1000,0,5").is_err());
+    assert_eq!(
+        parse_bricks("0,0,2~0,0,6\n2,0,1~2,0,1\n0,0,2~2,0,2")
+            .unwrap_err()
+            .to_string(),
+        "line 3: brick overlaps the one on line 1"
+    );
+    assert!(parse_bricks("0,0,4~0,0,6\n0,0,1~0,0,3\n1,0,3~1,0,9").is_ok());
+}
+
+#[test]
+fn test_serde() {
+    let bricks = parse_bricks(TEST_INPUT).unwrap();
+    let json = serde_json::to_string(&bricks).unwrap();
+    assert!(json
+        .starts_with(r#"[{"name":"A","north":0,"south":2,"east":1,"west":1,"top":1,"bottom":1},"#));
+    assert_eq!(serde_json::from_str::<Vec<Brick>>(&json).unwrap(), bricks);
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_brick_round_trip(
+        a in proptest::array::uniform3(0..10i64),
+        axis in 0..3usize,
+        len in 0..5i64,
+    ) {
+        // The ground is at z = 0, so bricks start at 1.
+        let a = [a[0], a[1], a[2] + 1];
+        let mut b = a;
+        b[axis] += len;
+        let brick: Brick = format!("{},{},{}~{},{},{}", a[0], a[1], a[2], b[0], b[1], b[2])
+            .parse()
+            .unwrap();
+        proptest::prop_assert_eq!(
+            [brick.east, brick.north, brick.bottom, brick.west, brick.south, brick.top],
+            [a[0], a[1], a[2], b[0], b[1], b[2]]
+        );
+    }
+
+    #[test]
+    fn test_parse_never_panics(
+        input in "(-?[0-9]{1,20},-?[0-9]{1,20}(,-?[0-9]{1,20})?~?-?[0-9]{1,20},[0-9]{0,3},[0-9]{1,3}\n){0,4}|\\PC*",
+    ) {
+        let _ = parse_bricks(&input);
+    }
+}
+
+#[test]
+fn test_supports() {
+    let mut bricks = parse_bricks(TEST_INPUT).unwrap();
+    settle_bricks(&mut bricks);
+    bricks.sort_unstable_by_key(|b| b.name.clone());
+
+    let check_supporters = |test: usize, supporters: &[usize]| {
+        for i in 0..bricks.len() {
+            if supporters.iter().find(|x| **x == i).is_some() {
+                assert!(
+                    bricks[test].supports(&bricks[i]),
+                    "{test} ({:?}) should support {i} ({:?})",
+                    bricks[test],
+                    bricks[i]
+                );
+            } else {
+                assert!(
+                    !bricks[test].supports(&bricks[i]),
+                    "{test} ({:?}) should NOT support {i} ({:?})",
+                    bricks[test],
+                    bricks[i]
+                );
+            }
+        }
+    };
+
+    check_supporters(0, &[1, 2]);
+    check_supporters(2, &[3, 4]);
+    check_supporters(3, &[5]);
+    check_supporters(4, &[5]);
+    check_supporters(5, &[6]);
+    check_supporters(6, &[]);
+}
+
+/// The topmost brick in each XY column of the stack so far.
+struct Heightmap {
+    width: usize,
+    /// Top of the highest brick in each column and its index, or None if nothing has landed in
+    /// that column yet
+    columns: Vec<Option<(i64, usize)>>,
+}
+
+impl Heightmap {
+    fn new(bricks: &[Brick]) -> Self {
+        let width = bricks.iter().map(|b| b.west + 1).max().unwrap_or(0) as usize;
+        let depth = bricks.iter().map(|b| b.south + 1).max().unwrap_or(0) as usize;
+        Heightmap {
+            width,
+            columns: vec![None; width * depth],
+        }
+    }
+
+    /// Indices into `columns` of every column the brick covers
+    fn columns_under(&self, brick: &Brick) -> impl Iterator<Item = usize> + '_ {
+        let xs = brick.east as usize..=brick.west as usize;
+        (brick.north as usize..=brick.south as usize)
+            .flat_map(move |y| xs.clone().map(move |x| y * self.width + x))
+    }
+
+    /// Top of the highest brick in any of the columns the brick covers and its index, or None
+    /// if nothing has landed in them yet.
+    fn highest_under(&self, brick: &Brick) -> Option<(i64, usize)> {
+        self.columns_under(brick)
+            .filter_map(|col| self.columns[col])
+            .max_by_key(|&(top, _)| top)
+    }
+
+    /// Makes the brick with index `idx` the topmost in every column it covers.
+    fn place(&mut self, idx: usize, brick: &Brick) {
+        for col in self.columns_under(brick).collect::<Vec<_>>() {
+            self.columns[col] = Some((brick.top, idx));
+        }
+    }
+
+    /// Drops the brick with index `idx` until it lands on the ground or on other bricks, and
+    /// returns the bricks it comes to rest on.
+    fn drop_brick(&mut self, idx: usize, brick: &mut Brick) -> Vec<usize> {
+        let highest_below = self.highest_under(brick).map_or(0, |(top, _)| top);
+        let amount_to_fall = brick.bottom - highest_below - 1;
+        assert!(
+            amount_to_fall >= 0,
+            "brick {} overlaps one below it, which parse_bricks rules out",
+            brick.name
+        );
+        brick.bottom -= amount_to_fall;
+        brick.top -= amount_to_fall;
+        assert!(brick.bottom > 0);
+
+        let mut resting_on = vec![];
+        for col in self.columns_under(brick) {
+            if let Some((top, below)) = self.columns[col] {
+                if top == highest_below && !resting_on.contains(&below) {
+                    resting_on.push(below);
+                }
+            }
+        }
+        self.place(idx, brick);
+        resting_on
+    }
+}
+
+/// Sorts the bricks from the bottom up and lets them fall as far as they can. Returns, for each
+/// brick in the new order, the bricks directly under it that it rests on.
+fn settle_bricks(bricks: &mut [Brick]) -> Vec<Vec<usize>> {
+    bricks.sort_unstable_by_key(|b| b.bottom);
+
+    let mut heightmap = Heightmap::new(bricks);
+    bricks
+        .iter_mut()
+        .enumerate()
+        .map(|(idx, brick)| heightmap.drop_brick(idx, brick))
+        .collect()
+}
+
+/// Settles the bricks and works out what supports what.
+pub fn support_graph(bricks: &[Brick]) -> SupportGraph {
+    let mut bricks = bricks.to_vec();
+    let supported_by = settle_bricks(&mut bricks);
+    SupportGraph::new(bricks, supported_by)
+}
+
+/// What happens to a settled stack when one brick is taken out.
+pub struct RemovalReport {
+    /// Bricks that fell, by their id in the original stack, and where each one came to rest
+    pub fallen: Vec<(BrickId, Brick)>,
+    /// The stack once everything has settled again. Its ids don't match the original stack's.
+    pub graph: SupportGraph,
+}
+
+/// Takes brick `id` out of the settled stack `bricks` and lets the rest settle again.
+pub fn simulate_removal(bricks: &[Brick], id: BrickId) -> RemovalReport {
+    let remaining: Vec<Brick> = bricks[..id]
+        .iter()
+        .chain(&bricks[id + 1..])
+        .cloned()
+        .collect();
+    let graph = support_graph(&remaining);
+    let original_ids: HashMap<&str, BrickId> = bricks
+        .iter()
+        .enumerate()
+        .map(|(id, brick)| (&*brick.name, id))
+        .collect();
+    let fallen = graph
+        .bricks()
+        .iter()
+        .map(|brick| (original_ids[&*brick.name], brick))
+        .filter(|&(original, brick)| bricks[original].bottom != brick.bottom)
+        .map(|(original, brick)| (original, brick.clone()))
+        .collect();
+    RemovalReport { fallen, graph }
+}
+
+/// Describes which bricks fall, and where they come to rest, if the brick called `name` is
+/// taken out of the settled stack.
+pub fn describe_removal(graph: &SupportGraph, name: &str) -> Result<String, Day22Error> {
+    let Some(id) = graph.bricks().iter().position(|b| b.name == name) else {
+        return Err(Day22Error::NoSuchBrick(name.to_string()));
+    };
+    let report = simulate_removal(graph.bricks(), id);
+    let settled_ids = report.graph.ids_by_name();
+    let mut out = format!(
+        "Removing {name} makes {} bricks fall\n",
+        report.fallen.len()
+    );
+    for (original, brick) in &report.fallen {
+        let before = &graph.bricks()[*original];
+        let below = report.graph.supported_by(settled_ids[&*brick.name]);
+        let resting_on = if below.is_empty() {
+            "the ground".to_string()
+        } else {
+            below
+                .iter()
+                .map(|&below| &*report.graph.bricks()[below].name)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        writeln!(
+            out,
+            "  {}: z={}..{} -> z={}..{}, now resting on {resting_on}",
+            brick.name, before.bottom, before.top, brick.bottom, brick.top,
+        )
+        .unwrap();
+    }
+    Ok(out)
+}
+
+/// Index of a brick in the settled stack, which is sorted from the bottom up.
+pub type BrickId = usize;
+
+/// Which bricks rest directly on which, once they've settled.
+pub struct SupportGraph {
+    /// The settled bricks, sorted from the bottom up
+    bricks: Vec<Brick>,
+    /// Bricks resting on each brick
+    supports: Vec<Vec<BrickId>>,
+    /// Bricks each brick rests on
+    supported_by: Vec<Vec<BrickId>>,
+}
+
+impl SupportGraph {
+    /// Builds the graph from what `settle_bricks` says each brick rests on.
+    fn new(bricks: Vec<Brick>, supported_by: Vec<Vec<BrickId>>) -> Self {
+        let mut supports = vec![vec![]; supported_by.len()];
+        for (above, below) in supported_by.iter().enumerate() {
+            for &below in below {
+                debug_assert!(bricks[below].supports(&bricks[above]));
+                supports[below].push(above);
+            }
+        }
+        SupportGraph {
+            bricks,
+            supports,
+            supported_by,
+        }
+    }
+
+    /// The settled bricks, from the bottom up.
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// Each brick's id, by its name.
+    fn ids_by_name(&self) -> HashMap<&str, BrickId> {
+        self.bricks
+            .iter()
+            .enumerate()
+            .map(|(id, brick)| (&*brick.name, id))
+            .collect()
+    }
+
+    /// Bricks resting directly on `id`
+    pub fn supports(&self, id: BrickId) -> &[BrickId] {
+        &self.supports[id]
+    }
+
+    /// Bricks `id` rests directly on
+    pub fn supported_by(&self, id: BrickId) -> &[BrickId] {
+        &self.supported_by[id]
+    }
+
+    /// Graphviz description of the graph, with an edge from each brick to the ones resting on
+    /// it, drawn from the ground up.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph bricks {\n    rankdir=BT;\n");
+        for (id, brick) in self.bricks.iter().enumerate() {
+            dot += &format!(
+                "    {id} [label=\"{}\\nz={}..{}\"];\n",
+                brick.name, brick.bottom, brick.top
+            );
+        }
+        for (id, above) in self.supports.iter().enumerate() {
+            for above in above {
+                dot += &format!("    {id} -> {above};\n");
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+
+    fn is_safe_to_disintegrate(&self, id: BrickId) -> bool {
+        self.supports(id)
+            .iter()
+            .all(|&above| self.supported_by(above).len() > 1)
+    }
+
+    fn num_safe_to_disintegrate(&self) -> usize {
+        (0..self.bricks.len())
+            .filter(|&id| self.is_safe_to_disintegrate(id))
+            .count()
+    }
+
+    /// Number of other bricks that fall if `id` is disintegrated: a brick falls once everything
+    /// it rests on has fallen.
+    fn num_falling_if_removed(&self, id: BrickId) -> usize {
+        let mut num_fallen_under = vec![0; self.supports.len()];
+        let mut queue = VecDeque::from([id]);
+        let mut num_falling = 0;
+        while let Some(fallen) = queue.pop_front() {
+            for &above in &self.supports[fallen] {
+                num_fallen_under[above] += 1;
+                if num_fallen_under[above] == self.supported_by[above].len() {
+                    num_falling += 1;
+                    queue.push_back(above);
+                }
+            }
+        }
+        num_falling
+    }
+
+    /// Each brick's immediate dominator: the nearest brick that every chain of supports from
+    /// the ground up to it goes through, or None if only the ground does.
+    ///
+    /// Relies on bricks only resting on bricks with lower ids, which `settle_bricks` ensures.
+    fn dominator_tree(&self) -> Vec<Option<BrickId>> {
+        let mut idom: Vec<Option<BrickId>> = Vec::with_capacity(self.supported_by.len());
+        // Depth in the dominator tree, where the ground is 0
+        let mut depth: Vec<usize> = Vec::with_capacity(self.supported_by.len());
+        for below in &self.supported_by {
+            // The immediate dominator is the lowest common ancestor of all the supporters
+            let lca = |a: Option<BrickId>, b: Option<BrickId>| {
+                let (mut a, mut b) = (a, b);
+                let depth_of = |n: Option<BrickId>| n.map_or(0, |n| depth[n]);
+                while depth_of(a) > depth_of(b) {
+                    a = idom[a.unwrap()];
+                }
+                while depth_of(b) > depth_of(a) {
+                    b = idom[b.unwrap()];
+                }
+                while a != b {
+                    a = idom[a.unwrap()];
+                    b = idom[b.unwrap()];
+                }
+                a
+            };
+            let dominator = below.iter().map(|&b| Some(b)).reduce(lca).unwrap_or(None);
+            depth.push(dominator.map_or(0, |d| depth[d]) + 1);
+            idom.push(dominator);
+        }
+        idom
+    }
+
+    /// Number of other bricks that fall if each brick is disintegrated, which is the size of its
+    /// subtree in the dominator tree.
+    fn num_falling_by_dominators(&self) -> Vec<usize> {
+        let idom = self.dominator_tree();
+        let mut subtree_sizes = vec![1; idom.len()];
+        for id in (0..idom.len()).rev() {
+            if let Some(parent) = idom[id] {
+                subtree_sizes[parent] += subtree_sizes[id];
+            }
+        }
+        subtree_sizes.into_iter().map(|size| size - 1).collect()
+    }
+}
+
+/// Ways of totalling up part 2, which should all agree.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Part2Solver {
+    /// Breadth-first search of the chain reaction from each brick
+    Bfs,
+    /// Subtree sizes of the support graph's dominator tree
+    Dominators,
+}
+
+impl Part2Solver {
+    fn solve(self, graph: &SupportGraph) -> usize {
+        match self {
+            Part2Solver::Bfs => (0..graph.bricks().len())
+                .map(|id| graph.num_falling_if_removed(id))
+                .sum(),
+            Part2Solver::Dominators => graph.num_falling_by_dominators().into_iter().sum(),
+        }
+    }
+}
+
+#[test]
+fn test_settle_bricks() {
+    let mut bricks = parse_bricks(TEST_INPUT).unwrap();
+    let resting_on = settle_bricks(&mut bricks);
+    let names = |idxs: &[usize]| -> String { idxs.iter().map(|&i| &*bricks[i].name).collect() };
+    let resting_on: Vec<String> = resting_on.iter().map(|r| names(r)).collect();
+    assert_eq!(resting_on, ["", "A", "A", "BC", "BC", "DE", "F"]);
+    assert_eq!(bricks[6].bottom, 5);
+    assert_eq!(bricks[6].top, 6);
+}
+
+#[test]
+fn test_num_falling_if_removed() {
+    let graph = support_graph(&parse_bricks(TEST_INPUT).unwrap());
+
+    assert_eq!(graph.num_falling_if_removed(0), 6);
+    assert_eq!(graph.num_falling_if_removed(1), 0);
+    assert_eq!(graph.num_falling_if_removed(2), 0);
+    assert_eq!(graph.num_falling_if_removed(3), 0);
+    assert_eq!(graph.num_falling_if_removed(4), 0);
+    assert_eq!(graph.num_falling_if_removed(5), 1);
+    assert_eq!(graph.num_falling_if_removed(6), 0);
+}
+
+pub fn part1(input: &str) -> Result<usize, Day22Error> {
+    Ok(support_graph(&parse_bricks(input)?).num_safe_to_disintegrate())
+}
+
+#[test]
+fn test_support_graph() {
+    let graph = support_graph(&parse_bricks(TEST_INPUT).unwrap());
+    assert_eq!(graph.supports(0), [1, 2]);
+    assert_eq!(graph.supported_by(3), [1, 2]);
+    assert!(graph.supported_by(0).is_empty());
+    assert_eq!(graph.bricks()[6].name(), "G");
+    assert_eq!(graph.bricks()[6].ends(), ([1, 1, 5], [1, 1, 6]));
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph bricks {"));
+    assert!(dot.contains("    0 [label=\"A\\nz=1..1\"];\n"));
+    assert!(dot.contains("    6 [label=\"G\\nz=5..6\"];\n"));
+    assert!(dot.contains("    0 -> 1;\n"));
+    assert!(dot.contains("    5 -> 6;\n"));
+    assert_eq!(dot.matches("->").count(), 9);
+}
+
+/// Colour for a brick that stays the same between runs and differs between neighbouring ids,
+/// by stepping the hue around the colour wheel by the golden angle.
+fn brick_color(id: BrickId) -> [u8; 3] {
+    let hue = (id as f64 * 137.508) % 360.0;
+    let sector = hue / 60.0;
+    let rising = (255.0 * (1.0 - (sector % 2.0 - 1.0).abs())) as u8;
+    match sector as u8 {
+        0 => [255, rising, 0],
+        1 => [rising, 255, 0],
+        2 => [0, 255, rising],
+        3 => [0, rising, 255],
+        4 => [rising, 0, 255],
+        _ => [255, 0, rising],
+    }
+}
+
+#[derive(Serialize)]
+struct Voxels<'a> {
+    name: &'a str,
+    color: [u8; 3],
+    cells: Vec<[i64; 3]>,
+}
+
+/// Writes each settled brick's cells as JSON, or as an OBJ with a cube per cell (using the
+/// common vertex colour extension) if the path ends in `.obj`.
+pub fn export_voxels(graph: &SupportGraph, path: &Path) -> std::io::Result<()> {
+    let contents = if path.extension().is_some_and(|ext| ext == "obj") {
+        voxels_to_obj(graph)
+    } else {
+        let voxels: Vec<Voxels> = graph
+            .bricks()
+            .iter()
+            .enumerate()
+            .map(|(id, brick)| Voxels {
+                name: &brick.name,
+                color: brick_color(id),
+                cells: brick.cells().collect(),
+            })
+            .collect();
+        serde_json::to_string(&voxels)?
+    };
+    std::fs::write(path, contents)
+}
+
+fn voxels_to_obj(graph: &SupportGraph) -> String {
+    const CORNERS: [[i64; 3]; 8] = [
+        [0, 0, 0],
+        [1, 0, 0],
+        [1, 1, 0],
+        [0, 1, 0],
+        [0, 0, 1],
+        [1, 0, 1],
+        [1, 1, 1],
+        [0, 1, 1],
+    ];
+    const FACES: [[usize; 4]; 6] = [
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+        [0, 1, 5, 4],
+        [2, 3, 7, 6],
+        [1, 2, 6, 5],
+        [0, 4, 7, 3],
+    ];
+    let mut obj = String::new();
+    let mut num_vertices = 0;
+    for (id, brick) in graph.bricks().iter().enumerate() {
+        let [r, g, b] = brick_color(id).map(|c| c as f64 / 255.0);
+        writeln!(obj, "o {}_{id}", brick.name).unwrap();
+        for [x, y, z] in brick.cells() {
+            for [dx, dy, dz] in CORNERS {
+                writeln!(
+                    obj,
+                    "v {} {} {} {r:.3} {g:.3} {b:.3}",
+                    x + dx,
+                    y + dy,
+                    z + dz
+                )
+                .unwrap();
+            }
+            for face in FACES {
+                let [a, b, c, d] = face.map(|corner| num_vertices + corner + 1);
+                writeln!(obj, "f {a} {b} {c} {d}").unwrap();
+            }
+            num_vertices += CORNERS.len();
+        }
+    }
+    obj
+}
+
+#[test]
+fn test_export_voxels() {
+    let graph = support_graph(&parse_bricks(TEST_INPUT).unwrap());
+    let cells: Vec<[i64; 3]> = graph.bricks()[6].cells().collect();
+    assert_eq!(cells, [[1, 1, 5], [1, 1, 6]]);
+    assert_ne!(brick_color(0), brick_color(1));
+
+    let obj = voxels_to_obj(&graph);
+    let num_cells: usize = graph.bricks().iter().map(|b| b.cells().count()).sum();
+    assert_eq!(
+        obj.lines().filter(|l| l.starts_with("v ")).count(),
+        8 * num_cells
+    );
+    assert_eq!(
+        obj.lines().filter(|l| l.starts_with("f ")).count(),
+        6 * num_cells
+    );
+    assert!(obj.starts_with("o A_0\nv 1 0 1 "));
+}
+
+#[test]
+fn test_simulate_removal() {
+    let graph = support_graph(&parse_bricks(TEST_INPUT).unwrap());
+    for id in 0..graph.bricks().len() {
+        let report = simulate_removal(graph.bricks(), id);
+        assert_eq!(report.fallen.len(), graph.num_falling_if_removed(id));
+        assert_eq!(report.graph.bricks().len(), graph.bricks().len() - 1);
+    }
+
+    let report = simulate_removal(graph.bricks(), 5);
+    let [(6, g)] = &report.fallen[..] else {
+        panic!("only G should fall, got {:?}", report.fallen);
+    };
+    assert_eq!((g.name.as_str(), g.bottom, g.top), ("G", 2, 3));
+    assert_eq!(
+        report.graph.supported_by(report.graph.ids_by_name()["G"]),
+        [0]
+    );
+
+    assert_eq!(brick_name(25), "Z");
+    assert_eq!(brick_name(26), "AA");
+    assert_eq!(brick_name(27 * 26), "AAA");
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), Ok(5));
+}
+
+pub fn part2(input: &str, solver: Part2Solver) -> Result<usize, Day22Error> {
+    let graph = support_graph(&parse_bricks(input)?);
+    Ok(solver.solve(&graph))
+}
+
+impl Solver for SupportGraph {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(support_graph(&parse_bricks(input)?))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.num_safe_to_disintegrate().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(Part2Solver::Bfs.solve(self).to_string())
+    }
+
+    /// Part 2 the other way, with the dominator tree. Part 1 is only ever solved one way.
+    fn naive(&self, part: u8) -> Result<String, SolveError> {
+        match part {
+            2 => Ok(Part2Solver::Dominators.solve(self).to_string()),
+            _ => Err(SolveError::NotImplemented),
+        }
+    }
+
+    /// The bricks once they've settled, and which bricks each one rests on, by name.
+    fn dump_state(&self, dump: &StateDump) -> Result<(), SolveError> {
+        dump.write("settled_bricks", &self.bricks())?;
+        let resting_on: BTreeMap<&str, Vec<&str>> = (0..self.bricks().len())
+            .map(|id| {
+                let under = self.supported_by(id).iter();
+                let names = under.map(|&under| self.bricks()[under].name()).collect();
+                (self.bricks()[id].name(), names)
+            })
+            .collect();
+        dump.write("resting_on", &resting_on)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT, Part2Solver::Bfs), Ok(7));
+    let graph = SupportGraph::parse(TEST_INPUT).unwrap();
+    assert_eq!(graph.naive(2), Ok("7".to_string()));
+    assert_eq!(graph.naive(1), Err(SolveError::NotImplemented));
+}
+
+#[test]
+fn test_dump_state() {
+    let dir = std::env::temp_dir().join(format!("day22-dump-test-{}", std::process::id()));
+    let graph = SupportGraph::parse(TEST_INPUT).unwrap();
+    graph.dump_state(&StateDump::new(&dir)).unwrap();
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+    let settled: Vec<Brick> = serde_json::from_str(&read("settled_bricks.json")).unwrap();
+    assert_eq!(settled, graph.bricks());
+    let resting_on: BTreeMap<String, Vec<String>> =
+        serde_json::from_str(&read("resting_on.json")).unwrap();
+    assert_eq!(resting_on["A"], [""; 0]);
+    assert_eq!(resting_on["D"], ["B", "C"]);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_solver() {
+    assert_eq!(SupportGraph::solve(TEST_INPUT, 1), Ok("5".to_string()));
+    assert_eq!(SupportGraph::solve(TEST_INPUT, 2), Ok("7".to_string()));
+}
+
+#[test]
+fn test_part2_solvers_agree() {
+    // A second stack where a brick's dominator isn't one of the bricks it rests on
+    let diamond = "0,0,1~1,0,1\n0,0,2~0,0,2\n1,0,2~1,0,2\n0,0,3~1,0,3\n0,0,4~0,0,6";
+    for input in [TEST_INPUT, diamond] {
+        let graph = support_graph(&parse_bricks(input).unwrap());
+        let by_bfs: Vec<usize> = (0..graph.bricks().len())
+            .map(|id| graph.num_falling_if_removed(id))
+            .collect();
+        assert_eq!(graph.num_falling_by_dominators(), by_bfs);
+        assert_eq!(graph.dominator_tree()[3], Some(0), "{input}");
+        for solver in Part2Solver::value_variants() {
+            assert_eq!(
+                solver.solve(&graph),
+                by_bfs.iter().sum::<usize>(),
+                "{solver:?} on {input}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(22, 1);
@@ -0,0 +1,1089 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    fmt,
+    ops::Index,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+pub use aoc_core::Coord;
+use aoc_core::{Cancel, Dir, Dot, Grid, ParseGridError, SolveError, Solver};
+use clap::ValueEnum;
+use rayon::prelude::*;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Day23Error {
+    #[error("no open tile in the top row to start from")]
+    NoEntrance,
+    #[error("no open tile in the bottom row to finish at")]
+    NoExit,
+    #[error("expected a coordinate like 1,0 but got {0:?}")]
+    InvalidCoord(String),
+    #[error("({x},{y}) is not an open tile")]
+    NotOpen { x: i64, y: i64 },
+    #[error(transparent)]
+    Malformed(#[from] ParseGridError<Infallible>),
+    #[error("no route from the start to the end")]
+    NoRoute,
+    #[error("ran out of time before finding any route from the start to the end")]
+    TimedOut,
+    #[error("malformed checkpoint line {0:?}")]
+    MalformedCheckpoint(String),
+    #[error("checkpoint route {0} doesn't follow the trails from the start")]
+    OffTrail(String),
+    #[error("checkpoint's best route {0} isn't a route to the end that long")]
+    WrongBest(String),
+}
+
+pub struct Maze {
+    maze: Grid<char>,
+    start: Coord,
+    end: Coord,
+    /// How long the part 2 search can take through [`Solver`] before giving up
+    time_limit: Option<Duration>,
+}
+
+impl FromStr for Maze {
+    type Err = Day23Error;
+
+    /// Parses the map, starting from the open tile in the top row and finishing at the one in
+    /// the bottom row.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let maze: Grid<char> = s.trim().parse()?;
+        let open_in = |row: Option<&[char]>| row?.iter().position(|&ch| ch != '#');
+        let start_x = open_in(maze.rows().next()).ok_or(Day23Error::NoEntrance)?;
+        let end_x = open_in(maze.rows().last()).ok_or(Day23Error::NoExit)?;
+        Ok(Self {
+            start: Coord::new(start_x as i64, 0),
+            end: Coord::new(end_x as i64, maze.height() as i64 - 1),
+            maze,
+            time_limit: None,
+        })
+    }
+}
+
+/// How the slope tiles (`>`, `<`, `^`, `v`) can be walked on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SlopeRule {
+    /// Slopes can only be walked downhill, in the direction they point
+    Directed,
+    /// Slopes are ordinary paths
+    Ignored,
+    /// Slopes can be walked along or across, but never against the way they point
+    UphillForbidden,
+}
+
+/// Which way a slope tile points.
+fn slope_direction(tile: char) -> Option<Dir> {
+    match tile {
+        '>' => Some(Dir::East),
+        '<' => Some(Dir::West),
+        '^' => Some(Dir::North),
+        'v' => Some(Dir::South),
+        _ => None,
+    }
+}
+
+/// Parses a tile's coordinates written as `x,y`.
+pub fn parse_coord(s: &str) -> Result<Coord, Day23Error> {
+    let err = || Day23Error::InvalidCoord(s.to_string());
+    let (x, y) = s.split_once(',').ok_or_else(err)?;
+    Ok(Coord::new(
+        x.trim().parse().map_err(|_| err())?,
+        y.trim().parse().map_err(|_| err())?,
+    ))
+}
+
+impl Maze {
+    fn start(&self) -> Coord {
+        self.start
+    }
+
+    fn end(&self) -> Coord {
+        self.end
+    }
+
+    /// Replaces the detected start and end tiles with the given ones.
+    pub fn with_endpoints(
+        self,
+        start: Option<Coord>,
+        end: Option<Coord>,
+    ) -> Result<Self, Day23Error> {
+        let start = start.unwrap_or(self.start);
+        let end = end.unwrap_or(self.end);
+        for coord @ Coord { x, y } in [start, end] {
+            if self.is_blocked(&coord) {
+                return Err(Day23Error::NotOpen { x, y });
+            }
+        }
+        Ok(Self { start, end, ..self })
+    }
+
+    fn width(&self) -> i64 {
+        self.maze.width() as i64
+    }
+    fn height(&self) -> i64 {
+        self.maze.height() as i64
+    }
+
+    fn is_open(&self, coord: &Coord) -> bool {
+        !self.is_blocked(coord)
+    }
+
+    fn is_blocked(&self, coord: &Coord) -> bool {
+        self.maze.get(*coord).is_none_or(|&tile| tile == '#')
+    }
+
+    fn possible_next_steps(&self, coord: &Coord, slopes: SlopeRule) -> Vec<Coord> {
+        let here = self[*coord];
+        let is_uphill = |tile: char, step: &Coord| {
+            slope_direction(tile).is_some_and(|dir| *step + dir == *coord)
+        };
+        coord
+            .neighbors4()
+            .filter(|step| !self.is_blocked(step))
+            .filter(|step| match slopes {
+                SlopeRule::Directed => {
+                    slope_direction(here).is_none_or(|dir| *step == *coord + dir)
+                }
+                SlopeRule::Ignored => true,
+                SlopeRule::UphillForbidden => {
+                    !is_uphill(here, step) && !is_uphill(self[*step], step)
+                }
+            })
+            .collect()
+    }
+
+    /// The maze as drawn in the puzzle, with the start marked `S` and the rest of `path`
+    /// marked `O`.
+    pub fn render_path(&self, path: &HashSet<Coord>) -> Grid<char> {
+        Grid::from_fn(self.maze.width(), self.maze.height(), |coord| {
+            if coord == self.start() {
+                'S'
+            } else if path.contains(&coord) {
+                assert!(self.is_open(&coord));
+                'O'
+            } else {
+                self[coord]
+            }
+        })
+    }
+
+    /// Length of the longest path from the start to the end that never steps on the same tile
+    /// twice, found by trying every route tile by tile.
+    fn max_path(&self, slopes: SlopeRule) -> Option<usize> {
+        let (start, end) = (self.start(), self.end());
+        let mut visited = Grid::new(self.maze.width(), self.maze.height(), false);
+        visited[start] = true;
+        self.max_path_from(start, end, slopes, &mut visited)
+    }
+
+    /// Longest path from `here` to `end` that avoids `visited` tiles, or None if there's no way
+    /// through. `visited` is back how it started when this returns.
+    fn max_path_from(
+        &self,
+        here: Coord,
+        end: Coord,
+        slopes: SlopeRule,
+        visited: &mut Grid<bool>,
+    ) -> Option<usize> {
+        if here == end {
+            return Some(0);
+        }
+        let mut longest = None;
+        for next in self.possible_next_steps(&here, slopes) {
+            let seen = &mut visited[next];
+            if *seen {
+                continue;
+            }
+            *seen = true;
+            let rest = self.max_path_from(next, end, slopes, visited);
+            visited[next] = false;
+            longest = longest.max(rest.map(|rest| rest + 1));
+        }
+        longest
+    }
+
+    /// Open tiles with more than two open neighbours, where paths split.
+    fn is_junction(&self, coord: &Coord) -> bool {
+        self.is_open(coord) && coord.neighbors4().filter(|n| self.is_open(n)).count() > 2
+    }
+}
+
+/// Collapses every corridor between junctions of the maze into a single weighted edge. With
+/// `SlopeRule::Directed`, an edge only exists in the direction its corridor can be walked.
+pub fn trail_graph(maze: &Maze, slopes: SlopeRule) -> TrailGraph {
+    let mut nodes = vec![maze.start(), maze.end()];
+    nodes.dedup();
+    for y in 0..maze.height() {
+        for x in 0..maze.width() {
+            let coord = Coord::new(x, y);
+            if maze.is_junction(&coord) && !nodes.contains(&coord) {
+                nodes.push(coord);
+            }
+        }
+    }
+    let node_ids: HashMap<Coord, usize> = nodes.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+    // Tiles from the step after `node` up to the next junction, or None if it's a dead end
+    let walk_corridor = |node: Coord, first: Coord| -> Option<Vec<Coord>> {
+        let (mut prev, mut tiles) = (node, vec![first]);
+        while let Some(&here) = tiles.last().filter(|t| !node_ids.contains_key(t)) {
+            let next = maze
+                .possible_next_steps(&here, slopes)
+                .into_iter()
+                .find(|&n| n != prev)?;
+            prev = here;
+            tiles.push(next);
+        }
+        Some(tiles)
+    };
+
+    let mut edges = vec![vec![]; nodes.len()];
+    let mut corridors = HashMap::new();
+    for (id, &node) in nodes.iter().enumerate() {
+        for first in maze.possible_next_steps(&node, slopes) {
+            let Some(tiles) = walk_corridor(node, first) else {
+                continue;
+            };
+            let next = node_ids[tiles.last().unwrap()];
+            if next == id {
+                continue;
+            }
+            // Of several corridors between the same junctions, only the longest matters
+            let longer = corridors
+                .get(&(id, next))
+                .is_none_or(|existing: &Vec<Coord>| existing.len() < tiles.len());
+            if longer {
+                corridors.insert((id, next), tiles);
+            }
+        }
+    }
+    for (&(from, to), tiles) in &corridors {
+        edges[from].push((to, tiles.len()));
+    }
+    for edges in &mut edges {
+        edges.sort_unstable();
+    }
+    TrailGraph {
+        nodes,
+        edges,
+        corridors,
+        start: 0,
+        end: node_ids[&maze.end()],
+    }
+}
+
+/// The maze reduced to its junctions, with the corridors between them as weighted edges.
+pub struct TrailGraph {
+    /// Coordinates of the start, the end (unless it's also the start), and then every junction
+    nodes: Vec<Coord>,
+    /// For each node, the nodes it leads to and the number of steps to get there
+    edges: Vec<Vec<(usize, usize)>>,
+    /// Tiles along the corridor for each edge, ending with the node it leads to
+    corridors: HashMap<(usize, usize), Vec<Coord>>,
+    start: usize,
+    end: usize,
+}
+
+/// How long the longest route found so far is, and the nodes along it.
+type BestRoute = (usize, Vec<usize>);
+
+/// A partial path still to be searched from, and the nodes along it.
+type SearchStart = (PartialPath, Vec<usize>);
+
+/// Which nodes of a trail graph a path has been through, a bit for each.
+#[derive(Clone)]
+struct NodeSet(Vec<u64>);
+
+impl NodeSet {
+    /// Room for `len` nodes, with none of them in it.
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn contains(&self, node: usize) -> bool {
+        self.0[node / 64] & (1 << (node % 64)) != 0
+    }
+
+    fn insert(&mut self, node: usize) {
+        self.0[node / 64] |= 1 << (node % 64);
+    }
+
+    fn remove(&mut self, node: usize) {
+        self.0[node / 64] &= !(1 << (node % 64));
+    }
+}
+
+/// A partly explored path through the trail graph.
+#[derive(Clone)]
+struct PartialPath {
+    node: usize,
+    visited: NodeSet,
+    steps: usize,
+    /// Upper bound on how many more steps the path could take: the longest edge into each
+    /// node it hasn't visited yet
+    remaining: usize,
+}
+
+/// How a longest route search stood at some point, by the tiles of the junctions along each
+/// route, which is enough to pick the search up again from there.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// How long the longest route to the end found so far is, and the route itself
+    pub best: Option<(usize, Vec<Coord>)>,
+    /// The partial paths from the start that are still left to search
+    pub frontier: Vec<Vec<Coord>>,
+}
+
+/// The tiles of `route` like `1,0 3,5 11,3`.
+fn route_text(route: &[Coord]) -> String {
+    let tiles: Vec<String> = route
+        .iter()
+        .map(|Coord { x, y }| format!("{x},{y}"))
+        .collect();
+    tiles.join(" ")
+}
+
+/// The best route on a `best` line, followed by each partial path on a line of its own.
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((steps, route)) = &self.best {
+            writeln!(f, "best {steps} {}", route_text(route))?;
+        }
+        for route in &self.frontier {
+            writeln!(f, "{}", route_text(route))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = Day23Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let route = |tiles: &str| -> Result<Vec<Coord>, Day23Error> {
+            tiles.split_whitespace().map(parse_coord).collect()
+        };
+        let mut checkpoint = Checkpoint::default();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            match line.strip_prefix("best ") {
+                Some(best) => {
+                    let malformed = || Day23Error::MalformedCheckpoint(line.to_string());
+                    let (steps, tiles) = best.split_once(' ').ok_or_else(malformed)?;
+                    let steps = steps.parse().map_err(|_| malformed())?;
+                    checkpoint.best = Some((steps, route(tiles)?));
+                }
+                None => checkpoint.frontier.push(route(line)?),
+            }
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// Hears about a longest route search as it goes.
+pub trait Progress: Sync {
+    /// A route to the end `steps` long has been found, which is the longest so far
+    fn improved(&self, _steps: usize) {}
+
+    /// Where the search has got to, every so often and when it stops
+    fn checkpoint(&self, _checkpoint: &Checkpoint) {}
+}
+
+/// How often a search tells its [`Progress`] where it's got to.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+impl Progress for () {}
+
+/// State shared between the search threads.
+struct SharedSearch<'a> {
+    /// Length of the longest route to the end found so far by any thread
+    steps: AtomicUsize,
+    /// The route that's `steps` long, with its length, which can lag behind `steps` while the
+    /// thread that found a longer one is storing it
+    route: Mutex<BestRoute>,
+    progress: &'a dyn Progress,
+    deadline: Option<Instant>,
+    cancel: &'a Cancel,
+    /// Set once the deadline has passed or the search was cancelled, so every thread gives up
+    timed_out: AtomicBool,
+}
+
+impl SharedSearch<'_> {
+    fn offer(&self, steps: usize, route: impl FnOnce() -> Vec<usize>) {
+        if self.steps.fetch_max(steps, Ordering::Relaxed) < steps {
+            let mut best_route = self.route.lock().unwrap();
+            // Another thread may have found an even longer route in the meantime
+            if self.steps.load(Ordering::Relaxed) == steps {
+                *best_route = (steps, route());
+                self.progress.improved(steps);
+            }
+        }
+    }
+
+    fn out_of_time(&self) -> bool {
+        if self.timed_out.load(Ordering::Relaxed) {
+            return true;
+        }
+        let out_of_time =
+            self.cancel.is_cancelled() || self.deadline.is_some_and(|d| Instant::now() >= d);
+        if out_of_time {
+            self.timed_out.store(true, Ordering::Relaxed);
+        }
+        out_of_time
+    }
+}
+
+/// The longest route a search found, which may not be the longest there is if the search ran
+/// out of time.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Hike {
+    pub steps: usize,
+    pub proven_optimal: bool,
+}
+
+/// A path from the start to the end of the trail graph.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Route {
+    pub steps: usize,
+    /// Every node along the way, starting with the start and finishing with the end
+    nodes: Vec<usize>,
+}
+
+impl TrailGraph {
+    /// Graphviz description of the graph, with each edge labelled by its number of steps.
+    pub fn to_dot(&self) -> Dot {
+        let mut dot = Dot::digraph("trails");
+        for (id, Coord { x, y }) in self.nodes.iter().enumerate() {
+            let kind = match id {
+                _ if id == self.start => "start ",
+                _ if id == self.end => "end ",
+                _ => "",
+            };
+            dot.node(&id.to_string(), &[("label", &format!("{kind}({x},{y})"))]);
+        }
+        for (id, edges) in self.edges.iter().enumerate() {
+            for (next, steps) in edges {
+                dot.edge(
+                    &id.to_string(),
+                    &next.to_string(),
+                    &[("label", &steps.to_string())],
+                );
+            }
+        }
+        dot
+    }
+
+    /// Length of the longest path from start to end that doesn't visit any node twice.
+    #[cfg(test)]
+    fn longest_path(&self) -> Option<usize> {
+        self.longest_route().map(|route| route.steps)
+    }
+
+    /// The longest path from start to end that doesn't visit any node twice.
+    pub fn longest_route(&self) -> Option<Route> {
+        self.longest_route_with(&(), None, &Cancel::new(), None)
+            .ok()?
+            .0
+    }
+
+    /// The longest path from start to end that doesn't visit any node twice, or the longest
+    /// found by `deadline` or when `cancel` was cancelled, along with whether the search
+    /// finished. A search that was cut short can be picked up again by passing its last
+    /// checkpoint as `resume`.
+    ///
+    /// The first few levels of the search are expanded up front and the resulting partial
+    /// paths are finished off in parallel by depth-first search, with the visited nodes as a
+    /// bitset. Branches that couldn't beat the best path found so far by any thread, even
+    /// using the longest edge into every unvisited node, are cut off.
+    fn longest_route_with(
+        &self,
+        progress: &dyn Progress,
+        deadline: Option<Instant>,
+        cancel: &Cancel,
+        resume: Option<&Checkpoint>,
+    ) -> Result<(Option<Route>, bool), Day23Error> {
+        if self.start == self.end {
+            let route = Route {
+                steps: 0,
+                nodes: vec![self.start],
+            };
+            return Ok((Some(route), true));
+        }
+        let mut longest_into = vec![0; self.nodes.len()];
+        for &(next, steps) in self.edges.iter().flatten() {
+            longest_into[next] = longest_into[next].max(steps);
+        }
+
+        let (best_so_far, mut paths) = match resume {
+            Some(checkpoint) => self.restore(checkpoint, &longest_into)?,
+            None => (
+                (0, vec![]),
+                vec![(self.start_path(&longest_into), vec![self.start])],
+            ),
+        };
+        let best = SharedSearch {
+            steps: AtomicUsize::new(best_so_far.0),
+            route: Mutex::new(best_so_far),
+            progress,
+            deadline,
+            cancel,
+            timed_out: AtomicBool::new(false),
+        };
+        let enough_paths = rayon::current_num_threads() * 8;
+        while paths.len() < enough_paths {
+            let mut next_paths = vec![];
+            for (path, route) in &paths {
+                for &edge in &self.edges[path.node] {
+                    if let Some(next) = self.extend(path, route, edge, &longest_into, &best) {
+                        let route = [&route[..], &[next.node]].concat();
+                        next_paths.push((next, route));
+                    }
+                }
+            }
+            if next_paths.is_empty() {
+                break;
+            }
+            paths = next_paths;
+        }
+
+        // Partial paths are only taken out of the frontier once they've been searched all the
+        // way, so what's left can be picked up again if the search is cut short
+        let frontier: Mutex<Vec<Option<Vec<usize>>>> =
+            Mutex::new(paths.iter().map(|(_, route)| Some(route.clone())).collect());
+        let last_checkpoint = Mutex::new(Instant::now());
+        let checkpoint = |frontier: &[Option<Vec<usize>>]| {
+            let coords = |route: &[usize]| route.iter().map(|&node| self.nodes[node]).collect();
+            let (steps, route) = best.route.lock().unwrap().clone();
+            progress.checkpoint(&Checkpoint {
+                best: (steps > 0).then(|| (steps, coords(&route))),
+                frontier: frontier
+                    .iter()
+                    .flatten()
+                    .map(|route| coords(route))
+                    .collect(),
+            });
+        };
+        paths
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(i, (mut path, mut route))| {
+                self.search(&mut path, &mut route, &longest_into, &best);
+                if best.out_of_time() {
+                    return;
+                }
+                let due = {
+                    let mut frontier = frontier.lock().unwrap();
+                    frontier[i] = None;
+                    let mut last = last_checkpoint.lock().unwrap();
+                    (last.elapsed() >= CHECKPOINT_INTERVAL).then(|| {
+                        *last = Instant::now();
+                        frontier.clone()
+                    })
+                };
+                // Reported without holding the lock so the other threads can carry on. If
+                // another thread's later checkpoint gets reported first, this one only has
+                // more left to search, so picking it up again is just slower
+                if let Some(frontier) = due {
+                    checkpoint(&frontier);
+                }
+            });
+
+        let finished = !best.timed_out.load(Ordering::Relaxed);
+        checkpoint(&frontier.into_inner().unwrap());
+        let route = match best.route.into_inner().unwrap() {
+            (0, _) => None,
+            (steps, nodes) => Some(Route { steps, nodes }),
+        };
+        Ok((route, finished))
+    }
+
+    /// The path that's just the start, with nowhere visited yet.
+    fn start_path(&self, longest_into: &[usize]) -> PartialPath {
+        let mut visited = NodeSet::new(self.nodes.len());
+        visited.insert(self.start);
+        PartialPath {
+            node: self.start,
+            visited,
+            steps: 0,
+            remaining: longest_into.iter().sum::<usize>() - longest_into[self.start],
+        }
+    }
+
+    /// The best route and partial paths of `checkpoint` as nodes of the graph, with each path
+    /// ready to carry on searching from.
+    fn restore(
+        &self,
+        checkpoint: &Checkpoint,
+        longest_into: &[usize],
+    ) -> Result<(BestRoute, Vec<SearchStart>), Day23Error> {
+        let node_ids: HashMap<Coord, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(id, &c)| (c, id))
+            .collect();
+        let follow = |route: &[Coord]| -> Result<SearchStart, Day23Error> {
+            let off_trail = || Day23Error::OffTrail(route_text(route));
+            let nodes: Vec<usize> = route
+                .iter()
+                .map(|c| node_ids.get(c).copied())
+                .collect::<Option<_>>()
+                .filter(|nodes: &Vec<usize>| nodes.first() == Some(&self.start))
+                .ok_or_else(off_trail)?;
+            let mut path = self.start_path(longest_into);
+            for &next in &nodes[1..] {
+                let &(_, steps) = self.edges[path.node]
+                    .iter()
+                    .find(|&&(to, _)| to == next)
+                    .filter(|_| !path.visited.contains(next))
+                    .ok_or_else(off_trail)?;
+                path.visited.insert(next);
+                path.node = next;
+                path.steps += steps;
+                path.remaining -= longest_into[next];
+            }
+            Ok((path, nodes))
+        };
+        let best = match &checkpoint.best {
+            Some((steps, route)) => {
+                let (path, nodes) = follow(route)?;
+                if path.node != self.end || path.steps != *steps {
+                    return Err(Day23Error::WrongBest(route_text(route)));
+                }
+                (path.steps, nodes)
+            }
+            None => (0, vec![]),
+        };
+        let paths = checkpoint
+            .frontier
+            .iter()
+            .map(|route| follow(route))
+            .collect::<Result<_, _>>()?;
+        Ok((best, paths))
+    }
+
+    /// `path` (which went through the nodes of `route`) continued along `edge`, unless that
+    /// revisits a node, reaches the end (in which case it's offered to `best`), or can't beat
+    /// `best`.
+    fn extend(
+        &self,
+        path: &PartialPath,
+        route: &[usize],
+        (next, steps): (usize, usize),
+        longest_into: &[usize],
+        best: &SharedSearch,
+    ) -> Option<PartialPath> {
+        if path.visited.contains(next) {
+            return None;
+        }
+        let mut path = path.clone();
+        self.step(&mut path, route, (next, steps), longest_into, best)
+            .then_some(path)
+    }
+
+    /// Moves `path` (which went through the nodes of `route`) along `edge` to a node it hasn't
+    /// visited, returning whether it's worth searching on from there. It isn't if it's reached
+    /// the end (in which case it's offered to `best`) or can't beat `best`.
+    fn step(
+        &self,
+        path: &mut PartialPath,
+        route: &[usize],
+        (next, steps): (usize, usize),
+        longest_into: &[usize],
+        best: &SharedSearch,
+    ) -> bool {
+        path.visited.insert(next);
+        path.node = next;
+        path.steps += steps;
+        path.remaining -= longest_into[next];
+        if next == self.end {
+            best.offer(path.steps, || [route, &[self.end]].concat());
+            return false;
+        }
+        path.steps + path.remaining > best.steps.load(Ordering::Relaxed)
+    }
+
+    /// Searches on from `path`, which is back how it started when this returns.
+    fn search(
+        &self,
+        path: &mut PartialPath,
+        route: &mut Vec<usize>,
+        longest_into: &[usize],
+        best: &SharedSearch,
+    ) {
+        if best.out_of_time() {
+            return;
+        }
+        let (here, steps, remaining) = (path.node, path.steps, path.remaining);
+        for &edge in &self.edges[here] {
+            let next = edge.0;
+            if path.visited.contains(next) {
+                continue;
+            }
+            if self.step(path, route, edge, longest_into, best) {
+                route.push(next);
+                self.search(path, route, longest_into, best);
+                route.pop();
+            }
+            path.visited.remove(next);
+            (path.node, path.steps, path.remaining) = (here, steps, remaining);
+        }
+    }
+
+    /// Every tile along a route through the graph, starting from the start.
+    pub fn route_tiles(&self, route: &Route) -> Vec<Coord> {
+        let mut tiles = vec![self.nodes[route.nodes[0]]];
+        for pair in route.nodes.windows(2) {
+            tiles.extend(&self.corridors[&(pair[0], pair[1])]);
+        }
+        tiles
+    }
+}
+
+#[test]
+fn test_many_junctions() {
+    // A corridor straight down with a dead end off the side of every other row, each making a
+    // junction
+    let rows: Vec<&str> = (0..=140)
+        .map(|y| if y % 2 == 1 { "#..#" } else { "#.##" })
+        .collect();
+    let maze: Maze = rows.join("\n").parse().unwrap();
+    let graph = trail_graph(&maze, SlopeRule::Ignored);
+    assert_eq!(graph.nodes.len(), 72);
+    assert_eq!(graph.longest_path(), Some(140));
+    let hike = part2(&maze, &(), None, None);
+    assert_eq!(hike.map(|hike| hike.steps), Ok(140));
+}
+
+#[test]
+fn test_trail_graph() {
+    let maze: Maze = TEST_STR.parse().unwrap();
+    let graph = trail_graph(&maze, SlopeRule::Directed);
+    // Start, end, and the 7 junctions of the example
+    assert_eq!(graph.nodes.len(), 9);
+    let [(first_junction, 15)] = graph.edges[graph.start][..] else {
+        panic!("start should lead to one junction 15 steps away");
+    };
+    assert_eq!(graph.nodes[first_junction], Coord::new(3, 5));
+    assert_eq!(graph.longest_path(), Some(94));
+    assert_eq!(graph.longest_path(), maze.max_path(SlopeRule::Directed));
+
+    for slopes in SlopeRule::value_variants() {
+        let graph = trail_graph(&maze, *slopes);
+        assert_eq!(graph.longest_path(), maze.max_path(*slopes), "{slopes:?}");
+    }
+    let graph = trail_graph(&maze, SlopeRule::Directed);
+
+    let dot = graph.to_dot().to_string();
+    assert!(dot.contains("    0 [label=\"start (1,0)\"];\n"));
+    assert!(dot.contains("    1 [label=\"end (21,22)\"];\n"));
+    assert!(dot.contains(&format!("    0 -> {first_junction} [label=\"15\"];\n")));
+
+    let graph = trail_graph(&maze, SlopeRule::Ignored);
+    assert_eq!(graph.longest_path(), Some(154));
+    let route = graph.longest_route().unwrap();
+    assert_eq!(route.nodes.first(), Some(&graph.start));
+    assert_eq!(route.nodes.last(), Some(&graph.end));
+    let tiles = graph.route_tiles(&route);
+    assert_eq!(tiles.len(), 155);
+    let rendered = maze.render_path(&tiles.into_iter().collect()).to_string();
+    assert_eq!(rendered.matches('O').count(), 154);
+    assert!(rendered.starts_with("#S#####"));
+    let edges_into_start = graph.edges.iter().flatten().filter(|&&(n, _)| n == 0);
+    assert_eq!(edges_into_start.count(), 1);
+}
+
+impl Index<Coord> for Maze {
+    type Output = char;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        &self.maze[index]
+    }
+}
+
+pub fn part1(maze: &Maze) -> Result<usize, Day23Error> {
+    maze.max_path(SlopeRule::Directed)
+        .ok_or(Day23Error::NoRoute)
+}
+
+#[test]
+fn test_tiny() {
+    let part1 = |input: &str| part1(&input.parse().unwrap());
+    assert_eq!(part1("#.#\n#.#\n#.#"), Ok(2));
+    assert_eq!(part1("#.##\n#...\n#..#\n##.#"), Ok(4));
+    assert_eq!(part1("#.#\n###\n#.#"), Err(Day23Error::NoRoute));
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&TEST_STR.parse().unwrap()), Ok(94));
+}
+
+/// Longest hike ignoring slopes, giving up on proving it's the longest at `deadline`. The
+/// search carries on from `resume` if it's given a checkpoint of an earlier one.
+pub fn part2(
+    maze: &Maze,
+    progress: &dyn Progress,
+    deadline: Option<Instant>,
+    resume: Option<&Checkpoint>,
+) -> Result<Hike, Day23Error> {
+    longest_hike(maze, progress, deadline, &Cancel::new(), resume)
+}
+
+fn longest_hike(
+    maze: &Maze,
+    progress: &dyn Progress,
+    deadline: Option<Instant>,
+    cancel: &Cancel,
+    resume: Option<&Checkpoint>,
+) -> Result<Hike, Day23Error> {
+    let (route, finished) = trail_graph(maze, SlopeRule::Ignored)
+        .longest_route_with(progress, deadline, cancel, resume)?;
+    let route = route.ok_or(if finished {
+        Day23Error::NoRoute
+    } else {
+        Day23Error::TimedOut
+    })?;
+    Ok(Hike {
+        steps: route.steps,
+        proven_optimal: finished,
+    })
+}
+
+impl Solver for Maze {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input.parse().map_err(SolveError::failed)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(part1(self).map_err(SolveError::failed)?.to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        self.solve_cancellable(2, &Cancel::new())
+    }
+
+    fn solve_cancellable(&self, part: u8, cancel: &Cancel) -> Result<String, SolveError> {
+        if part == 1 {
+            return self.part1();
+        }
+        let deadline = self.time_limit.map(|limit| Instant::now() + limit);
+        let hike = match longest_hike(self, &(), deadline, cancel, None) {
+            Ok(hike) => hike,
+            Err(Day23Error::TimedOut) => return Err(SolveError::TimedOut { progress: None }),
+            Err(e) => return Err(SolveError::failed(e)),
+        };
+        if !hike.proven_optimal {
+            return Err(SolveError::TimedOut {
+                progress: Some(format!("a route {} steps long", hike.steps)),
+            });
+        }
+        Ok(hike.steps.to_string())
+    }
+
+    fn set_time_limit(&mut self, limit: Duration) {
+        self.time_limit = Some(limit);
+    }
+}
+
+#[test]
+fn test_part2() {
+    let hike = part2(&TEST_STR.parse().unwrap(), &(), None, None);
+    assert_eq!(
+        hike,
+        Ok(Hike {
+            steps: 154,
+            proven_optimal: true
+        })
+    );
+}
+
+#[cfg(test)]
+#[derive(Default)]
+struct Recorder {
+    improvements: Mutex<Vec<usize>>,
+    checkpoints: Mutex<Vec<Checkpoint>>,
+}
+
+#[cfg(test)]
+impl Progress for Recorder {
+    fn improved(&self, steps: usize) {
+        self.improvements.lock().unwrap().push(steps);
+    }
+    fn checkpoint(&self, checkpoint: &Checkpoint) {
+        let frontier = &checkpoint.frontier;
+        assert!(frontier.iter().all(|route| route[0] == Coord::new(1, 0)));
+        // What's written can be read back
+        assert_eq!(
+            &checkpoint.to_string().parse::<Checkpoint>().unwrap(),
+            checkpoint
+        );
+        self.checkpoints.lock().unwrap().push(checkpoint.clone());
+    }
+}
+
+#[test]
+fn test_progress() {
+    let maze: Maze = TEST_STR.parse().unwrap();
+    let recorder = Recorder::default();
+    let hike = part2(&maze, &recorder, None, None).unwrap();
+    let improvements = recorder.improvements.into_inner().unwrap();
+    assert!(improvements.is_sorted());
+    assert_eq!(improvements.last(), Some(&hike.steps));
+    let last = recorder.checkpoints.into_inner().unwrap().pop().unwrap();
+    assert_eq!(last.frontier.len(), 0);
+    assert_eq!(last.best.map(|(steps, _)| steps), Some(154));
+
+    // Out of time before the depth-first search starts, so only the routes found expanding
+    // the first few levels are, if any
+    let recorder = Recorder::default();
+    let hike = part2(&maze, &recorder, Some(Instant::now()), None);
+    assert!(
+        matches!(
+            hike,
+            Ok(Hike {
+                proven_optimal: false,
+                ..
+            }) | Err(Day23Error::TimedOut)
+        ),
+        "{hike:?}"
+    );
+    let checkpoints = recorder.checkpoints.into_inner().unwrap();
+    assert!(checkpoints.last().is_some_and(|c| !c.frontier.is_empty()));
+}
+
+#[test]
+fn test_resume() {
+    let maze: Maze = TEST_STR.parse().unwrap();
+    let finished = Hike {
+        steps: 154,
+        proven_optimal: true,
+    };
+
+    // Picking up a search that was cut off before it started finishes it
+    let recorder = Recorder::default();
+    let _ = part2(&maze, &recorder, Some(Instant::now()), None);
+    let cut_off = recorder.checkpoints.into_inner().unwrap().pop().unwrap();
+    assert_eq!(part2(&maze, &(), None, Some(&cut_off)), Ok(finished));
+
+    // Picking up a finished one has nothing left to search
+    let recorder = Recorder::default();
+    part2(&maze, &recorder, None, None).unwrap();
+    let done = recorder.checkpoints.into_inner().unwrap().pop().unwrap();
+    let recorder = Recorder::default();
+    let hike = part2(&maze, &recorder, None, Some(&done));
+    assert_eq!(hike.map(|hike| hike.steps), Ok(154));
+    assert!(recorder.improvements.into_inner().unwrap().is_empty());
+
+    let checkpoint: Checkpoint = "best 3 1,0 3,5\n1,0 3,5\n".parse().unwrap();
+    assert_eq!(
+        part2(&maze, &(), None, Some(&checkpoint)),
+        Err(Day23Error::WrongBest("1,0 3,5".to_string()))
+    );
+    let checkpoint: Checkpoint = "1,0 3,5 1,0\n".parse().unwrap();
+    assert_eq!(
+        part2(&maze, &(), None, Some(&checkpoint)),
+        Err(Day23Error::OffTrail("1,0 3,5 1,0".to_string()))
+    );
+    assert_eq!(
+        "best x 1,0".parse::<Checkpoint>(),
+        Err(Day23Error::MalformedCheckpoint("best x 1,0".to_string()))
+    );
+    assert_eq!(
+        "1,0 3;5".parse::<Checkpoint>(),
+        Err(Day23Error::InvalidCoord("3;5".to_string()))
+    );
+}
+
+#[test]
+fn test_time_limit() {
+    let mut maze = Maze::parse(TEST_STR).unwrap();
+    maze.set_time_limit(Duration::from_secs(60));
+    assert_eq!(maze.part2(), Ok("154".to_string()));
+    maze.set_time_limit(Duration::ZERO);
+    assert!(matches!(maze.part2(), Err(SolveError::TimedOut { .. })));
+
+    let maze = Maze::parse(TEST_STR).unwrap();
+    let cancel = Cancel::new();
+    cancel.cancel();
+    assert!(matches!(
+        maze.solve_cancellable(2, &cancel),
+        Err(SolveError::TimedOut { .. })
+    ));
+}
+
+#[test]
+fn test_slope_rules() {
+    let maze: Maze = "#.###\n#.>.#\n#.#.#\n#...#\n###.#".parse().unwrap();
+    let steps = |from: Coord, slopes| maze.possible_next_steps(&from, slopes);
+    assert_eq!(
+        steps(Coord::new(2, 1), SlopeRule::Directed),
+        [Coord::new(3, 1)]
+    );
+    assert_eq!(
+        steps(Coord::new(2, 1), SlopeRule::Ignored),
+        [Coord::new(1, 1), Coord::new(3, 1)]
+    );
+    assert_eq!(
+        steps(Coord::new(2, 1), SlopeRule::UphillForbidden),
+        [Coord::new(3, 1)]
+    );
+    // Stepping onto the slope against the way it points
+    assert_eq!(
+        steps(Coord::new(3, 1), SlopeRule::Directed),
+        [Coord::new(3, 2), Coord::new(2, 1)]
+    );
+    assert_eq!(
+        steps(Coord::new(3, 1), SlopeRule::UphillForbidden),
+        [Coord::new(3, 2)]
+    );
+
+    let maze: Maze = TEST_STR.parse().unwrap();
+    let longest = SlopeRule::value_variants()
+        .iter()
+        .map(|&s| maze.max_path(s));
+    assert_eq!(longest.collect::<Vec<_>>(), [Some(94), Some(154), Some(94)]);
+}
+
+#[test]
+fn test_endpoints() {
+    let maze: Maze = "##.#\n#..#\n#.##".parse().unwrap();
+    assert_eq!(
+        (maze.start(), maze.end()),
+        (Coord::new(2, 0), Coord::new(1, 2))
+    );
+    assert_eq!(part1(&maze), Ok(3));
+
+    let maze = maze
+        .with_endpoints(Some(parse_coord("1,1").unwrap()), None)
+        .unwrap();
+    assert_eq!(part1(&maze), Ok(1));
+    assert_eq!(part2(&maze, &(), None, None).map(|hike| hike.steps), Ok(1));
+    assert_eq!(
+        maze.with_endpoints(None, Some(Coord::new(0, 0))).err(),
+        Some(Day23Error::NotOpen { x: 0, y: 0 })
+    );
+
+    assert_eq!(
+        "###\n#.#\n#.#".parse::<Maze>().err(),
+        Some(Day23Error::NoEntrance)
+    );
+    assert_eq!(
+        "#.#\n#.#\n###".parse::<Maze>().err(),
+        Some(Day23Error::NoExit)
+    );
+    assert_eq!(
+        parse_coord("1;2"),
+        Err(Day23Error::InvalidCoord("1;2".to_string()))
+    );
+}
+
+#[cfg(test)]
+const TEST_STR: &str = aoc_core::examples::get(23, 1);
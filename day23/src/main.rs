@@ -1,260 +1,143 @@
 use std::{
-    collections::{HashSet, VecDeque},
-    ops::Index,
-    str::FromStr,
+    fmt::Display,
+    path::PathBuf,
+    time::{Duration, Instant},
 };
 
-struct Maze {
-    maze: Vec<Vec<u8>>,
-}
-
-impl FromStr for Maze {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            maze: s.trim().lines().map(|line| line.trim().into()).collect(),
-        })
-    }
-}
+use aoc_core::CommonArgs;
+use clap::Parser;
+use day23::{
+    parse_coord, part1, part2, trail_graph, Checkpoint, Coord, Day23Error, Maze, Progress,
+    SlopeRule,
+};
 
-struct FoundTiles {
-    longest_path_to: Vec<Vec<Vec<HashSet<Coord>>>>,
+/// A number of seconds like `2.5`, which can't be negative.
+fn parse_secs(s: &str) -> Result<Duration, String> {
+    s.parse()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("expected a number of seconds but got {s:?}"))
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct Coord(isize, isize);
-
-impl Maze {
-    fn width(&self) -> isize {
-        self.maze[0].len() as isize
-    }
-    fn height(&self) -> isize {
-        self.maze.len() as isize
-    }
-
-    fn is_blocked(&self, coord: &Coord) -> bool {
-        if coord.0 < 0 || coord.0 >= self.width() || coord.1 < 0 || coord.1 >= self.height() {
-            return true;
-        }
-        match self[*coord] {
-            b'#' => true,
-            _ => false,
-        }
-    }
-
-    fn possible_next_steps(&self, coord: &Coord) -> Vec<Coord> {
-        let left = Coord(coord.0 - 1, coord.1);
-        let right = Coord(coord.0 + 1, coord.1);
-        let above = Coord(coord.0, coord.1 - 1);
-        let below = Coord(coord.0, coord.1 + 1);
-        let steps = match self[*coord] {
-            b'>' => vec![right],
-            b'<' => vec![left],
-            b'^' => vec![above],
-            b'v' => vec![below],
-            b'.' => vec![left, right, above, below],
-            _ => unreachable!(),
-        };
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
 
-        steps
-            .into_iter()
-            .filter(|step| !self.is_blocked(step))
-            .collect()
-    }
+    /// Print the trail graph in Graphviz format instead of solving
+    #[arg(long)]
+    dot: bool,
 
-    fn print_found_tiles(&self, found_tiles: &FoundTiles) {
-        for y in 0..self.height() as usize {
-            let mut boundary_line = String::new();
-            let mut numbered_line = String::new();
-            for x in 0..self.width() as usize {
-                let ch = std::char::from_u32(self.maze[y][x] as u32).unwrap();
-                let five_ch = format!("{ch}{ch}{ch}{ch}{ch}");
-                if self.maze[y][x] == b'#' {
-                    boundary_line.push_str(&five_ch);
-                    numbered_line.push_str(&five_ch);
-                // } else if let Some(ref path) = found_tiles.longest_path_to[y][x] {
-                //     let path_len_str = format!("{}", path.len());
-                //     // let last_char = &path_len_str[path_len_str.len() - 1..];
-                //     numbered_line.push_str(&format!("{ch}{path_len_str:.^3.3}{ch}"));
-                //     boundary_line.push_str(&five_ch);
-                } else {
-                    numbered_line.push_str(&format!("{ch}xxx{ch}"));
-                    boundary_line.push_str(&five_ch);
-                }
-            }
-            println!("{boundary_line}\n{numbered_line}\n{boundary_line}");
-        }
-    }
-    fn print_path(&self, found_tiles: &FoundTiles, longest_path: &HashSet<Coord>) {
-        println!();
-        for y in 0..self.height() {
-            let mut boundary_line = String::new();
-            let mut numbered_line = String::new();
-            for x in 0..self.width() {
-                let coord = Coord(x, y);
+    /// Print the maze with the longest route marked instead of solving
+    #[arg(long)]
+    render: bool,
 
-                let ch = std::char::from_u32(self[coord] as u32).unwrap();
-                let five_ch = format!("{ch}{ch}{ch}{ch}{ch}");
-                if self[coord] == b'#' {
-                    assert!(!longest_path.contains(&coord));
-                    boundary_line.push_str(&five_ch);
-                    numbered_line.push_str(&five_ch);
-                } else if longest_path.contains(&coord) {
-                    // let path = found_tiles.longest_path_to[y as usize][x as usize]
-                    //     .as_ref()
-                    //     .unwrap();
-                    // let path_len_str = format!("{}", path.len());
+    /// How to treat slopes for --dot and --render
+    #[arg(long, value_enum, default_value_t = SlopeRule::Directed)]
+    slopes: SlopeRule,
 
-                    // numbered_line.push_str(&format!("{ch}{path_len_str:.^3.3}{ch}"));
-                    numbered_line.push_str(&format!("{ch}{ch}O{ch}{ch}"));
-                    boundary_line.push_str(&five_ch);
-                } else {
-                    boundary_line.push_str(&five_ch);
-                    numbered_line.push_str(&five_ch);
-                }
-            }
-            println!("{boundary_line}\n{numbered_line}\n{boundary_line}");
-        }
-        println!();
-    }
-
-    fn max_path(&self) -> usize {
-        let mut found_tiles = FoundTiles {
-            longest_path_to: vec![vec![vec![]; self.width() as usize]; self.height() as usize],
-        };
+    /// Tile to start from instead of the open tile in the top row
+    #[arg(long, value_name = "X,Y", value_parser = parse_coord)]
+    start: Option<Coord>,
 
-        let start_coord = Coord(1, 0);
-        let mut to_examine: VecDeque<(Coord, usize)> = VecDeque::from([(start_coord, 0)]);
-        found_tiles.longest_path_to[0][1] = vec![HashSet::new()];
+    /// Tile to finish at instead of the open tile in the bottom row
+    #[arg(long, value_name = "X,Y", value_parser = parse_coord)]
+    end: Option<Coord>,
 
-        while let Some((here, path_idx)) = to_examine.pop_front() {
-            for next in self.possible_next_steps(&here) {
-                let path_to_here =
-                    &found_tiles.longest_path_to[here.1 as usize][here.0 as usize][path_idx];
-                if path_to_here.contains(&next) {
-                    // Already saw this coordinate
-                    continue;
-                }
-                // for path_to_here in
-                //     found_tiles.longest_path_to[here.1 as usize][here.0 as usize].iter().cloned()
-                // {
+    /// Print each longer route found during the part 2 search
+    #[arg(long)]
+    progress: bool,
 
-                // if let Some(existing) = &longest_path_to[next.1 as usize][next.0 as usize] {
-                //     if existing.len() >= path_to_here.len() + 1 {
-                //         continue;
-                //     }
-                // }
+    /// Give up on the part 2 search after this many seconds and report the longest route found
+    #[arg(long, value_name = "SECS", value_parser = parse_secs, allow_negative_numbers = true)]
+    time_limit: Option<Duration>,
 
-                let mut path_to_next = path_to_here.clone();
-                path_to_next.insert(here.clone());
+    /// Keep this file updated with the longest route so far and the partial paths the part 2
+    /// search still has to finish
+    #[arg(long, value_name = "FILE")]
+    checkpoint: Option<PathBuf>,
 
-                // self.print_path(&found_tiles, &path_to_next);
+    /// Carry on the part 2 search from a file written by --checkpoint
+    #[arg(long, value_name = "FILE")]
+    resume: Option<PathBuf>,
+}
 
-                let longest_path_to_next =
-                    &mut found_tiles.longest_path_to[next.1 as usize][next.0 as usize];
+/// Reports part 2's search progress on stderr and in a checkpoint file.
+struct Reporter {
+    print_improvements: bool,
+    checkpoint: Option<PathBuf>,
+}
 
-                to_examine.push_back((next, longest_path_to_next.len()));
-                longest_path_to_next.push(path_to_next);
-                // self.print_found_tiles(&found_tiles);
-            }
+impl Progress for Reporter {
+    fn improved(&self, steps: usize) {
+        if self.print_improvements {
+            eprintln!("Longest so far: {steps}");
         }
-
-        // println!();
-
-        found_tiles.longest_path_to[self.height() as usize - 1][self.width() as usize - 2]
-            .iter()
-            .map(|p| p.len())
-            .max()
-            .unwrap()
     }
 
-    // fn max_dist
-}
-
-impl Index<Coord> for Maze {
-    type Output = u8;
-
-    fn index(&self, index: Coord) -> &Self::Output {
-        &self.maze[index.1 as usize][index.0 as usize]
+    fn checkpoint(&self, checkpoint: &Checkpoint) {
+        let Some(path) = &self.checkpoint else {
+            return;
+        };
+        // The search is worth finishing even if it can't be picked up again
+        if let Err(e) = std::fs::write(path, checkpoint.to_string()) {
+            eprintln!("couldn't write checkpoint {}: {e}", path.display());
+        }
     }
 }
 
-fn part1(input: &str) -> usize {
-    input.parse::<Maze>().unwrap().max_path()
-}
-
-// #[test]
-// fn test_tiny() {
-//     assert_eq!(part2("#.#\n#.#\n#.#"), 2);
-//     assert_eq!(part2("#.#\n#..\n#.#"), 2);
-//     assert_eq!(
-//         part2(
-//             r"
-//     #.##
-//     ....
-//     .#..
-//     .#.#"
-//         ),
-//         5
-//     );
-// }
-
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_STR), 94);
-}
-
-fn part2(input: &str) -> usize {
-    input
-        .replace(">", ".")
-        .replace("<", ".")
-        .replace("v", ".")
-        .replace("^", ".")
-        .parse::<Maze>()
-        .unwrap()
-        .max_path()
-}
-
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_STR), 154);
+fn exit_with_error(e: impl Display) -> ! {
+    eprintln!("error: {e}");
+    std::process::exit(1);
 }
 
 fn main() {
-//     part2(
-//         r"
-// #.##
-// ....
-// .#..
-// .#.#",
-//     );
-
-    let input = &std::fs::read_to_string("input.txt").expect("input.txt should exist");
-    println!("Part 1: {}", part1(input));
-    // println!("Part 2: {}", part2(input));
+    let args = Args::parse();
+    aoc_core::init_logging(args.common.verbose);
+    let input = &args.common.input.read_or_exit();
+    let maze = input
+        .parse::<Maze>()
+        .and_then(|maze| maze.with_endpoints(args.start, args.end))
+        .unwrap_or_else(|e| exit_with_error(e));
+    if args.dot || args.render {
+        let graph = trail_graph(&maze, args.slopes);
+        if args.dot {
+            print!("{}", graph.to_dot());
+        }
+        if args.render {
+            let route = graph
+                .longest_route()
+                .unwrap_or_else(|| exit_with_error(Day23Error::NoRoute));
+            let tiles = graph.route_tiles(&route).into_iter().collect();
+            print!("{}", maze.render_path(&tiles));
+            println!("{} steps", route.steps);
+        }
+        return;
+    }
+    let reporter = Reporter {
+        print_improvements: args.progress,
+        checkpoint: args.checkpoint,
+    };
+    let resume = args.resume.map(|path| {
+        std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| exit_with_error(format!("couldn't read {}: {e}", path.display())))
+            .parse::<Checkpoint>()
+            .unwrap_or_else(|e| exit_with_error(e))
+    });
+    let deadline = args.time_limit.map(|limit| Instant::now() + limit);
+    let answers = part1(&maze).and_then(|p1| {
+        let p2 = part2(&maze, &reporter, deadline, resume.as_ref())?;
+        Ok((p1, p2))
+    });
+    match answers {
+        Ok((p1, p2)) => {
+            println!("Part 1: {p1}");
+            if p2.proven_optimal {
+                println!("Part 2: {}", p2.steps);
+            } else {
+                println!("Part 2: {} (not proven optimal)", p2.steps);
+            }
+        }
+        Err(e) => exit_with_error(e),
+    }
 }
-
-const TEST_STR: &str = r"#.#####################
-#.......#########...###
-#######.#########.#.###
-###.....#.>.>.###.#.###
-###v#####.#v#.###.#.###
-###.>...#.#.#.....#...#
-###v###.#.#.#########.#
-###...#.#.#.......#...#
-#####.#.#.#######.#.###
-#.....#.#.#.......#...#
-#.#####.#.#.#########v#
-#.#...#...#...###...>.#
-#.#.#v#######v###.###v#
-#...#.>.#...>.>.#.###.#
-#####v#.#.###v#.#.###.#
-#.....#...#...#.#.#...#
-#.#########.###.#.#.###
-#...###...#...#...#.###
-###.###.#.###v#####v###
-#...#...#.#.>.>.#.>.###
-#.###.###.#.###.#.#v###
-#.....###...###...#...#
-#####################.#";
@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day24::count_xy_intersections_in_test_zone;
+
+fn bench_xy_intersections(c: &mut Criterion) {
+    let input = std::fs::read_to_string("input.txt").unwrap_or_else(|_| random_input(300));
+
+    // One thread stands in for the sequential pair checks, to compare against all of them.
+    let sequential = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("xy_intersections");
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            sequential.install(|| {
+                count_xy_intersections_in_test_zone(black_box(&input), 2e14, 4e14).unwrap()
+            })
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| count_xy_intersections_in_test_zone(black_box(&input), 2e14, 4e14).unwrap())
+    });
+    group.finish();
+}
+
+/// Hailstones shaped like the puzzle input's, for when there's no input.txt.
+fn random_input(len: usize) -> String {
+    // A cheap LCG so the benchmark is deterministic without pulling in rand.
+    let mut seed: u64 = 0x2023_1224;
+    let mut next = |range: i64| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (seed >> 16) as i64 % range
+    };
+    (0..len)
+        .map(|_| {
+            let [px, py, pz] = [(); 3].map(|_| 1e14 as i64 + next(4e14 as i64));
+            let [vx, vy, vz] = [(); 3].map(|_| next(801) - 400);
+            format!("{px}, {py}, {pz} @ {vx}, {vy}, {vz}\n")
+        })
+        .collect()
+}
+
+criterion_group!(benches, bench_xy_intersections);
+criterion_main!(benches);
@@ -0,0 +1,717 @@
+use std::str::FromStr;
+
+use aoc_core::{parse_lines, AocError, SolveError, Solver};
+use clap::ValueEnum;
+use num::{BigInt, BigRational, Zero};
+use rayon::prelude::*;
+use tracing::trace;
+
+/// Where a hailstone is now and how far it moves each nanosecond.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub px: i64,
+    pub py: i64,
+    pub pz: i64,
+    pub vx: i64,
+    pub vy: i64,
+    pub vz: i64,
+}
+
+impl FromStr for Hailstone {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || AocError::malformed(s, "px, py, pz @ vx, vy, vz");
+        let numbers: Vec<i64> = s
+            .split([',', '@'])
+            .map(|n| n.trim().parse().map_err(|_| malformed()))
+            .collect::<Result<_, _>>()?;
+        if numbers.len() != 6 || s.matches('@').count() != 1 {
+            return Err(malformed());
+        }
+        Ok(Hailstone {
+            px: numbers[0],
+            py: numbers[1],
+            pz: numbers[2],
+            vx: numbers[3],
+            vy: numbers[4],
+            vz: numbers[5],
+        })
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    let malformed = |line, s| AocError::malformed(s, "px, py, pz @ vx, vy, vz").on_line(line);
+    assert_eq!(
+        parse_hailstones("19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1"),
+        Err(malformed(2, "18, 19, 22 @ -1, -1"))
+    );
+    assert_eq!(
+        parse_hailstones("19, 13, 30, -2, 1, -2"),
+        Err(malformed(1, "19, 13, 30, -2, 1, -2"))
+    );
+    assert_eq!(
+        parse_hailstones("19, 13, x @ -2, 1, -2"),
+        Err(malformed(1, "19, 13, x @ -2, 1, -2"))
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_round_trip(
+        position in proptest::array::uniform3(-(1i64 << 50)..1 << 50),
+        velocity in proptest::array::uniform3(-1000..1000i64),
+    ) {
+        let [px, py, pz] = position;
+        let [vx, vy, vz] = velocity;
+        let hailstone: Hailstone = format!("{px}, {py},  {pz} @ {vx}, {vy}, {vz}")
+            .parse()
+            .unwrap();
+        proptest::prop_assert_eq!(hailstone.position(), position);
+        proptest::prop_assert_eq!(hailstone.velocity(), velocity);
+    }
+
+    #[test]
+    fn test_parse_never_panics(
+        input in "((-?[0-9]{1,20}, ){2,3}-?[0-9]{1,20} ?@? (-?[0-9]{1,20},? ){2,4}\n){0,4}|\\PC*",
+    ) {
+        let _ = parse_hailstones(&input);
+    }
+}
+
+impl Hailstone {
+    pub fn position(&self) -> [i64; 3] {
+        [self.px, self.py, self.pz]
+    }
+
+    pub fn velocity(&self) -> [i64; 3] {
+        [self.vx, self.vy, self.vz]
+    }
+
+    /// Where the hailstone is `t` nanoseconds from now.
+    pub fn at(&self, t: i64) -> [i64; 3] {
+        let (position, velocity) = (self.position(), self.velocity());
+        std::array::from_fn(|i| position[i] + velocity[i] * t)
+    }
+
+    /// Where the two paths come closest to each other, or None if they're parallel. This looks
+    /// at the paths as lines, so the times can be different for each hailstone and in the past.
+    /// A hailstone that isn't moving is there at whatever time the other one gets closest.
+    pub fn closest_approach(&self, other: &Hailstone) -> Option<ClosestApproach> {
+        let dot = |a: [i64; 3], b: [i64; 3]| -> i128 {
+            a.iter()
+                .zip(b)
+                .map(|(&a, b)| i128::from(a) * i128::from(b))
+                .sum()
+        };
+        let (v1, v2) = (self.velocity(), other.velocity());
+        let offset: [i64; 3] = std::array::from_fn(|i| self.position()[i] - other.position()[i]);
+        let (a, b, c) = (dot(v1, v1), dot(v1, v2), dot(v2, v2));
+        let (d, e) = (dot(v1, offset), dot(v2, offset));
+        let denominator = a * c - b * b;
+        let (t1, t2) = match (a, c) {
+            (0, 0) => (0.0, 0.0),
+            (0, _) => (e as f64 / c as f64, e as f64 / c as f64),
+            (_, 0) => (-d as f64 / a as f64, -d as f64 / a as f64),
+            _ if denominator == 0 => return None,
+            _ => (
+                (b * e - c * d) as f64 / denominator as f64,
+                (a * e - b * d) as f64 / denominator as f64,
+            ),
+        };
+
+        let at = |hailstone: &Hailstone, t: f64| -> [f64; 3] {
+            let (position, velocity) = (hailstone.position(), hailstone.velocity());
+            std::array::from_fn(|i| position[i] as f64 + velocity[i] as f64 * t)
+        };
+        let (p1, p2) = (at(self, t1), at(other, t2));
+        let distance = p1
+            .iter()
+            .zip(p2)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        Some(ClosestApproach {
+            distance,
+            times: (t1, t2),
+        })
+    }
+
+    /// Where the two paths cross in x and y, ignoring z, along with how long it takes this
+    /// hailstone and then the other to get there, or None if the paths are parallel. The times
+    /// are negative for a crossing in the past. Paths along the same line don't cross at any one
+    /// point, so they're None too; see `xy_paths_coincide`. A hailstone that isn't moving in x
+    /// and y is there at the same time as the other.
+    pub fn xy_intersection_time(&self, other: &Hailstone) -> Option<(f64, f64, (f64, f64))> {
+        if self.is_xy_still() || other.is_xy_still() {
+            let (still, moving) = if self.is_xy_still() {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            let t = moving.xy_time_at(still.px, still.py)?;
+            return Some((t, t, (still.px as f64, still.py as f64)));
+        }
+
+        // Solve self.p + self.v * t1 = other.p + other.v * t2 with Cramer's rule, exactly up to
+        // the last division
+        let [v1x, v1y] = [self.vx, self.vy].map(i128::from);
+        let [v2x, v2y] = [other.vx, other.vy].map(i128::from);
+        let dx = i128::from(other.px - self.px);
+        let dy = i128::from(other.py - self.py);
+        let det = v2x * v1y - v1x * v2y;
+        if det == 0 {
+            return None;
+        }
+        let t1 = (v2x * dy - v2y * dx) as f64 / det as f64;
+        let t2 = (v1x * dy - v1y * dx) as f64 / det as f64;
+        let point = (
+            self.px as f64 + self.vx as f64 * t1,
+            self.py as f64 + self.vy as f64 * t1,
+        );
+        Some((t1, t2, point))
+    }
+
+    /// Whether both hailstones move in x and y along the same line.
+    pub fn xy_paths_coincide(&self, other: &Hailstone) -> bool {
+        let cross = |(ax, ay): (i64, i64), (bx, by): (i64, i64)| {
+            i128::from(ax) * i128::from(by) - i128::from(ay) * i128::from(bx)
+        };
+        let offset = (other.px - self.px, other.py - self.py);
+        !self.is_xy_still()
+            && !other.is_xy_still()
+            && cross((self.vx, self.vy), (other.vx, other.vy)) == 0
+            && cross((self.vx, self.vy), offset) == 0
+    }
+
+    fn is_xy_still(&self) -> bool {
+        self.vx == 0 && self.vy == 0
+    }
+
+    /// When the hailstone is at (`x`, `y`), or None if it never is. A still hailstone sitting
+    /// there is there now.
+    fn xy_time_at(&self, x: i64, y: i64) -> Option<f64> {
+        let [vx, vy] = [self.vx, self.vy].map(i128::from);
+        let [dx, dy] = [x - self.px, y - self.py].map(i128::from);
+        if vx * dy != vy * dx {
+            return None;
+        }
+        if self.is_xy_still() {
+            return (dx == 0 && dy == 0).then_some(0.0);
+        }
+        Some((vx * dx + vy * dy) as f64 / (vx * vx + vy * vy) as f64)
+    }
+
+    /// Whether two hailstones whose paths coincide are ever both headed through the same point
+    /// in the square from `min_xy` to `max_xy` in x and y.
+    fn coincident_paths_meet_in_area(&self, other: &Hailstone, min_xy: f64, max_xy: f64) -> bool {
+        // Measure both paths in terms of this hailstone's time, which starts now
+        let (mut from, mut to) = (0.0_f64, f64::INFINITY);
+        let other_now = self
+            .xy_time_at(other.px, other.py)
+            .expect("paths should coincide");
+        if self.vx * other.vx + self.vy * other.vy > 0 {
+            from = from.max(other_now);
+        } else {
+            to = to.min(other_now);
+        }
+
+        // Then cut that down to when this hailstone is inside the area
+        for (p, v) in [(self.px, self.vx), (self.py, self.vy)] {
+            let (p, v) = (p as f64, v as f64);
+            if v == 0.0 {
+                if p < min_xy || p > max_xy {
+                    return false;
+                }
+            } else {
+                let (a, b) = ((min_xy - p) / v, (max_xy - p) / v);
+                from = from.max(a.min(b));
+                to = to.min(a.max(b));
+            }
+        }
+        from <= to
+    }
+}
+
+/// One hailstone per line of the puzzle input.
+pub fn parse_hailstones(input: &str) -> Result<Vec<Hailstone>, AocError> {
+    parse_lines(input.trim(), str::parse)
+}
+
+/// The closest two hailstones' paths get, and when each hailstone is at that point.
+#[derive(Debug, PartialEq)]
+pub struct ClosestApproach {
+    pub distance: f64,
+    pub times: (f64, f64),
+}
+
+#[test]
+fn test_closest_approach() {
+    let hailstones = parse_hailstones(TEST_INPUT).unwrap();
+    assert_eq!(hailstones[0].at(5), [9, 18, 20]);
+
+    // The rock hits the first hailstone after 5 nanoseconds
+    let rock: Hailstone = "24, 13, 10 @ -3, 1, 2".parse().unwrap();
+    assert_eq!(
+        rock.closest_approach(&hailstones[0]),
+        Some(ClosestApproach {
+            distance: 0.0,
+            times: (5.0, 5.0)
+        })
+    );
+
+    // Paths along the x and y axes, offset by 2 in z
+    let a: Hailstone = "0, 0, 0 @ 1, 0, 0".parse().unwrap();
+    let b: Hailstone = "3, -4, 2 @ 0, 2, 0".parse().unwrap();
+    assert_eq!(
+        a.closest_approach(&b),
+        Some(ClosestApproach {
+            distance: 2.0,
+            times: (3.0, 2.0)
+        })
+    );
+    assert_eq!(a.closest_approach(&a), None);
+}
+
+#[test]
+fn test_at() {
+    let hailstone: Hailstone = "19, 13, 30 @ -2, 1, -2".parse().unwrap();
+    assert_eq!(hailstone.at(0), [19, 13, 30]);
+    assert_eq!(hailstone.at(1), [17, 14, 28]);
+    assert_eq!(hailstone.at(-3), [25, 10, 36]);
+}
+
+#[test]
+fn test_xy_intersection_time() {
+    let hailstones = parse_hailstones(TEST_INPUT).unwrap();
+    let close = |(t1, t2, (x, y)): (f64, f64, (f64, f64)), expected: [f64; 4]| {
+        [t1, t2, x, y]
+            .iter()
+            .zip(expected)
+            .all(|(actual, expected)| (actual - expected).abs() < 1e-9)
+    };
+
+    // The example's crossings inside the test area
+    let ab = hailstones[0].xy_intersection_time(&hailstones[1]).unwrap();
+    assert!(
+        close(ab, [7.0 / 3.0, 11.0 / 3.0, 43.0 / 3.0, 46.0 / 3.0]),
+        "{ab:?}"
+    );
+    let ac = hailstones[0].xy_intersection_time(&hailstones[2]).unwrap();
+    assert!(
+        close(ac, [11.0 / 3.0, 25.0 / 6.0, 35.0 / 3.0, 50.0 / 3.0]),
+        "{ac:?}"
+    );
+
+    // Swapping the hailstones swaps the times but not the point
+    let ba = hailstones[1].xy_intersection_time(&hailstones[0]).unwrap();
+    assert!(close(ba, [ab.1, ab.0, ab.2 .0, ab.2 .1]), "{ba:?}");
+
+    // A crossing in the past for the first hailstone
+    let (t1, t2, _) = hailstones[0].xy_intersection_time(&hailstones[4]).unwrap();
+    assert!(t1 < 0.0 && t2 > 0.0);
+
+    // Parallel paths
+    assert_eq!(hailstones[1].xy_intersection_time(&hailstones[2]), None);
+    assert_eq!(hailstones[0].xy_intersection_time(&hailstones[0]), None);
+
+    // Straight up and down in y, which has no slope
+    let a: Hailstone = "5, 0, 0 @ 0, 1, 0".parse().unwrap();
+    let b: Hailstone = "0, 3, 0 @ 2, 1, 0".parse().unwrap();
+    assert!(close(
+        a.xy_intersection_time(&b).unwrap(),
+        [5.5, 2.5, 5.0, 5.5]
+    ));
+}
+
+pub fn count_xy_intersections_in_test_zone(
+    input: &str,
+    min_xy: f64,
+    max_xy: f64,
+) -> Result<usize, AocError> {
+    Ok(count_xy_intersections(
+        &parse_hailstones(input)?,
+        min_xy,
+        max_xy,
+    ))
+}
+
+fn count_xy_intersections(hailstones: &[Hailstone], min_xy: f64, max_xy: f64) -> usize {
+    (0..hailstones.len())
+        .into_par_iter()
+        .map(|h1_idx| {
+            let h1 = &hailstones[h1_idx];
+            hailstones[h1_idx + 1..]
+                .iter()
+                .filter(|h2| {
+                    if h1.xy_paths_coincide(h2) {
+                        trace!("{h1:?} and {h2:?} share a path");
+                        return h1.coincident_paths_meet_in_area(h2, min_xy, max_xy);
+                    }
+                    let Some((t1, t2, intersection)) = h1.xy_intersection_time(h2) else {
+                        trace!("{h1:?} and {h2:?} do not intersect");
+                        return false;
+                    };
+                    if t1 < 0.0 || t2 < 0.0 {
+                        trace!("{h1:?} and {h2:?} intersected in the past");
+                        return false;
+                    }
+                    trace!(
+                        "{h1:?} and {h2:?} intersect at ({},{})",
+                        intersection.0,
+                        intersection.1
+                    );
+
+                    intersection.0 >= min_xy
+                        && intersection.0 <= max_xy
+                        && intersection.1 >= min_xy
+                        && intersection.1 <= max_xy
+                })
+                .count()
+        })
+        .sum()
+}
+
+#[test]
+fn test_count_xy_intersections_in_test_zone() {
+    assert_eq!(
+        count_xy_intersections_in_test_zone(TEST_INPUT, 7.0, 27.0),
+        Ok(2)
+    );
+}
+
+#[test]
+fn test_degenerate_trajectories() {
+    let count = |input: &str| count_xy_intersections_in_test_zone(input, 7.0, 27.0).unwrap();
+
+    // Straight up in y, with no slope, crossing paths with the example's first hailstone
+    let vertical = "10, 0, 0 @ 0, 1, 0";
+    assert_eq!(count(&format!("{vertical}\n19, 13, 30 @ -2, 1, -2")), 1);
+    assert_eq!(count(&format!("{vertical}\n10, 3, 0 @ 0, 5, 0")), 1);
+
+    // Along the same line, meeting inside the area unless they're headed apart or only share
+    // it outside
+    let shared = "10, 10, 0 @ 1, 1, 0";
+    assert_eq!(count(&format!("{shared}\n12, 12, 5 @ 2, 2, 1")), 1);
+    assert_eq!(count(&format!("{shared}\n20, 20, 5 @ -1, -1, 1")), 1);
+    assert_eq!(count(&format!("{shared}\n8, 8, 5 @ -1, -1, 1")), 0);
+    assert_eq!(count(&format!("{shared}\n30, 30, 5 @ 1, 1, 1")), 0);
+    assert_eq!(count("1, 20, 0 @ 1, 0, 0\n3, 20, 5 @ 1, 0, 1"), 1);
+    let shared: Hailstone = shared.parse().unwrap();
+    assert!(shared.xy_paths_coincide(&"0, 0, 0 @ -3, -3, 0".parse().unwrap()));
+    assert!(!shared.xy_paths_coincide(&"0, 1, 0 @ 1, 1, 0".parse().unwrap()));
+    assert_eq!(shared.xy_intersection_time(&shared), None);
+
+    // Not moving in x and y, in the way of another hailstone or not
+    let still: Hailstone = "15, 15, 0 @ 0, 0, 1".parse().unwrap();
+    assert_eq!(
+        still.xy_intersection_time(&shared),
+        Some((5.0, 5.0, (15.0, 15.0)))
+    );
+    assert_eq!(
+        shared.xy_intersection_time(&"5, 5, 0 @ 0, 0, 0".parse().unwrap()),
+        Some((-5.0, -5.0, (5.0, 5.0)))
+    );
+    assert_eq!(
+        still.xy_intersection_time(&still),
+        Some((0.0, 0.0, (15.0, 15.0)))
+    );
+    assert_eq!(
+        still.xy_intersection_time(&"16, 15, 0 @ 0, 0, 0".parse().unwrap()),
+        None
+    );
+    assert_eq!(
+        count("15, 15, 0 @ 0, 0, 1\n10, 10, 0 @ 1, 1, 0\n10, 20, 0 @ 1, 1, 0"),
+        1
+    );
+
+    // A still hailstone's closest approach is whenever the other gets nearest it
+    let still: Hailstone = "15, 15, 0 @ 0, 0, 0".parse().unwrap();
+    let approach = still
+        .closest_approach(&"0, 0, 0 @ 1, 0, 0".parse().unwrap())
+        .unwrap();
+    assert_eq!(approach.times, (15.0, 15.0));
+    assert_eq!(approach.distance, 15.0);
+}
+
+/// Lowest x and y of the puzzle's test area.
+pub const TEST_AREA_MIN: f64 = 200000000000000.0;
+/// Highest x and y of the puzzle's test area.
+pub const TEST_AREA_MAX: f64 = 400000000000000.0;
+
+/// Number of pairs of hailstones whose paths cross inside the test area from `min_xy` to
+/// `max_xy`, which is `TEST_AREA_MIN` to `TEST_AREA_MAX` for the real puzzle.
+pub fn part1(input: &str, min_xy: f64, max_xy: f64) -> Result<usize, AocError> {
+    count_xy_intersections_in_test_zone(input, min_xy, max_xy)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT, 7.0, 27.0), Ok(2));
+    assert_eq!(part1(TEST_INPUT, 7.0, 16.0), Ok(1));
+    assert_eq!(part1(TEST_INPUT, TEST_AREA_MIN, TEST_AREA_MAX), Ok(0));
+}
+
+fn cross(a: [i64; 3], b: [i64; 3]) -> [i128; 3] {
+    let [ax, ay, az] = a.map(i128::from);
+    let [bx, by, bz] = b.map(i128::from);
+    [ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx]
+}
+
+/// Solves the square system whose rows are the coefficients followed by the right hand side,
+/// or None if it doesn't have a single solution.
+fn solve_linear_system(mut rows: Vec<Vec<BigRational>>) -> Option<Vec<BigRational>> {
+    let n = rows.len();
+    for col in 0..n {
+        let pivot = (col..n).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot);
+        let pivot_row = rows[col].clone();
+        for (row_idx, row) in rows.iter_mut().enumerate() {
+            if row_idx == col || row[col].is_zero() {
+                continue;
+            }
+            let factor = &row[col] / &pivot_row[col];
+            for (cell, pivot_cell) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *cell -= &factor * pivot_cell;
+            }
+        }
+    }
+    Some(
+        rows.iter()
+            .enumerate()
+            .map(|(i, row)| &row[n] / &row[i])
+            .collect(),
+    )
+}
+
+/// A rock thrown so it hits every hailstone, worked out from the first three.
+///
+/// The rock at P moving at V hits hailstone i exactly when (P - p_i) x (V - v_i) = 0. The P x V
+/// term is the same for every hailstone, so subtracting the equations for two hailstones leaves
+/// P x (v_j - v_i) + (p_j - p_i) x V = p_j x v_j - p_i x v_i, which is linear. Two pairs give six
+/// equations for the six unknowns.
+pub fn rock_throw(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    let [first, rest @ ..] = hailstones else {
+        return None;
+    };
+    let mut rows = vec![];
+    for other in rest.iter().take(2) {
+        let dv: [i64; 3] = std::array::from_fn(|i| other.velocity()[i] - first.velocity()[i]);
+        let dp: [i64; 3] = std::array::from_fn(|i| other.position()[i] - first.position()[i]);
+        let rhs: [i128; 3] = std::array::from_fn(|i| {
+            cross(other.position(), other.velocity())[i]
+                - cross(first.position(), first.velocity())[i]
+        });
+        // Coefficients of (Px, Py, Pz, Vx, Vy, Vz) in each component of the cross products
+        let [ax, ay, az] = dv.map(i128::from);
+        let [bx, by, bz] = dp.map(i128::from);
+        let coefficients = [
+            [0, az, -ay, 0, -bz, by],
+            [-az, 0, ax, bz, 0, -bx],
+            [ay, -ax, 0, -by, bx, 0],
+        ];
+        for (coefficients, rhs) in coefficients.into_iter().zip(rhs) {
+            rows.push(
+                coefficients
+                    .into_iter()
+                    .chain([rhs])
+                    .map(|n| BigRational::from_integer(BigInt::from(n)))
+                    .collect(),
+            );
+        }
+    }
+    if rows.len() < 6 {
+        return None;
+    }
+
+    let solution: Vec<i64> = solve_linear_system(rows)?
+        .iter()
+        .map(|n| {
+            n.is_integer()
+                .then(|| n.to_integer().try_into().ok())
+                .flatten()
+        })
+        .collect::<Option<_>>()?;
+    let rock = Hailstone {
+        px: solution[0],
+        py: solution[1],
+        pz: solution[2],
+        vx: solution[3],
+        vy: solution[4],
+        vz: solution[5],
+    };
+
+    // Only three hailstones went into the solution, so check it hits the rest too
+    hits_all(&rock, hailstones).then_some(rock)
+}
+
+/// Whether `rock` hits every hailstone, each at a whole number of nanoseconds from now.
+fn hits_all(rock: &Hailstone, hailstones: &[Hailstone]) -> bool {
+    hailstones.iter().all(|hailstone| {
+        rock.closest_approach(hailstone).is_some_and(|approach| {
+            let t = approach.times.0.round() as i64;
+            t >= 0 && rock.at(t) == hailstone.at(t)
+        })
+    })
+}
+
+/// Fastest the velocity search tries throwing the rock along x and y.
+const MAX_ROCK_SPEED: i64 = 500;
+
+/// A rock thrown so it hits every hailstone, found by trying each rock velocity in x and y up
+/// to `max_speed`.
+///
+/// Seen from the rock, every hailstone comes straight at it, so with the rock's velocity taken
+/// off theirs, all the hailstones' paths cross where the rock starts. For each (vx, vy), the first
+/// two hailstones' shifted xy paths cross where the rock would have to start, and the times they
+/// get there pin down vz and z.
+fn rock_throw_by_velocity_search(hailstones: &[Hailstone], max_speed: i64) -> Option<Hailstone> {
+    (-max_speed..=max_speed)
+        .flat_map(|vx| (-max_speed..=max_speed).map(move |vy| (vx, vy)))
+        .find_map(|(vx, vy)| rock_throw_with_xy_velocity(hailstones, vx, vy))
+}
+
+fn rock_throw_with_xy_velocity(hailstones: &[Hailstone], vx: i64, vy: i64) -> Option<Hailstone> {
+    let shifted = |h: &Hailstone| Hailstone {
+        vx: h.vx - vx,
+        vy: h.vy - vy,
+        ..*h
+    };
+    let (first, rest) = hailstones.split_first()?;
+    // Any hailstone whose shifted path isn't parallel to the first's will do
+    let (second, (t, s, _)) = rest
+        .iter()
+        .find_map(|h| Some((h, shifted(first).xy_intersection_time(&shifted(h))?)))?;
+
+    // The rock has to hit both at whole numbers of nanoseconds, at different times
+    let (t, s) = (t.round() as i64, s.round() as i64);
+    if t < 0 || s < 0 || t == s {
+        return None;
+    }
+    let (z0, z1) = (first.at(t)[2], second.at(s)[2]);
+    if (z0 - z1) % (t - s) != 0 {
+        return None;
+    }
+    let vz = (z0 - z1) / (t - s);
+    let rock = Hailstone {
+        px: first.px + (first.vx - vx) * t,
+        py: first.py + (first.vy - vy) * t,
+        pz: z0 - vz * t,
+        vx,
+        vy,
+        vz,
+    };
+    hits_all(&rock, hailstones).then_some(rock)
+}
+
+/// Ways of working out the rock throw for part 2, which should all agree.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Part2Solver {
+    /// Gaussian elimination on the cross product constraints from three hailstones
+    LinearAlgebra,
+    /// Search through small rock velocities for one that lines every hailstone up
+    VelocitySearch,
+}
+
+impl Part2Solver {
+    pub fn rock_throw(self, hailstones: &[Hailstone]) -> Option<Hailstone> {
+        match self {
+            Part2Solver::LinearAlgebra => rock_throw(hailstones),
+            Part2Solver::VelocitySearch => {
+                rock_throw_by_velocity_search(hailstones, MAX_ROCK_SPEED)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rock_throw() {
+    let hailstones = parse_hailstones(TEST_INPUT).unwrap();
+    assert_eq!(
+        rock_throw(&hailstones),
+        Some("24, 13, 10 @ -3, 1, 2".parse().unwrap())
+    );
+}
+
+/// Sum of the coordinates the rock has to be thrown from to hit every hailstone, or `None` if
+/// no throw hits them all.
+pub fn part2(input: &str, solver: Part2Solver) -> Result<Option<i64>, AocError> {
+    let hailstones = parse_hailstones(input)?;
+    Ok(rock_throw_sum(&hailstones, solver))
+}
+
+fn rock_throw_sum(hailstones: &[Hailstone], solver: Part2Solver) -> Option<i64> {
+    let rock = solver.rock_throw(hailstones)?;
+    Some(rock.position().iter().sum())
+}
+
+/// The hailstones' positions and velocities, one per line.
+pub struct Hailstones(Vec<Hailstone>);
+
+impl Solver for Hailstones {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self(parse_hailstones(input)?))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(count_xy_intersections(&self.0, TEST_AREA_MIN, TEST_AREA_MAX).to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        let sum = rock_throw_sum(&self.0, Part2Solver::LinearAlgebra)
+            .ok_or(SolveError::failed("no rock throw hits every hailstone"))?;
+        Ok(sum.to_string())
+    }
+
+    /// Part 2 by searching through rock velocities instead. Part 1 is only ever solved one way.
+    fn naive(&self, part: u8) -> Result<String, SolveError> {
+        if part != 2 {
+            return Err(SolveError::NotImplemented);
+        }
+        let sum = rock_throw_sum(&self.0, Part2Solver::VelocitySearch)
+            .ok_or(SolveError::failed("no rock throw hits every hailstone"))?;
+        Ok(sum.to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT, Part2Solver::LinearAlgebra), Ok(Some(47)));
+    let hailstones = Hailstones::parse(TEST_INPUT).unwrap();
+    assert_eq!(hailstones.naive(2), Ok("47".to_string()));
+    assert_eq!(hailstones.naive(1), Err(SolveError::NotImplemented));
+}
+
+#[test]
+fn test_part2_solvers_agree() {
+    let hailstones = parse_hailstones(TEST_INPUT).unwrap();
+    // The same hailstones and the rock that hits them, mirrored through the origin
+    let mirrored: Vec<Hailstone> = hailstones
+        .iter()
+        .map(|h| Hailstone {
+            px: -h.px,
+            py: -h.py,
+            pz: -h.pz,
+            vx: -h.vx,
+            vy: -h.vy,
+            vz: -h.vz,
+        })
+        .collect();
+    for (hailstones, rock) in [
+        (&hailstones, "24, 13, 10 @ -3, 1, 2"),
+        (&mirrored, "-24, -13, -10 @ 3, -1, -2"),
+    ] {
+        for solver in Part2Solver::value_variants() {
+            assert_eq!(
+                solver.rock_throw(hailstones),
+                Some(rock.parse().unwrap()),
+                "{solver:?}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(24, 1);
@@ -0,0 +1,665 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Range,
+    str::FromStr,
+};
+
+use aoc_core::{Dot, NodeId, SolveError, Solver, UnionFind};
+use clap::ValueEnum;
+use rayon::prelude::*;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Day25Error {
+    #[error("malformed line {0:?}, expected abc: def ghi ...")]
+    MalformedLine(String),
+    #[error("the graph has no nodes")]
+    Empty,
+    #[error("the graph is already in {0} pieces before cutting anything")]
+    Disconnected(usize),
+    #[error("{0:?} found no 3 edges that cut the graph in two")]
+    NoCut(Algorithm),
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
+pub struct Node([u8; 3]);
+
+impl std::fmt::Debug for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", &std::str::from_utf8(&self.0).unwrap())
+    }
+}
+
+impl FromStr for Node {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.as_bytes().try_into().map_err(|_| ())?))
+    }
+}
+
+/// The components and the wires between them, with the edges numbered for the flow along them.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    /// Each node, added in alphabetical order so they're numbered in it, with each edge both
+    /// ways and each node's neighbors in order
+    adjacency: aoc_core::Graph<Node>,
+    /// Numbering every node's edges one after another, node `i`'s are
+    /// `offsets[i]..offsets[i + 1]`
+    offsets: Vec<u32>,
+    /// Each edge once, lower index first, in order
+    edges: Vec<(NodeId, NodeId)>,
+}
+
+impl FromStr for Graph {
+    type Err = Day25Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut named_edges: Vec<(Node, Node)> = vec![];
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let malformed = || Day25Error::MalformedLine(line.to_string());
+            let (start, ends) = line.split_once(':').ok_or_else(malformed)?;
+            let start: Node = start.trim().parse().map_err(|_| malformed())?;
+            for end in ends.split_whitespace() {
+                named_edges.push((start, end.parse().map_err(|_| malformed())?));
+            }
+        }
+
+        let mut names: Vec<Node> = named_edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+        names.sort();
+        names.dedup();
+        let id = |name: &Node| names.binary_search(name).unwrap() as NodeId;
+        let edges = named_edges
+            .iter()
+            .map(|(a, b)| (id(a).min(id(b)), id(a).max(id(b))))
+            .collect();
+        Ok(Graph::from_edges(names, edges))
+    }
+}
+
+impl Graph {
+    /// A graph with each of `names` as a node, joined by `edges` in either direction.
+    fn from_edges(names: Vec<Node>, mut edges: Vec<(NodeId, NodeId)>) -> Graph {
+        for edge in &mut edges {
+            *edge = (edge.0.min(edge.1), edge.0.max(edge.1));
+        }
+        edges.sort();
+        edges.dedup();
+
+        let mut adjacency = aoc_core::Graph::new();
+        for name in &names {
+            adjacency.add_node(name);
+        }
+        // Going through the edges in order adds each node's neighbors in order too
+        for &(a, b) in &edges {
+            adjacency.add_edge(a, b);
+            adjacency.add_edge(b, a);
+        }
+        let offsets = std::iter::once(0)
+            .chain(adjacency.nodes().scan(0, |offset, node| {
+                *offset += adjacency.successors(node).len() as u32;
+                Some(*offset)
+            }))
+            .collect();
+
+        Graph {
+            adjacency,
+            offsets,
+            edges,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn nodes(&self) -> Range<NodeId> {
+        self.adjacency.nodes()
+    }
+
+    pub fn name(&self, node: NodeId) -> Node {
+        *self.adjacency.label(node)
+    }
+
+    #[cfg(test)]
+    fn id_of(&self, name: &str) -> Option<NodeId> {
+        self.adjacency.id(&name.parse().ok()?)
+    }
+
+    /// Where `node`'s neighbors are in `neighbors`, which also numbers the edges out of it.
+    fn arcs(&self, node: NodeId) -> Range<usize> {
+        self.offsets[node as usize] as usize..self.offsets[node as usize + 1] as usize
+    }
+
+    fn neighbors(&self, node: NodeId) -> &[NodeId] {
+        self.adjacency.successors(node)
+    }
+
+    /// The groups of nodes connected to each other, biggest first.
+    fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        self.adjacency.connected_components()
+    }
+
+    /// The number of nodes in each group still connected to each other with `cut` taken out,
+    /// biggest first.
+    fn component_sizes(&self, cut: &[(NodeId, NodeId)]) -> Vec<usize> {
+        let mut sets = UnionFind::new(self.len());
+        for &(a, b) in &self.edges {
+            if !cut.iter().any(|&e| e == (a, b) || e == (b, a)) {
+                sets.union(a as usize, b as usize);
+            }
+        }
+        sets.set_sizes()
+    }
+
+    /// A copy of the graph with `edges` taken out.
+    fn without_edges(&self, edges: &[(NodeId, NodeId)]) -> Graph {
+        let is_removed = |a: NodeId, b: NodeId| edges.iter().any(|&e| e == (a, b) || e == (b, a));
+        let kept = self
+            .edges
+            .iter()
+            .copied()
+            .filter(|&(a, b)| !is_removed(a, b))
+            .collect();
+        let names = self.nodes().map(|node| self.name(node)).collect();
+        Graph::from_edges(names, kept)
+    }
+
+    pub fn degree_stats(&self) -> DegreeStats {
+        let degrees: Vec<usize> = self.nodes().map(|n| self.neighbors(n).len()).collect();
+        DegreeStats {
+            nodes: self.len(),
+            edges: self.edges.len(),
+            min: degrees.iter().copied().min().unwrap_or(0),
+            max: degrees.iter().copied().max().unwrap_or(0),
+            mean: 2.0 * self.edges.len() as f64 / self.len().max(1) as f64,
+        }
+    }
+
+    /// The graph in Graphviz format, with the two sides left by cutting `cut` in different
+    /// colours and the cut edges in red.
+    pub fn to_dot(&self, cut: &[(NodeId, NodeId)]) -> Dot {
+        let is_cut = |a: NodeId, b: NodeId| cut.iter().any(|&e| e == (a, b) || e == (b, a));
+        let mut side = vec![0; self.len()];
+        for (i, component) in self
+            .without_edges(cut)
+            .connected_components()
+            .iter()
+            .enumerate()
+        {
+            for &node in component {
+                side[node as usize] = i;
+            }
+        }
+        let colors = ["lightblue", "lightpink"];
+
+        let mut dot = Dot::graph("components");
+        for node in self.nodes() {
+            let color = colors.get(side[node as usize]).unwrap_or(&"white");
+            let name = format!("{:?}", self.name(node));
+            dot.node(&name, &[("style", "filled"), ("fillcolor", color)]);
+        }
+        for &(a, b) in &self.edges {
+            let (a_name, b_name) = (format!("{:?}", self.name(a)), format!("{:?}", self.name(b)));
+            if is_cut(a, b) {
+                dot.edge(&a_name, &b_name, &[("color", "red"), ("penwidth", "3")]);
+            } else {
+                dot.edge(&a_name, &b_name, &[]);
+            }
+        }
+        dot
+    }
+}
+
+/// A maximum set of paths from one node to another that don't share any edges.
+struct MaxFlow {
+    paths: usize,
+    /// Which nodes the start can still push more flow to, which is its side of a minimum cut
+    reachable: Vec<bool>,
+}
+
+/// Finds paths from `start` to `end` that don't share any edges as a unit capacity max flow,
+/// pushing one more unit along a shortest augmenting path each time.
+fn max_flow(graph: &Graph, start: NodeId, end: NodeId) -> MaxFlow {
+    // Net flow along each edge out of each node, which is -1, 0 or 1
+    let mut flow: Vec<i8> = vec![0; 2 * graph.edges.len()];
+    let mut paths = 0;
+    loop {
+        // The node each node was reached from, and along which of its edges
+        let mut came_from: Vec<Option<(NodeId, usize)>> = vec![None; graph.len()];
+        came_from[start as usize] = Some((start, usize::MAX));
+        let mut to_examine: VecDeque<NodeId> = VecDeque::from([start]);
+        while let Some(here) = to_examine.pop_front() {
+            if here == end {
+                break;
+            }
+            for (arc, &next) in graph.arcs(here).zip(graph.neighbors(here)) {
+                if flow[arc] < 1 && came_from[next as usize].is_none() {
+                    came_from[next as usize] = Some((here, arc));
+                    to_examine.push_back(next);
+                }
+            }
+        }
+        if came_from[end as usize].is_none() {
+            let reachable = came_from.iter().map(Option::is_some).collect();
+            return MaxFlow { paths, reachable };
+        }
+
+        let mut here = end;
+        while here != start {
+            let (prev, arc) = came_from[here as usize].unwrap();
+            let back = graph.arcs(here).start + graph.neighbors(here).binary_search(&prev).unwrap();
+            flow[arc] += 1;
+            flow[back] -= 1;
+            here = prev;
+        }
+        paths += 1;
+    }
+}
+
+/// Number of paths from `start` to `end` that don't share any edges, which is also how few
+/// edges it takes to cut them apart.
+fn edge_connectivity(graph: &Graph, start: NodeId, end: NodeId) -> usize {
+    max_flow(graph, start, end).paths
+}
+
+/// The fewest edges that cut `start` off from `end`: the ones leaving the part of the residual
+/// graph the start can still reach once the flow between them is maxed out.
+fn min_cut(graph: &Graph, start: NodeId, end: NodeId) -> Vec<(NodeId, NodeId)> {
+    let reachable = max_flow(graph, start, end).reachable;
+    graph
+        .edges
+        .iter()
+        .copied()
+        .filter(|&(a, b)| reachable[a as usize] != reachable[b as usize])
+        .collect()
+}
+
+#[test]
+fn test_edge_connectivity() {
+    let graph = Graph::from_str(TEST_INPUT).unwrap();
+    let node = |name: &str| graph.id_of(name).unwrap();
+    // Across the cut
+    assert_eq!(edge_connectivity(&graph, node("jqt"), node("cmg")), 3);
+    assert_eq!(edge_connectivity(&graph, node("hfx"), node("pzl")), 3);
+    // On the same side, limited by the nodes' degrees
+    assert_eq!(edge_connectivity(&graph, node("hfx"), node("xhk")), 5);
+    assert_eq!(edge_connectivity(&graph, node("cmg"), node("frs")), 4);
+
+    assert_eq!(min_cut(&graph, node("jqt"), node("cmg")).len(), 3);
+    assert_eq!(min_cut(&graph, node("cmg"), node("frs")).len(), 4);
+
+    let chain = Graph::from_str("abc: bcd\nbcd: cde\nxyz: yzx").unwrap();
+    let node = |name: &str| chain.id_of(name).unwrap();
+    assert_eq!(edge_connectivity(&chain, node("abc"), node("cde")), 1);
+    assert_eq!(edge_connectivity(&chain, node("abc"), node("xyz")), 0);
+    assert_eq!(
+        min_cut(&chain, node("abc"), node("cde")),
+        [(node("abc"), node("bcd"))]
+    );
+}
+
+/// How many edges the nodes have.
+#[derive(Debug, PartialEq)]
+pub struct DegreeStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+#[test]
+fn test_graph_utilities() {
+    let graph = Graph::from_str(TEST_INPUT).unwrap();
+    assert_eq!(
+        graph.degree_stats(),
+        DegreeStats {
+            nodes: 15,
+            edges: 33,
+            min: 4,
+            max: 5,
+            mean: 4.4
+        }
+    );
+    assert_eq!(graph.connected_components().len(), 1);
+
+    let cut = find_edges_to_disconnect(&graph).unwrap();
+    let sizes: Vec<usize> = graph
+        .without_edges(&cut)
+        .connected_components()
+        .iter()
+        .map(|c| c.len())
+        .collect();
+    assert_eq!(sizes, [9, 6]);
+    assert_eq!(graph.component_sizes(&cut), [9, 6]);
+    assert_eq!(graph.component_sizes(&cut[..2]), [15]);
+
+    let dot = graph.to_dot(&cut).to_string();
+    assert!(dot.starts_with("graph components {\n"));
+    assert_eq!(dot.matches(" -- ").count(), 33);
+    assert_eq!(dot.matches("color=red").count(), 3);
+    assert!(dot.contains("    bvb -- cmg [color=red, penwidth=3];\n"));
+    assert_eq!(dot.matches("fillcolor=lightblue").count(), 9);
+    assert_eq!(dot.matches("fillcolor=lightpink").count(), 6);
+}
+
+#[test]
+fn test_from_str() {
+    let graph = Graph::from_str(TEST_INPUT).unwrap();
+    let node = |name: &str| graph.id_of(name).unwrap();
+    assert_eq!(graph.len(), 15);
+    assert_eq!(graph.edges.len(), 33);
+    assert_eq!(graph.name(0), "bvb".parse().unwrap());
+    assert_eq!(graph.id_of("abc"), None);
+    let neighbors: Vec<Node> = graph
+        .neighbors(node("qnr"))
+        .iter()
+        .map(|&n| graph.name(n))
+        .collect();
+    assert_eq!(
+        neighbors,
+        ["cmg", "frs", "nvd", "rzs"].map(|n| n.parse().unwrap())
+    );
+    for (a, b) in graph.edges.iter().copied() {
+        assert!(a < b);
+        assert!(graph.neighbors(a).contains(&b));
+        assert!(graph.neighbors(b).contains(&a));
+    }
+}
+
+fn find_nodes_in_loosely_connected_parts(graph: &Graph) -> Option<(NodeId, NodeId)> {
+    let n = graph.len() as NodeId;
+    (0..n)
+        .into_par_iter()
+        .flat_map_iter(|n1| (n1 + 1..n).map(move |n2| (n1, n2)))
+        // Nodes on opposite sides of the 3 edge cut only have 3 separate paths between them
+        .find_map_any(|(n1, n2)| (edge_connectivity(graph, n1, n2) <= 3).then_some((n1, n2)))
+}
+
+#[test]
+fn test_find_nodes_in_loosely_connected_parts() {
+    let graph = Graph::from_str(TEST_INPUT).unwrap();
+    let node = |name: &str| graph.id_of(name).unwrap();
+    // Any pair will do as long as the known cut splits it, since the search takes whichever
+    // it finds first
+    let (n1, n2) = find_nodes_in_loosely_connected_parts(&graph).unwrap();
+    assert!(n1 < n2);
+    assert_eq!(edge_connectivity(&graph, n1, n2), 3);
+    let cut = [("hfx", "pzl"), ("bvb", "cmg"), ("nvd", "jqt")].map(|(a, b)| (node(a), node(b)));
+    let pieces = graph.without_edges(&cut).connected_components();
+    assert_eq!(pieces.len(), 2);
+    let side = |n| pieces.iter().position(|piece| piece.contains(&n)).unwrap();
+    assert_ne!(side(n1), side(n2));
+}
+
+fn find_edges_to_disconnect(graph: &Graph) -> Option<[(NodeId, NodeId); 3]> {
+    let (n1, n2) = find_nodes_in_loosely_connected_parts(graph)?;
+    min_cut(graph, n1, n2).try_into().ok()
+}
+
+/// Three edges that cut the graph in two, and how many nodes end up on each side.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cut {
+    pub edges: [(NodeId, NodeId); 3],
+    pub sizes: [usize; 2],
+}
+
+impl Cut {
+    /// The cut from taking out `edges`, or None if that doesn't leave exactly two pieces.
+    fn new(graph: &Graph, mut edges: [(NodeId, NodeId); 3]) -> Option<Cut> {
+        edges.sort();
+        let sizes = graph.component_sizes(&edges).try_into().ok()?;
+        Some(Cut { edges, sizes })
+    }
+}
+
+#[test]
+fn test_find_edges_to_disconnect() {
+    let graph = Graph::from_str(TEST_INPUT).unwrap();
+    let edges = find_edges_to_disconnect(&graph).unwrap();
+    let mut names: Vec<String> = edges
+        .iter()
+        .map(|(a, b)| {
+            let mut pair = [
+                format!("{:?}", graph.name(*a)),
+                format!("{:?}", graph.name(*b)),
+            ];
+            pair.sort();
+            pair.join("/")
+        })
+        .collect();
+    names.sort();
+    assert_eq!(names, ["bvb/cmg", "hfx/pzl", "jqt/nvd"]);
+}
+
+/// SplitMix64, so Karger's contractions can be repeated from a seed without pulling in rand.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// How many times Karger's algorithm contracts the graph before giving up on finding 3 edges.
+const KARGER_ATTEMPTS: usize = 1_000_000;
+
+/// Sizes of the two sides of a 3 edge cut found by Karger's algorithm: contract random edges
+/// until there are only two nodes left, and try again until just 3 edges join them.
+fn karger_cut(graph: &Graph, seed: u64) -> Option<Cut> {
+    let mut edges: Vec<(usize, usize)> = graph
+        .edges
+        .iter()
+        .map(|&(a, b)| (a as usize, b as usize))
+        .collect();
+    let mut rng = Rng(seed);
+
+    for _ in 0..KARGER_ATTEMPTS {
+        // Contracting edges in a random order is the same as picking random edges each time
+        for i in (1..edges.len()).rev() {
+            edges.swap(i, rng.below(i + 1));
+        }
+
+        // The nodes contracted together so far
+        let mut contracted = UnionFind::new(graph.len());
+        for &(a, b) in &edges {
+            if contracted.sets() == 2 {
+                break;
+            }
+            contracted.union(a, b);
+        }
+        if contracted.sets() != 2 {
+            continue;
+        }
+
+        let cut: Vec<(NodeId, NodeId)> = edges
+            .iter()
+            .filter(|&&(a, b)| !contracted.same(a, b))
+            .map(|&(a, b)| (a as NodeId, b as NodeId))
+            .collect();
+        if let Ok(cut) = cut.try_into() {
+            return Cut::new(graph, cut);
+        }
+    }
+    None
+}
+
+/// The 3 edge cut between the first nodes found with only 3 separate paths between them.
+fn flow_cut(graph: &Graph) -> Option<Cut> {
+    Cut::new(graph, find_edges_to_disconnect(graph)?)
+}
+
+/// How many nodes the betweenness algorithm runs a breadth-first search from each round.
+const BETWEENNESS_SAMPLE: usize = 200;
+
+/// How many shortest paths from a random sample of nodes go through each edge, keyed with the
+/// lower node first.
+fn edge_betweenness(graph: &Graph, rng: &mut Rng) -> HashMap<(NodeId, NodeId), usize> {
+    let mut counts: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+    for _ in 0..BETWEENNESS_SAMPLE.min(graph.len()) {
+        let source = rng.below(graph.len()) as NodeId;
+        let came_from = graph.adjacency.bfs(source);
+        for mut here in graph.nodes() {
+            while let Some(prev) = came_from[here as usize].filter(|_| here != source) {
+                *counts.entry((prev.min(here), prev.max(here))).or_default() += 1;
+                here = prev;
+            }
+        }
+    }
+    counts
+}
+
+/// The cut from repeatedly taking out the edge the most shortest paths go through,
+/// Girvan-Newman style, or None if 3 such cuts don't split the graph.
+fn betweenness_cut(graph: &Graph, seed: u64) -> Option<Cut> {
+    let mut rng = Rng(seed);
+    let mut cut = vec![];
+    let mut remaining = graph.clone();
+    for _ in 0..3 {
+        let betweenness = edge_betweenness(&remaining, &mut rng);
+        let (&edge, _) = betweenness
+            .iter()
+            .max_by_key(|&(&edge, &count)| (count, std::cmp::Reverse(edge)))?;
+        cut.push(edge);
+        remaining = graph.without_edges(&cut);
+    }
+
+    Cut::new(graph, cut.try_into().unwrap())
+}
+
+/// Ways of finding the 3 edges to cut, which should all agree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Algorithm {
+    /// Look for nodes only 3 edge-disjoint paths apart, then cut between them
+    Flow,
+    /// Randomly contract edges until only 3 are left between two groups of nodes
+    Karger,
+    /// Cut the edges the most shortest paths from a sample of nodes go through
+    Betweenness,
+}
+
+impl Algorithm {
+    /// Three edges that cut the graph in two, with `seed` for the randomized algorithms.
+    pub fn cut(self, graph: &Graph, seed: u64) -> Result<Cut, Day25Error> {
+        match graph.connected_components().len() {
+            0 => return Err(Day25Error::Empty),
+            1 => {}
+            pieces => return Err(Day25Error::Disconnected(pieces)),
+        }
+        match self {
+            Algorithm::Flow => flow_cut(graph),
+            Algorithm::Karger => karger_cut(graph, seed),
+            Algorithm::Betweenness => betweenness_cut(graph, seed),
+        }
+        .ok_or(Day25Error::NoCut(self))
+    }
+}
+
+pub fn part1(input: &str, algorithm: Algorithm, seed: u64) -> Result<u64, Day25Error> {
+    let graph: Graph = input.parse()?;
+    let [a, b] = algorithm.cut(&graph, seed)?.sizes;
+    Ok(a as u64 * b as u64)
+}
+
+impl Solver for Graph {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input.parse().map_err(SolveError::failed)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        cut_product(self, Algorithm::Flow)
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    /// Part 1 with Karger's random contractions instead of max flow.
+    fn naive(&self, part: u8) -> Result<String, SolveError> {
+        match part {
+            1 => cut_product(self, Algorithm::Karger),
+            _ => Err(SolveError::NotImplemented),
+        }
+    }
+}
+
+/// The product of the sizes of the two pieces `algorithm` cuts the graph into.
+fn cut_product(graph: &Graph, algorithm: Algorithm) -> Result<String, SolveError> {
+    let [a, b] = algorithm
+        .cut(graph, 2023)
+        .map_err(SolveError::failed)?
+        .sizes;
+    Ok((a as u64 * b as u64).to_string())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT, Algorithm::Flow, 0), Ok(54));
+    let graph = Graph::parse(TEST_INPUT).unwrap();
+    assert_eq!(graph.naive(1), Ok("54".to_string()));
+    assert_eq!(graph.naive(2), Err(SolveError::NotImplemented));
+}
+
+#[test]
+fn test_algorithms_agree() {
+    let graph = Graph::from_str(TEST_INPUT).unwrap();
+    let expected = Cut::new(&graph, find_edges_to_disconnect(&graph).unwrap()).unwrap();
+    assert_eq!(expected.sizes, [9, 6]);
+    for algorithm in Algorithm::value_variants() {
+        for seed in 0..10 {
+            assert_eq!(
+                algorithm.cut(&graph, seed).as_ref(),
+                Ok(&expected),
+                "{algorithm:?} with seed {seed}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        part1("jqt rhn xhk", Algorithm::Flow, 0),
+        Err(Day25Error::MalformedLine("jqt rhn xhk".to_string()))
+    );
+    assert_eq!(
+        part1("jqt: rhnn", Algorithm::Flow, 0),
+        Err(Day25Error::MalformedLine("jqt: rhnn".to_string()))
+    );
+    assert_eq!(part1("\n", Algorithm::Flow, 0), Err(Day25Error::Empty));
+
+    // Two squares, which need no cutting to be apart
+    let apart = "abc: bcd cde\ndef: bcd cde\nuvw: vwx wxy\nxyz: vwx wxy";
+    // A square, which falls apart after 2 cuts, and a complete graph, which takes 4
+    let square = "abc: bcd cde\ndef: bcd cde";
+    let complete = "abc: bcd cde def ghi\nbcd: cde def ghi\ncde: def ghi\ndef: ghi";
+    for algorithm in Algorithm::value_variants() {
+        assert_eq!(
+            part1(apart, *algorithm, 0),
+            Err(Day25Error::Disconnected(2))
+        );
+        for input in [square, complete] {
+            if matches!(algorithm, Algorithm::Karger) && input == square {
+                // Every contraction finds one of the cuts with 2 edges, so it'd try for a while
+                continue;
+            }
+            assert_eq!(
+                part1(input, *algorithm, 0),
+                Err(Day25Error::NoCut(*algorithm)),
+                "{algorithm:?} on {input}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(25, 1);
@@ -0,0 +1,315 @@
+use std::{collections::VecDeque, io::BufRead, str::FromStr};
+
+use aoc_core::{for_each_line, AocError, Coord, SolveError, Solver};
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(3, 1);
+
+#[derive(Debug, PartialEq, Eq)]
+struct PartNumber {
+    num: u32,
+    row: usize,
+    start_col: usize,
+    end_col: usize,
+}
+
+impl PartNumber {
+    fn covers(&self, p: Coord) -> bool {
+        p.y == self.row as i64 && (self.start_col as i64..self.end_col as i64).contains(&p.x)
+    }
+
+    fn is_adjacent_to(&self, p: Coord) -> bool {
+        p.neighbors8().any(|n| self.covers(n))
+    }
+}
+
+#[test]
+fn test_is_adjacent_to() {
+    let pn = PartNumber {
+        num: 42,
+        row: 3,
+        start_col: 5,
+        end_col: 8,
+    };
+    assert!(pn.is_adjacent_to(Coord::new(4, 2)));
+    assert!(pn.is_adjacent_to(Coord::new(4, 3)));
+    assert!(pn.is_adjacent_to(Coord::new(4, 4)));
+    assert!(pn.is_adjacent_to(Coord::new(5, 2)));
+    assert!(pn.is_adjacent_to(Coord::new(6, 4)));
+    assert!(pn.is_adjacent_to(Coord::new(8, 4)));
+
+    assert!(!pn.is_adjacent_to(Coord::new(6, 5)));
+    assert!(!pn.is_adjacent_to(Coord::new(6, 1)));
+
+    assert!(!pn.is_adjacent_to(Coord::new(3, 4)));
+    assert!(!pn.is_adjacent_to(Coord::new(9, 4)));
+
+    let pn = PartNumber {
+        num: 42,
+        row: 3,
+        start_col: 0,
+        end_col: 5,
+    };
+    assert!(pn.is_adjacent_to(Coord::new(0, 4)));
+    assert!(!pn.is_adjacent_to(Coord::new(6, 4)));
+}
+
+fn finish_number(
+    line: &str,
+    row: usize,
+    end_col: usize,
+    start_col: &mut Option<usize>,
+) -> Result<PartNumber, AocError> {
+    let start_col = start_col.take().unwrap();
+    let digits = &line[start_col..end_col];
+    Ok(PartNumber {
+        num: digits
+            .parse()
+            .map_err(|_| AocError::malformed(digits, "a number under 2^32"))?,
+        row,
+        start_col,
+        end_col,
+    })
+}
+
+fn extract_part_numbers(schematic: &str) -> Result<Vec<PartNumber>, AocError> {
+    let mut part_numbers = Vec::new();
+    for (row, line) in schematic.lines().enumerate() {
+        let mut num_start: Option<usize> = None;
+        for (col, ch) in line.bytes().enumerate() {
+            match (ch.is_ascii_digit(), num_start.is_some()) {
+                (true, true) => {}   // Number is continuing
+                (false, false) => {} // Non-number is continuing
+                (true, false) => {
+                    num_start = Some(col); // Number is starting
+                }
+                (false, true) => {
+                    part_numbers.push(finish_number(line, row, col, &mut num_start)?);
+                }
+            }
+        }
+        if num_start.is_some() {
+            part_numbers.push(finish_number(line, row, line.len(), &mut num_start)?);
+        }
+    }
+    Ok(part_numbers)
+}
+
+#[test]
+fn test_extract_part_numbers() {
+    assert_eq!(
+        extract_part_numbers(TEST_INPUT).unwrap()[..3],
+        [
+            PartNumber {
+                num: 467,
+                row: 0,
+                start_col: 0,
+                end_col: 3,
+            },
+            PartNumber {
+                num: 114,
+                row: 0,
+                start_col: 5,
+                end_col: 8,
+            },
+            PartNumber {
+                num: 35,
+                row: 2,
+                start_col: 2,
+                end_col: 4,
+            },
+        ]
+    );
+
+    assert_eq!(
+        extract_part_numbers("1.2\n3.4").unwrap(),
+        [
+            PartNumber {
+                num: 1,
+                row: 0,
+                start_col: 0,
+                end_col: 1,
+            },
+            PartNumber {
+                num: 2,
+                row: 0,
+                start_col: 2,
+                end_col: 3,
+            },
+            PartNumber {
+                num: 3,
+                row: 1,
+                start_col: 0,
+                end_col: 1,
+            },
+            PartNumber {
+                num: 4,
+                row: 1,
+                start_col: 2,
+                end_col: 3,
+            },
+        ]
+    );
+    assert_eq!(
+        extract_part_numbers("..99999999999"),
+        Err(AocError::malformed("99999999999", "a number under 2^32"))
+    );
+}
+
+fn is_symbol(ch: u8) -> bool {
+    ch != b'.' && !ch.is_ascii_digit()
+}
+
+fn is_gear_symbol(ch: u8) -> bool {
+    ch == b'*'
+}
+
+/// The engine schematic: its numbers, and the symbols that make them part numbers.
+pub struct Schematic {
+    numbers: Vec<PartNumber>,
+    /// Each symbol and where it is
+    symbols: Vec<(Coord, u8)>,
+}
+
+impl FromStr for Schematic {
+    type Err = AocError;
+
+    fn from_str(schematic: &str) -> Result<Self, Self::Err> {
+        let numbers = extract_part_numbers(schematic)?;
+        let symbols = schematic
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.bytes()
+                    .enumerate()
+                    .filter(|&(_, ch)| is_symbol(ch))
+                    .map(move |(col, ch)| (Coord::new(col as i64, row as i64), ch))
+            })
+            .collect();
+        Ok(Self { numbers, symbols })
+    }
+}
+
+impl Schematic {
+    /// The numbers next to a symbol.
+    fn valid_parts(&self) -> impl Iterator<Item = &PartNumber> {
+        self.numbers
+            .iter()
+            .filter(|pn| self.symbols.iter().any(|&(pt, _)| pn.is_adjacent_to(pt)))
+    }
+
+    /// The row of each gear and the two part numbers next to it.
+    fn gears(&self) -> Vec<(usize, (u32, u32))> {
+        self.symbols
+            .iter()
+            .filter(|&&(_, ch)| is_gear_symbol(ch))
+            .filter_map(|&(pt, _)| {
+                let mut adjacent = self.numbers.iter().filter(|pn| pn.is_adjacent_to(pt));
+                match (adjacent.next(), adjacent.next(), adjacent.next()) {
+                    (Some(a), Some(b), None) => Some((pt.y as usize, (a.num, b.num))),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn part_sum(&self) -> u32 {
+        self.valid_parts().map(|pn| pn.num).sum()
+    }
+
+    fn gear_ratio_sum(&self) -> u32 {
+        self.gears().iter().map(|(_, (a, b))| a * b).sum()
+    }
+}
+
+#[test]
+fn test_valid_parts() {
+    let schematic: Schematic = TEST_INPUT.parse().unwrap();
+    assert_eq!(
+        schematic.valid_parts().map(|pn| pn.num).collect::<Vec<_>>(),
+        [467, 35, 633, 617, 592, 755, 664, 598,]
+    );
+}
+
+pub fn part1(schematic: &str) -> Result<u32, AocError> {
+    Ok(schematic.parse::<Schematic>()?.part_sum())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), Ok(4361));
+}
+
+#[test]
+fn test_gears() {
+    let schematic: Schematic = TEST_INPUT.parse().unwrap();
+    assert_eq!(schematic.gears(), [(1, (467, 35)), (8, (755, 598))]);
+}
+
+pub fn part2(schematic: &str) -> Result<u32, AocError> {
+    Ok(schematic.parse::<Schematic>()?.gear_ratio_sum())
+}
+
+/// Both parts' answers for just the middle row of a three-row window of the schematic.
+fn middle_row_sums(window: &str) -> Result<(u32, u32), AocError> {
+    let schematic: Schematic = window.parse()?;
+    let part_sum = schematic
+        .valid_parts()
+        .filter(|pn| pn.row == 1)
+        .map(|pn| pn.num)
+        .sum();
+    let gear_sum = schematic
+        .gears()
+        .iter()
+        .filter(|&&(row, _)| row == 1)
+        .map(|(_, (a, b))| a * b)
+        .sum();
+    Ok((part_sum, gear_sum))
+}
+
+/// Both parts' answers, reading the schematic a line at a time and only keeping the lines
+/// on either side of the one being looked at.
+pub fn solve_streaming(reader: impl BufRead) -> Result<(u32, u32), AocError> {
+    let mut sums = (0, 0);
+    // An empty row above the first, so every row is in the middle of a window once
+    let mut window = VecDeque::from([String::new()]);
+    let mut slide = |window: &mut VecDeque<String>, line: &str| {
+        window.push_back(line.to_string());
+        if window.len() == 3 {
+            let (part_sum, gear_sum) = middle_row_sums(&window.make_contiguous().join("\n"))?;
+            sums.0 += part_sum;
+            sums.1 += gear_sum;
+            window.pop_front();
+        }
+        Ok(())
+    };
+    for_each_line(reader, |line| slide(&mut window, line))?;
+    slide(&mut window, "")?;
+    Ok(sums)
+}
+
+#[test]
+fn test_solve_streaming() {
+    assert_eq!(solve_streaming(TEST_INPUT.as_bytes()), Ok((4361, 467835)));
+    assert_eq!(solve_streaming("".as_bytes()), Ok((0, 0)));
+    assert_eq!(solve_streaming("12*3".as_bytes()), Ok((15, 36)));
+}
+
+impl Solver for Schematic {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.part_sum().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.gear_ratio_sum().to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(467835));
+}
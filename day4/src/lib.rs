@@ -0,0 +1,229 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    io::BufRead,
+    str::FromStr,
+};
+
+use aoc_core::{for_each_line, parse_numbers, AocError, SolveError, Solver};
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(4, 1);
+
+#[derive(Default, PartialEq, Eq, Debug)]
+struct Card {
+    got: HashSet<u32>,
+    winners: HashSet<u32>,
+}
+
+fn parse_card(line: &str) -> Result<Card, AocError> {
+    let malformed = || AocError::malformed(line, "a card like Card 1: 41 48 | 83 86");
+    let (_, numbers) = line.split_once(':').ok_or_else(malformed)?;
+    let (winners, got) = numbers.split_once('|').ok_or_else(malformed)?;
+    Ok(Card {
+        winners: parse_numbers(winners)?.into_iter().collect(),
+        got: parse_numbers(got)?.into_iter().collect(),
+    })
+}
+
+#[test]
+fn test_parse_card() {
+    assert_eq!(
+        parse_card("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"),
+        Ok(Card {
+            winners: [87, 83, 26, 28, 32].into(),
+            got: [88, 30, 70, 12, 93, 22, 82, 36].into(),
+        })
+    );
+    assert_eq!(
+        parse_card("Card 1 41 | 83"),
+        Err(AocError::malformed(
+            "Card 1 41 | 83",
+            "a card like Card 1: 41 48 | 83 86"
+        ))
+    );
+    assert_eq!(
+        parse_card("Card 1: 41 | 83 | 86"),
+        Err(AocError::malformed("|", "a number"))
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_card_round_trip(
+        id in 1..300u32,
+        winners in proptest::collection::hash_set(0..100u32, 0..10),
+        got in proptest::collection::hash_set(0..100u32, 0..25),
+    ) {
+        let list = |numbers: &HashSet<u32>| -> String {
+            numbers.iter().map(|n| format!("{n:>2} ")).collect()
+        };
+        let line = format!("Card {id:>3}: {}| {}", list(&winners), list(&got));
+        proptest::prop_assert_eq!(parse_card(&line), Ok(Card { got, winners }));
+    }
+
+    #[test]
+    fn test_parse_card_never_panics(
+        line in "(Card +[0-9]{1,3}:)?( +[0-9]{1,11}){0,5}( \\|)?( +[0-9]{1,11}){0,8}|\\PC*",
+    ) {
+        let _ = parse_card(&line);
+    }
+}
+
+fn count_winners(c: Card) -> usize {
+    c.got.intersection(&c.winners).count()
+}
+
+#[test]
+fn test_count_winners() {
+    assert_eq!(
+        count_winners(Card {
+            winners: [41, 48, 83, 86, 17].into(),
+            got: [83, 86, 6, 31, 17, 9, 48, 53].into(),
+        }),
+        4
+    );
+}
+
+/// An error if a card has too many winning numbers for its score to fit.
+fn check_winners(card: usize, num_winners: usize) -> Result<(), AocError> {
+    if num_winners > 32 {
+        return Err(AocError::Invalid(format!(
+            "card {card} has {num_winners} winning numbers, too many to score"
+        )));
+    }
+    Ok(())
+}
+
+fn score_part1(num_winners: usize) -> u32 {
+    if num_winners == 0 {
+        0
+    } else {
+        1 << (num_winners - 1)
+    }
+}
+
+#[test]
+fn test_score() {
+    assert_eq!(score_part1(0), 0);
+    assert_eq!(score_part1(1), 1);
+    assert_eq!(score_part1(4), 8);
+}
+
+/// The pile of scratchcards, as how many winning numbers each card has. Lines that aren't
+/// cards are skipped.
+pub struct Scratchcards(Vec<usize>);
+
+impl FromStr for Scratchcards {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let card_winners: Vec<usize> = input
+            .lines()
+            .filter_map(|line| parse_card(line).ok())
+            .map(count_winners)
+            .collect();
+        for (card_num, &num_wins) in card_winners.iter().enumerate() {
+            check_winners(card_num + 1, num_wins)?;
+            if card_num + num_wins >= card_winners.len() {
+                return Err(AocError::Invalid(format!(
+                    "card {} wins copies of cards past the last one",
+                    card_num + 1
+                )));
+            }
+        }
+        Ok(Self(card_winners))
+    }
+}
+
+impl Scratchcards {
+    fn points(&self) -> u32 {
+        self.0.iter().copied().map(score_part1).sum()
+    }
+
+    fn total_cards(&self) -> usize {
+        // Start with 1 of each card
+        let mut card_counts = vec![1; self.0.len()];
+
+        for (card_num, num_wins) in self.0.iter().cloned().enumerate() {
+            // Look ahead the number of cards that this card won and increment those card counts by the card count of the current card.
+            for i in 0..num_wins {
+                card_counts[card_num + i + 1] += card_counts[card_num];
+            }
+        }
+
+        card_counts.iter().sum()
+    }
+}
+
+pub fn part1(input: &str) -> Result<u32, AocError> {
+    Ok(input.parse::<Scratchcards>()?.points())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), Ok(13));
+}
+
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    Ok(input.parse::<Scratchcards>()?.total_cards())
+}
+
+/// Both parts' answers, reading the cards one at a time and only keeping count of the copies
+/// won of the cards still to come. Like [`part1`] and [`part2`], lines that aren't cards are
+/// skipped.
+pub fn solve_streaming(reader: impl BufRead) -> Result<(u32, usize), AocError> {
+    let (mut points, mut cards, mut cards_seen) = (0, 0, 0);
+    // Copies won of each of the next few cards
+    let mut won: VecDeque<usize> = VecDeque::new();
+    for_each_line(reader, |line| {
+        let Ok(card) = parse_card(line) else {
+            return Ok(());
+        };
+        let num_wins = count_winners(card);
+        check_winners(cards_seen + 1, num_wins)?;
+        cards_seen += 1;
+        points += score_part1(num_wins);
+        let copies = 1 + won.pop_front().unwrap_or(0);
+        cards += copies;
+        if won.len() < num_wins {
+            won.resize(num_wins, 0);
+        }
+        for count in won.iter_mut().take(num_wins) {
+            *count += copies;
+        }
+        Ok(())
+    })?;
+    Ok((points, cards))
+}
+
+#[test]
+fn test_solve_streaming() {
+    assert_eq!(solve_streaming(TEST_INPUT.as_bytes()), Ok((13, 30)));
+}
+
+impl Solver for Scratchcards {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.points().to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.total_cards().to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(30));
+    assert_eq!(part2("Card 1: 41 | 41\nCard 2: 5 | 6"), Ok(3));
+    assert_eq!(
+        part2("Card 1: 41 | 41\nCard 2: 5 | 5"),
+        Err(AocError::Invalid(
+            "card 2 wins copies of cards past the last one".to_string()
+        ))
+    );
+}
@@ -0,0 +1,251 @@
+use aoc_core::{parse_numbers, AocError, SolveError, Solver};
+
+/// The numbers on a line, after its label if it has one.
+fn parse_row(line: &str) -> Result<Vec<u64>, AocError> {
+    let numbers = line.split_once(':').map_or(line, |(_, numbers)| numbers);
+    parse_numbers(numbers)
+}
+
+#[derive(Debug)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+
+fn parse_races(s: &str) -> Result<Vec<Race>, AocError> {
+    let mut lines = s.lines();
+    let times = parse_row(lines.next().ok_or(AocError::Missing("the times line"))?)
+        .map_err(|e| e.on_line(1))?;
+    let distances = parse_row(
+        lines
+            .next()
+            .ok_or(AocError::Missing("the distances line"))?,
+    )
+    .map_err(|e| e.on_line(2))?;
+    if times.len() != distances.len() {
+        return Err(AocError::Invalid(format!(
+            "there are {} times but {} distances",
+            times.len(),
+            distances.len()
+        )));
+    }
+    Ok(times
+        .iter()
+        .zip(distances.iter())
+        .map(|(&time, &distance)| Race { time, distance })
+        .collect())
+}
+
+#[test]
+fn test_parse_races() {
+    assert_eq!(
+        parse_races("").unwrap_err(),
+        AocError::Missing("the times line")
+    );
+    assert_eq!(
+        parse_races("Time: 7 15").unwrap_err(),
+        AocError::Missing("the distances line")
+    );
+    assert_eq!(
+        parse_races("Time: 7 15\nDistance: 9")
+            .unwrap_err()
+            .to_string(),
+        "there are 2 times but 1 distances"
+    );
+    assert_eq!(
+        parse_races("Time: 7 15\nDistance: 9 x").unwrap_err(),
+        AocError::malformed("x", "a number").on_line(2)
+    );
+}
+
+fn distance_travelled(race_time: u64, charge_time: u64) -> u64 {
+    charge_time * (race_time - charge_time)
+}
+
+#[test]
+fn test_distance_travelled() {
+    assert_eq!(distance_travelled(7, 0), 0);
+    assert_eq!(distance_travelled(7, 1), 6);
+    assert_eq!(distance_travelled(7, 2), 10);
+    assert_eq!(distance_travelled(7, 3), 12);
+    assert_eq!(distance_travelled(7, 4), 12);
+    assert_eq!(distance_travelled(7, 5), 10);
+    assert_eq!(distance_travelled(7, 6), 6);
+    assert_eq!(distance_travelled(7, 7), 0);
+
+    assert!(distance_travelled(30, 10) <= 200);
+    assert!(distance_travelled(30, 11) > 200);
+    assert!(distance_travelled(30, 19) > 200);
+    assert!(distance_travelled(30, 20) <= 200);
+}
+
+/// The shortest and longest charge times that win, or None if none do.
+fn minmax_charge_to_win(race: &Race) -> Option<(u64, u64)> {
+    // Travelled = charge_time * (race_time - charge_time)
+    // Want to beat distance, so solve
+    // distance < charge_time * (race_time - charge_time)
+    // distance < charge_time * race_time - charge_time^2
+    // -charge_time^2 + race_time * charge_time - distance > 0
+    // roots of charge_time = (race_time +- sqrt(race_time^2 - 4*distance))/2
+
+    let discriminant = (race.time as u128)
+        .pow(2)
+        .checked_sub(4 * race.distance as u128)?;
+    let sqrt_discriminant = (discriminant as f64).sqrt();
+    let min = (race.time as f64 - sqrt_discriminant) / 2.0;
+    let max = (race.time as f64 + sqrt_discriminant) / 2.0;
+
+    // Take the roots and find integer inequalities. If the roots aren't exact integers,
+    // then floor/ceil is fine. If they are exact integers, then take the "next" integer
+    // to satisfy the inequality
+    let mut min_int = min.ceil();
+    if min_int == min {
+        min_int += 1.0;
+    }
+
+    let mut max_int = max.floor();
+    if max_int == max {
+        max_int -= 1.0;
+    }
+
+    (min_int <= max_int).then_some((min_int as u64, max_int as u64))
+}
+
+#[test]
+fn test_minmax_charge_to_win() {
+    assert_eq!(
+        minmax_charge_to_win(&Race {
+            distance: 9,
+            time: 7
+        }),
+        Some((2, 5))
+    );
+    assert_eq!(
+        minmax_charge_to_win(&Race {
+            distance: 40,
+            time: 15
+        }),
+        Some((4, 11))
+    );
+    assert_eq!(
+        minmax_charge_to_win(&Race {
+            distance: 200,
+            time: 30
+        }),
+        Some((11, 19))
+    );
+    assert_eq!(
+        minmax_charge_to_win(&Race {
+            distance: 10,
+            time: 6
+        }),
+        None
+    );
+    assert_eq!(
+        minmax_charge_to_win(&Race {
+            distance: 9,
+            time: 6
+        }),
+        None
+    );
+}
+
+fn ways_to_win_race(race: &Race) -> u64 {
+    minmax_charge_to_win(race).map_or(0, |(min, max)| max - min + 1)
+}
+
+/// [`ways_to_win_race`] by trying every charge time.
+fn ways_to_win_race_naive(race: &Race) -> u64 {
+    (0..=race.time)
+        .filter(|&charge| distance_travelled(race.time, charge) > race.distance)
+        .count() as u64
+}
+
+/// The product of the number of ways to win each race, counted by `ways_to_win`.
+fn product_of_ways(races: &[Race], ways_to_win: fn(&Race) -> u64) -> Result<u64, AocError> {
+    races
+        .iter()
+        .map(ways_to_win)
+        .try_fold(1u64, |product, ways| product.checked_mul(ways))
+        .ok_or_else(|| AocError::Invalid("there are too many ways to win to count".to_string()))
+}
+
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    product_of_ways(&parse_races(input)?, ways_to_win_race)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT1), Ok(288));
+}
+
+/// The input with the "kerning" between digits removed, so each line holds a single number.
+fn unkern(input: &str) -> String {
+    input
+        .lines()
+        .flat_map(|line| line.chars().filter(char::is_ascii_digit).chain(['\n']))
+        .collect()
+}
+
+/// Part 1 with the kerning removed.
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    part1(&unkern(input))
+}
+
+/// The sheet of race times and record distances, read as part 1 reads it and as part 2 does,
+/// without the spaces.
+pub struct RaceSheet {
+    races: Vec<Race>,
+    unkerned: Vec<Race>,
+}
+
+impl RaceSheet {
+    fn races(&self, part: u8) -> &[Race] {
+        match part {
+            1 => &self.races,
+            _ => &self.unkerned,
+        }
+    }
+}
+
+impl Solver for RaceSheet {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self {
+            races: parse_races(input)?,
+            unkerned: parse_races(&unkern(input))?,
+        })
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(product_of_ways(self.races(1), ways_to_win_race)?.to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(product_of_ways(self.races(2), ways_to_win_race)?.to_string())
+    }
+
+    fn naive(&self, part: u8) -> Result<String, SolveError> {
+        Ok(product_of_ways(self.races(part), ways_to_win_race_naive)?.to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part1(TEST_INPUT2), Ok(71503));
+    assert_eq!(part2(TEST_INPUT1), Ok(71503));
+    assert_eq!(part2(TEST_INPUT2), Ok(71503));
+}
+
+#[test]
+fn test_naive() {
+    let sheet = RaceSheet::parse(TEST_INPUT1).unwrap();
+    assert_eq!(sheet.naive(1), Ok("288".to_string()));
+    assert_eq!(sheet.naive(2), Ok("71503".to_string()));
+}
+
+#[cfg(test)]
+const TEST_INPUT1: &str = aoc_core::examples::get(6, 1);
+
+#[cfg(test)]
+const TEST_INPUT2: &str = r#"Time:      71530
+Distance:  940200"#;
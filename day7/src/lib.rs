@@ -0,0 +1,303 @@
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+
+use aoc_core::{parse_lines, AocError, SolveError, Solver};
+
+fn card_to_value(card: u8) -> u8 {
+    match card {
+        b'2'..=b'9' => card - b'0',
+        b'T' => 10,
+        b'J' => 1, // Part 2 says J is Joker and only worth 1
+        b'Q' => 12,
+        b'K' => 13,
+        b'A' => 14,
+        _ => unreachable!(),
+    }
+}
+
+fn cmp_cards(a: u8, b: u8) -> std::cmp::Ordering {
+    card_to_value(a).cmp(&card_to_value(b))
+}
+
+pub struct CamelCards {
+    hands: Vec<Hand>,
+}
+
+impl FromStr for CamelCards {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CamelCards {
+            hands: parse_lines(s, Hand::from_str)?,
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Hand {
+    hand: String,
+    bid: u32,
+}
+
+impl FromStr for Hand {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || AocError::malformed(s, "5 cards and a bid");
+        let mut parts = s.split_whitespace();
+        let hand = parts.next().ok_or_else(malformed)?.to_string();
+        let is_card = |c| matches!(c, b'2'..=b'9' | b'T' | b'J' | b'Q' | b'K' | b'A');
+        if hand.len() != 5 || !hand.bytes().all(is_card) {
+            return Err(malformed());
+        }
+        let bid = parts
+            .next()
+            .ok_or_else(malformed)?
+            .parse()
+            .map_err(|_| malformed())?;
+        if parts.next().is_some() {
+            return Err(malformed());
+        }
+        Ok(Hand { hand, bid })
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    let malformed =
+        |line: usize, s: &str| AocError::malformed(s, "5 cards and a bid").on_line(line);
+    assert_eq!(
+        "32T3K 765\nT55J 684".parse::<CamelCards>().err(),
+        Some(malformed(2, "T55J 684"))
+    );
+    assert_eq!(
+        "32T3X 765".parse::<CamelCards>().err(),
+        Some(malformed(1, "32T3X 765"))
+    );
+    assert_eq!(
+        "32T3K 765 1".parse::<CamelCards>().err(),
+        Some(malformed(1, "32T3K 765 1"))
+    );
+    assert_eq!(
+        "32T3K".parse::<CamelCards>().err(),
+        Some(malformed(1, "32T3K"))
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parse_hand_round_trip(hand in "[2-9TJQKA]{5}", bid: u32) {
+        proptest::prop_assert_eq!(
+            format!("{hand} {bid}").parse::<Hand>(),
+            Ok(Hand { hand, bid })
+        );
+    }
+
+    #[test]
+    fn test_parse_never_panics(input in "([2-9TJQKAX]{4,6} [0-9]{1,11}\n){0,4}|\\PC*") {
+        let _ = input.parse::<CamelCards>();
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, PartialOrd, Ord)]
+enum Type {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl Hand {
+    fn find_type(&self) -> Type {
+        let mut counts: HashMap<u8, u32> = HashMap::new();
+        let mut num_jokers = 0;
+        for ch in self.hand.bytes() {
+            if ch == b'J' {
+                num_jokers += 1;
+            } else {
+                *counts.entry(ch).or_default() += 1;
+            }
+        }
+        if counts.len() <= 1 {
+            return Type::FiveOfAKind;
+        }
+
+        let mut best_counts: Vec<u32> = counts.values().cloned().collect();
+        // Sort so highest numbers are at the start
+        best_counts.sort_by(|a, b| b.cmp(a));
+        match (best_counts[0], best_counts[1]) {
+            (4, 1) => Type::FourOfAKind,
+            (3, 1) => {
+                if num_jokers == 1 {
+                    Type::FourOfAKind
+                } else {
+                    Type::ThreeOfAKind
+                }
+            }
+            (3, 2) => Type::FullHouse,
+            (2, 2) => {
+                if num_jokers == 1 {
+                    Type::FullHouse
+                } else {
+                    Type::TwoPair
+                }
+            }
+            (2, 1) => match num_jokers {
+                0 => Type::OnePair,
+                1 => Type::ThreeOfAKind,
+                2 => Type::FourOfAKind,
+                _ => unreachable!(),
+            },
+            (1, 1) => match num_jokers {
+                0 => Type::HighCard,
+                1 => Type::OnePair,
+                2 => Type::ThreeOfAKind,
+                3 => Type::FourOfAKind,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn test_find_type() {
+    assert_eq!(
+        Hand {
+            hand: "32T3K".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::OnePair
+    );
+    assert_eq!(
+        Hand {
+            hand: "T55J5".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::FourOfAKind
+    );
+    assert_eq!(
+        Hand {
+            hand: "KK677".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::TwoPair
+    );
+    assert_eq!(
+        Hand {
+            hand: "KTJJT".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::FourOfAKind
+    );
+    assert_eq!(
+        Hand {
+            hand: "QQQJA".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::FourOfAKind
+    );
+    assert_eq!(
+        Hand {
+            hand: "AAAAJ".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::FiveOfAKind
+    );
+    assert_eq!(
+        Hand {
+            hand: "33333".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::FiveOfAKind
+    );
+    assert_eq!(
+        Hand {
+            hand: "12345".to_string(),
+            bid: 0
+        }
+        .find_type(),
+        Type::HighCard
+    );
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.find_type().cmp(&other.find_type()).then_with(|| {
+            for i in 0..self.hand.len() {
+                match cmp_cards(self.hand.as_bytes()[i], other.hand.as_bytes()[i]) {
+                    Ordering::Equal => (),
+                    non_eq => return non_eq,
+                }
+            }
+            Ordering::Equal
+        })
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[test]
+#[ignore = "Only works for part 1"]
+fn test_hand_ordering() {
+    let mut cards: CamelCards = TEST_INPUT.parse().unwrap();
+    cards.hands.sort();
+    assert_eq!(cards.hands[0].hand, "32T3K");
+    assert_eq!(cards.hands[1].hand, "KTJJT");
+    assert_eq!(cards.hands[2].hand, "KK677");
+    assert_eq!(cards.hands[3].hand, "T55J5");
+    assert_eq!(cards.hands[4].hand, "QQQJA");
+}
+
+impl CamelCards {
+    /// Sum of each hand's bid times its rank, with jokers wild.
+    fn total_winnings(&self) -> u32 {
+        let mut hands: Vec<&Hand> = self.hands.iter().collect();
+        hands.sort();
+        hands
+            .iter()
+            .enumerate()
+            .map(|(rank, hand)| (rank as u32 + 1) * hand.bid)
+            .sum()
+    }
+}
+
+pub fn part2(s: &str) -> Result<u32, AocError> {
+    Ok(s.parse::<CamelCards>()?.total_winnings())
+}
+
+impl Solver for CamelCards {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.total_winnings().to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(5905));
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(7, 1);
@@ -0,0 +1,290 @@
+use nom::character::complete::char;
+use nom::{
+    bytes::complete::{tag, take_while1},
+    sequence::{delimited, separated_pair},
+};
+use std::{collections::HashSet, str::FromStr};
+
+use aoc_core::{number::lcm, Dot, Graph, NodeId, SolveError, Solver};
+use tracing::debug;
+
+/// The directions to follow, and the network with each node's left then right node as its
+/// successors.
+#[derive(Debug)]
+pub struct Maps {
+    directions: String,
+    network: Graph<String>,
+}
+
+impl FromStr for Maps {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let directions = lines.next().ok_or(())?.to_string();
+        if directions.is_empty() || directions.contains(|c| c != 'L' && c != 'R') {
+            debug!("directions {directions:?} aren't all L and R");
+            return Err(());
+        }
+        lines.next(); // skip blank line
+        let mut network = Graph::new();
+        for l in lines {
+            let mut combinator = separated_pair(
+                take_while1(char::is_alphanumeric),
+                tag(" = "),
+                delimited(
+                    char('('),
+                    separated_pair(
+                        take_while1(char::is_alphanumeric),
+                        tag(", "),
+                        take_while1(char::is_alphanumeric),
+                    ),
+                    char(')'),
+                ),
+            );
+            match combinator(l) {
+                Ok((_, (src, (left, right)))) => {
+                    let src = network.add_node(src);
+                    for next in [left, right] {
+                        let next = network.add_node(next);
+                        network.add_edge(src, next);
+                    }
+                }
+                Err(e) => {
+                    let e: nom::Err<()> = e;
+                    debug!(line = l, "couldn't parse map: {e:?}");
+                    return Err(());
+                }
+            }
+        }
+        // Every node needs a map of its own, and only one
+        if let Some(node) = network
+            .nodes()
+            .find(|&node| network.successors(node).len() != 2)
+        {
+            debug!("{} doesn't have exactly one map", network.label(node));
+            return Err(());
+        }
+        Ok(Maps {
+            directions,
+            network,
+        })
+    }
+}
+
+#[test]
+fn test_maps_parse() {
+    let maps: Maps = TEST_INPUT2.parse().unwrap();
+    assert_eq!(maps.directions, "LLR");
+    let mut lines: Vec<String> = maps
+        .network
+        .nodes()
+        .map(|node| {
+            let [left, right] = maps.network.successors(node) else {
+                panic!("every node has two successors");
+            };
+            format!(
+                "{} = ({}, {})",
+                maps.network.label(node),
+                maps.network.label(*left),
+                maps.network.label(*right)
+            )
+        })
+        .collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        ["AAA = (BBB, BBB)", "BBB = (AAA, ZZZ)", "ZZZ = (ZZZ, ZZZ)"]
+    );
+    assert_eq!("LR\n\nAAA = (BBB, BBB)".parse::<Maps>().unwrap_err(), ());
+    assert_eq!("\n\nAAA = (AAA, AAA)".parse::<Maps>().unwrap_err(), ());
+    assert_eq!("LX\n\nAAA = (AAA, AAA)".parse::<Maps>().unwrap_err(), ());
+    assert_eq!(
+        "LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)"
+            .parse::<Maps>()
+            .unwrap_err(),
+        ()
+    );
+}
+
+impl Maps {
+    /// The node reached by following `dir` from `location`.
+    fn step(&self, location: NodeId, dir: char) -> NodeId {
+        let [left, right] = self.network.successors(location) else {
+            unreachable!("every node has two successors");
+        };
+        match dir {
+            'R' => *right,
+            'L' => *left,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Nodes whose names end in `suffix`.
+    fn nodes_ending_with(&self, suffix: char) -> impl Iterator<Item = NodeId> + '_ {
+        self.network
+            .nodes()
+            .filter(move |&node| self.network.label(node).ends_with(suffix))
+    }
+
+    /// Steps from `from` to any of `to`, or None if following the directions never gets
+    /// there. Once there've been as many steps as there are nodes and places in the directions
+    /// together, it's going round in circles.
+    fn count_steps_from_to(&self, from: NodeId, to: &HashSet<NodeId>) -> Option<usize> {
+        let mut location = from;
+        let limit = self.directions.len() * self.network.len();
+        for (iteration, dir) in self.directions.chars().cycle().take(limit).enumerate() {
+            location = self.step(location, dir);
+            if to.contains(&location) {
+                return Some(iteration + 1);
+            }
+        }
+        None
+    }
+
+    fn count_steps(&self) -> Result<usize, SolveError> {
+        let (Some(start), Some(end)) = (self.network.id("AAA"), self.network.id("ZZZ")) else {
+            return Err(SolveError::failed("the network needs AAA and ZZZ"));
+        };
+        self.count_steps_from_to(start, &[end].into())
+            .ok_or_else(|| SolveError::failed("ZZZ can't be reached from AAA"))
+    }
+
+    fn count_ghost_steps(&self) -> Result<usize, SolveError> {
+        let end_locations = self.nodes_ending_with('Z').collect();
+        if self.nodes_ending_with('A').next().is_none() {
+            return Err(SolveError::failed("no node ends in A to start from"));
+        }
+
+        self.nodes_ending_with('A').try_fold(1, |steps, start| {
+            let to_end = self
+                .count_steps_from_to(start, &end_locations)
+                .ok_or_else(|| {
+                    let start = self.network.label(start);
+                    SolveError::failed(format!("no node ending in Z can be reached from {start}"))
+                })?;
+            Ok(lcm(steps, to_end))
+        })
+    }
+
+    /// The network in Graphviz format, with each edge labelled by the direction that follows
+    /// it.
+    pub fn to_dot(&self) -> Dot {
+        let mut dot = Dot::digraph("network");
+        dot.add_graph(
+            &self.network,
+            |node| self.network.label(node).clone(),
+            |_, nth| Some(["L", "R"][nth].to_string()),
+        );
+        dot
+    }
+
+    /// [`Self::count_ghost_steps`] by walking every ghost at once until they're all at an end.
+    fn count_ghost_steps_naive(&self) -> usize {
+        assert!(!self.directions.is_empty());
+
+        let mut locations: Vec<NodeId> = self.nodes_ending_with('A').collect();
+        for (iteration, dir) in self.directions.chars().cycle().enumerate() {
+            for loc in locations.iter_mut() {
+                *loc = self.step(*loc, dir);
+            }
+
+            if locations
+                .iter()
+                .all(|&loc| self.network.label(loc).ends_with('Z'))
+            {
+                return iteration + 1;
+            }
+        }
+        unreachable!()
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    input.parse::<Maps>().unwrap().count_steps().unwrap()
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT1), 2);
+    assert_eq!(part1(TEST_INPUT2), 6);
+
+    let part1 = |input: &str| Maps::parse(input).unwrap().part1();
+    assert_eq!(
+        part1("L\n\nBBB = (BBB, BBB)\n"),
+        Err(SolveError::failed("the network needs AAA and ZZZ"))
+    );
+    assert_eq!(
+        Maps::parse("L\n\nBBB = (BBB, BBB)\n").unwrap().part2(),
+        Err(SolveError::failed("no node ends in A to start from"))
+    );
+    assert_eq!(
+        part1("LR\n\nAAA = (AAA, BBB)\nBBB = (AAA, AAA)\nZZZ = (AAA, AAA)\n"),
+        Err(SolveError::failed("ZZZ can't be reached from AAA"))
+    );
+}
+
+#[test]
+fn test_to_dot() {
+    let maps: Maps = TEST_INPUT2.parse().unwrap();
+    assert_eq!(
+        maps.to_dot().to_string(),
+        r#"digraph network {
+    0 [label="AAA"];
+    1 [label="BBB"];
+    2 [label="ZZZ"];
+    0 -> 1 [label="L"];
+    0 -> 1 [label="R"];
+    1 -> 0 [label="L"];
+    1 -> 2 [label="R"];
+    2 -> 2 [label="L"];
+    2 -> 2 [label="R"];
+}
+"#
+    );
+}
+
+pub fn part2(input: &str) -> usize {
+    input.parse::<Maps>().unwrap().count_ghost_steps().unwrap()
+}
+
+impl Solver for Maps {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        input
+            .parse()
+            .map_err(|()| SolveError::failed("malformed maps"))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        Ok(self.count_steps()?.to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        Ok(self.count_ghost_steps()?.to_string())
+    }
+
+    /// Part 1 is only ever solved the obvious way.
+    fn naive(&self, part: u8) -> Result<String, SolveError> {
+        match part {
+            2 => Ok(self.count_ghost_steps_naive().to_string()),
+            _ => Err(SolveError::NotImplemented),
+        }
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT3), 6);
+    let maps = Maps::parse(TEST_INPUT3).unwrap();
+    assert_eq!(maps.naive(2), Ok("6".to_string()));
+    assert_eq!(maps.naive(1), Err(SolveError::NotImplemented));
+}
+
+#[cfg(test)]
+const TEST_INPUT1: &str = aoc_core::examples::get(8, 1);
+
+#[cfg(test)]
+const TEST_INPUT2: &str = aoc_core::examples::get(8, 2);
+
+#[cfg(test)]
+const TEST_INPUT3: &str = aoc_core::examples::get(8, 3);
@@ -0,0 +1,134 @@
+use std::io::BufRead;
+
+use aoc_core::{for_each_line, parse_lines, parse_numbers, AocError, SolveError, Solver};
+
+/// One line's history, which has to have at least one value.
+fn parse_history(line: &str) -> Result<Vec<i64>, AocError> {
+    let history = parse_numbers(line)?;
+    if history.is_empty() {
+        return Err(AocError::malformed(line, "a history of numbers"));
+    }
+    Ok(history)
+}
+
+/// Each history in the report.
+fn parse(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    parse_lines(input, parse_history)
+}
+
+fn get_differences(nums: &[i64]) -> Vec<i64> {
+    nums.windows(2).map(|win| win[1] - win[0]).collect()
+}
+
+#[test]
+fn test_get_differences() {
+    assert_eq!(get_differences(&[-2, 3, 18, 24]), [5, 15, 6]);
+    assert_eq!(get_differences(&[1]), [0; 0]);
+}
+
+fn compute_all_differences(nums: Vec<i64>) -> Vec<Vec<i64>> {
+    let mut differences: Vec<Vec<i64>> = vec![nums];
+    loop {
+        let next_differences = get_differences(differences.last().unwrap());
+        if next_differences.iter().all(|x| *x == 0) {
+            break;
+        }
+        differences.push(next_differences);
+    }
+    differences
+}
+
+#[test]
+fn test_compute_all_differences() {
+    assert_eq!(
+        compute_all_differences(vec![0, 1, 2, 3]),
+        [vec![0, 1, 2, 3], vec![1, 1, 1]]
+    );
+    assert_eq!(
+        compute_all_differences(vec![0, 1, 3, 6]),
+        [vec![0, 1, 3, 6], vec![1, 2, 3], vec![1, 1]]
+    );
+}
+
+fn get_next(nums: Vec<i64>) -> i64 {
+    let differences = compute_all_differences(nums);
+    let mut last_end_diff = 0;
+    for diff in differences.iter().rev() {
+        last_end_diff += diff.last().unwrap();
+    }
+    last_end_diff
+}
+
+fn get_prev(nums: Vec<i64>) -> i64 {
+    let differences = compute_all_differences(nums);
+    let mut last_start_diff = 0;
+    for diff in differences.iter().rev() {
+        last_start_diff = diff.first().unwrap() - last_start_diff;
+    }
+    last_start_diff
+}
+
+pub fn part1(input: &str) -> Result<i64, AocError> {
+    Ok(parse(input)?.into_iter().map(get_next).sum())
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(TEST_INPUT), Ok(114));
+    assert_eq!(
+        part1("1 2\n\n3"),
+        Err(AocError::malformed("", "a history of numbers").on_line(2))
+    );
+}
+
+pub fn part2(input: &str) -> Result<i64, AocError> {
+    Ok(parse(input)?.into_iter().map(get_prev).sum())
+}
+
+/// Both parts' answers, reading the report a history at a time.
+pub fn solve_streaming(reader: impl BufRead) -> Result<(i64, i64), AocError> {
+    let (mut next_sum, mut prev_sum) = (0, 0);
+    for_each_line(reader, |line| {
+        let history = parse_history(line)?;
+        next_sum += get_next(history.clone());
+        prev_sum += get_prev(history);
+        Ok(())
+    })?;
+    Ok((next_sum, prev_sum))
+}
+
+#[test]
+fn test_solve_streaming() {
+    assert_eq!(solve_streaming(TEST_INPUT.as_bytes()), Ok((114, 2)));
+    assert_eq!(
+        solve_streaming("1 2\n\n3".as_bytes()),
+        Err(AocError::malformed("", "a history of numbers").on_line(2))
+    );
+}
+
+/// The oasis report, one history per line.
+pub struct Report(Vec<Vec<i64>>);
+
+impl Solver for Report {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        Ok(Self(parse(input)?))
+    }
+
+    fn part1(&self) -> Result<String, SolveError> {
+        let sum: i64 = self.0.iter().cloned().map(get_next).sum();
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self) -> Result<String, SolveError> {
+        let sum: i64 = self.0.iter().cloned().map(get_prev).sum();
+        Ok(sum.to_string())
+    }
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(TEST_INPUT), Ok(2));
+}
+
+#[cfg(test)]
+const TEST_INPUT: &str = aoc_core::examples::get(9, 1);
@@ -0,0 +1,12 @@
+//! Applies an arbitrary initialization sequence to day 15's boxes.
+#![no_main]
+
+use day15::Boxes;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|list: &str| {
+    let mut boxes = Boxes::default();
+    if boxes.apply_list(list).is_ok() {
+        boxes.get_focusing_power();
+    }
+});
@@ -0,0 +1,20 @@
+//! Runs every part of an arbitrary day 19 puzzle through its workflows, checking that the
+//! compiled workflows agree with the originals.
+#![no_main]
+
+use day19::Puzzle;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let Ok(puzzle) = input.parse::<Puzzle>() else {
+        return;
+    };
+    let compiled = puzzle.workflows.compile();
+    for part in &puzzle.parts {
+        assert_eq!(
+            puzzle.workflows.check_part(part),
+            compiled.check_part(part),
+            "{part:?}"
+        );
+    }
+});
@@ -0,0 +1,53 @@
+//! Feeds each day's parser arbitrary input. The first byte picks the day, and the rest is the
+//! puzzle input, which only has to be parsed without panicking, not parsed successfully.
+//!
+//! Every day's [`Solver::parse`] builds the puzzle it solves, so this reaches all of the
+//! parsing, like day 18 reading both its directions and its colors. The parts aren't run, since
+//! on arbitrary input some of them go on for as long as the steps or presses they're given.
+//!
+//! Run with `cargo +nightly fuzz run parse` from the repository root, and likewise for the
+//! other targets.
+#![no_main]
+
+use aoc_core::Solver;
+use libfuzzer_sys::fuzz_target;
+
+fn parse<S: Solver>(input: &str) {
+    let _ = S::parse(input);
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&day, input)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return;
+    };
+    match day % 25 + 1 {
+        1 => parse::<day1::CalibrationDocument>(input),
+        2 => parse::<day2::GameRecord>(input),
+        3 => parse::<day3::Schematic>(input),
+        4 => parse::<day4::Scratchcards>(input),
+        5 => parse::<day5::Almanac>(input),
+        6 => parse::<day6::RaceSheet>(input),
+        7 => parse::<day7::CamelCards>(input),
+        8 => parse::<day8::Maps>(input),
+        9 => parse::<day9::Report>(input),
+        10 => parse::<day10::Puzzle>(input),
+        11 => parse::<day11::StarMap>(input),
+        12 => parse::<day12::ConditionRecords>(input),
+        13 => parse::<day13::Notes>(input),
+        14 => parse::<day14::Platform>(input),
+        15 => parse::<day15::InitSequence>(input),
+        16 => parse::<day16::Contraption>(input),
+        17 => parse::<day17::Map>(input),
+        18 => parse::<day18::DigPlan>(input),
+        19 => parse::<day19::Puzzle>(input),
+        20 => parse::<day20::ModuleConfiguration>(input),
+        21 => parse::<day21::GardenMap>(input),
+        22 => parse::<day22::SupportGraph>(input),
+        23 => parse::<day23::Maze>(input),
+        24 => parse::<day24::Hailstones>(input),
+        _ => parse::<day25::Graph>(input),
+    }
+});
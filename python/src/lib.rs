@@ -0,0 +1,199 @@
+//! The `aoc2023` Python module: every day's solver, plus typed access to the structures some
+//! days build along the way, for analysing and plotting them from a notebook. Build it into
+//! the current virtualenv with `maturin develop --release` from this directory.
+
+use pyo3::{
+    exceptions::{PyIndexError, PyValueError},
+    prelude::*,
+};
+
+fn value_error(e: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Solves one part of one day's puzzle for the given input, raising ValueError if the input
+/// is broken or that part isn't solved yet.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u8, part: u8, input_text: &str) -> PyResult<String> {
+    py.allow_threads(|| aoc_solvers::solve(day, part, input_text))
+        .map_err(value_error)
+}
+
+/// A machine part's ratings, from day 19.
+#[pyclass(frozen, get_all)]
+#[derive(Clone)]
+struct Part {
+    x: i64,
+    m: i64,
+    a: i64,
+    s: i64,
+}
+
+#[pymethods]
+impl Part {
+    #[new]
+    fn new(x: i64, m: i64, a: i64, s: i64) -> Self {
+        Self { x, m, a, s }
+    }
+
+    fn __repr__(&self) -> String {
+        let Self { x, m, a, s } = self;
+        format!("Part(x={x}, m={m}, a={a}, s={s})")
+    }
+}
+
+impl From<&day19::Part> for Part {
+    fn from(&day19::Part { x, m, a, s }: &day19::Part) -> Self {
+        Self { x, m, a, s }
+    }
+}
+
+/// Parts whose ratings in each category are from the first to the second number of its pair,
+/// all of which day 19's workflows accept.
+#[pyclass(frozen, get_all)]
+struct PartRange {
+    x: (i64, i64),
+    m: (i64, i64),
+    a: (i64, i64),
+    s: (i64, i64),
+    /// How many distinct parts are in the range
+    count: i64,
+}
+
+#[pymethods]
+impl PartRange {
+    fn __repr__(&self) -> String {
+        let Self { x, m, a, s, count } = self;
+        format!("PartRange(x={x:?}, m={m:?}, a={a:?}, s={s:?}, count={count})")
+    }
+}
+
+/// Day 19's workflows.
+#[pyclass(frozen)]
+struct Workflows(day19::Workflows);
+
+#[pymethods]
+impl Workflows {
+    fn accepts(&self, part: PyRef<'_, Part>) -> bool {
+        let Part { x, m, a, s } = *part;
+        self.0.check_part(&day19::Part { x, m, a, s })
+    }
+
+    /// Disjoint ranges covering every part with ratings from 1 to 4000 that gets accepted.
+    fn accepted_regions(&self) -> Vec<PartRange> {
+        let ends = |r: &day19::Range| (r.min, r.max);
+        self.0
+            .accepted_regions()
+            .iter()
+            .map(|region| PartRange {
+                x: ends(&region.x),
+                m: ends(&region.m),
+                a: ends(&region.a),
+                s: ends(&region.s),
+                count: region.num_distinct_parts(),
+            })
+            .collect()
+    }
+
+    /// How many distinct parts with ratings from 1 to 4000 get accepted.
+    fn count_accepted(&self) -> i64 {
+        self.0.solve_part2()
+    }
+}
+
+/// Day 19's input, as its workflows and the parts listed after them.
+#[pyfunction(name = "day19")]
+fn parse_day19(input_text: &str) -> PyResult<(Workflows, Vec<Part>)> {
+    let puzzle: day19::Puzzle = input_text.parse().map_err(value_error)?;
+    let parts = puzzle.parts.iter().map(Part::from).collect();
+    Ok((Workflows(puzzle.workflows), parts))
+}
+
+/// A brick from day 22 where it came to rest, given by the (x, y, z) cells at either end.
+#[pyclass(frozen, get_all)]
+struct Brick {
+    name: String,
+    start: (i64, i64, i64),
+    end: (i64, i64, i64),
+}
+
+#[pymethods]
+impl Brick {
+    fn __repr__(&self) -> String {
+        let Self { name, start, end } = self;
+        format!("Brick(name={name:?}, start={start:?}, end={end:?})")
+    }
+}
+
+/// Day 22's bricks once they've settled, and which rest directly on which. Bricks are referred
+/// to by their index in `bricks()`.
+#[pyclass(frozen)]
+struct SupportGraph(day22::SupportGraph);
+
+impl SupportGraph {
+    fn check(&self, index: usize) -> PyResult<usize> {
+        if index < self.0.bricks().len() {
+            Ok(index)
+        } else {
+            Err(PyIndexError::new_err(format!("there's no brick {index}")))
+        }
+    }
+}
+
+#[pymethods]
+impl SupportGraph {
+    /// The settled bricks, from the bottom up.
+    fn bricks(&self) -> Vec<Brick> {
+        self.0
+            .bricks()
+            .iter()
+            .map(|brick| {
+                let ([x1, y1, z1], [x2, y2, z2]) = brick.ends();
+                Brick {
+                    name: brick.name().to_string(),
+                    start: (x1, y1, z1),
+                    end: (x2, y2, z2),
+                }
+            })
+            .collect()
+    }
+
+    /// Bricks resting directly on brick `index`.
+    fn supports(&self, index: usize) -> PyResult<Vec<usize>> {
+        Ok(self.0.supports(self.check(index)?).to_vec())
+    }
+
+    /// Bricks brick `index` rests directly on.
+    fn supported_by(&self, index: usize) -> PyResult<Vec<usize>> {
+        Ok(self.0.supported_by(self.check(index)?).to_vec())
+    }
+
+    /// Graphviz description of the graph, drawn from the ground up.
+    fn to_dot(&self) -> String {
+        self.0.to_dot()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.bricks().len()
+    }
+}
+
+/// Day 22's bricks, settled.
+#[pyfunction(name = "day22")]
+fn settle_day22(input_text: &str) -> PyResult<SupportGraph> {
+    let bricks = day22::parse_bricks(input_text).map_err(value_error)?;
+    Ok(SupportGraph(day22::support_graph(&bricks)))
+}
+
+#[pymodule]
+fn aoc2023(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_day19, m)?)?;
+    m.add_function(wrap_pyfunction!(settle_day22, m)?)?;
+    m.add_class::<Part>()?;
+    m.add_class::<PartRange>()?;
+    m.add_class::<Workflows>()?;
+    m.add_class::<Brick>()?;
+    m.add_class::<SupportGraph>()?;
+    Ok(())
+}
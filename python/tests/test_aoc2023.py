@@ -0,0 +1,68 @@
+"""Run with pytest after `maturin develop`."""
+
+import pytest
+
+import aoc2023
+
+WORKFLOWS = """\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+"""
+
+BRICKS = """\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+"""
+
+
+def test_solve():
+    assert aoc2023.solve(19, 1, WORKFLOWS) == "19114"
+    assert aoc2023.solve(22, 2, BRICKS) == "7"
+    with pytest.raises(ValueError):
+        aoc2023.solve(22, 1, "1,0,1~1,2")
+    with pytest.raises(ValueError):
+        aoc2023.solve(26, 1, "")
+
+
+def test_day19():
+    workflows, parts = aoc2023.day19(WORKFLOWS)
+    assert [workflows.accepts(part) for part in parts] == [True, False, True, False, True]
+    assert parts[0].x == 787
+    regions = workflows.accepted_regions()
+    assert sum(region.count for region in regions) == workflows.count_accepted()
+    assert workflows.count_accepted() == 167409079868000
+    assert all(1 <= low <= high <= 4000 for region in regions for low, high in [region.x])
+    assert workflows.accepts(aoc2023.Part(x=1, m=1, a=1, s=1)) is True
+
+
+def test_day22():
+    graph = aoc2023.day22(BRICKS)
+    assert len(graph) == 7
+    bricks = graph.bricks()
+    assert bricks[6].name == "G"
+    assert (bricks[6].start, bricks[6].end) == ((1, 1, 5), (1, 1, 6))
+    assert graph.supports(0) == [1, 2]
+    assert graph.supported_by(3) == [1, 2]
+    assert graph.to_dot().startswith("digraph bricks {")
+    with pytest.raises(IndexError):
+        graph.supports(7)
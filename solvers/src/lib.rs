@@ -0,0 +1,47 @@
+//! Every day's solver behind one function, for front ends that are told the day at runtime.
+
+use aoc_core::{SolveError, Solver};
+
+/// Parses `input` as `day`'s puzzle and solves `part`.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, SolveError> {
+    if !(1..=2).contains(&part) {
+        return Err(SolveError::failed(format!("there is no part {part}")));
+    }
+    match day {
+        1 => day1::CalibrationDocument::solve(input, part),
+        2 => day2::GameRecord::solve(input, part),
+        3 => day3::Schematic::solve(input, part),
+        4 => day4::Scratchcards::solve(input, part),
+        5 => day5::Almanac::solve(input, part),
+        6 => day6::RaceSheet::solve(input, part),
+        7 => day7::CamelCards::solve(input, part),
+        8 => day8::Maps::solve(input, part),
+        9 => day9::Report::solve(input, part),
+        10 => day10::Puzzle::solve(input, part),
+        11 => day11::StarMap::solve(input, part),
+        12 => day12::ConditionRecords::solve(input, part),
+        13 => day13::Notes::solve(input, part),
+        14 => day14::Platform::solve(input, part),
+        15 => day15::InitSequence::solve(input, part),
+        16 => day16::Contraption::solve(input, part),
+        17 => day17::Map::solve(input, part),
+        18 => day18::DigPlan::solve(input, part),
+        19 => day19::Puzzle::solve(input, part),
+        20 => day20::ModuleConfiguration::solve(input, part),
+        21 => day21::GardenMap::solve(input, part),
+        22 => day22::SupportGraph::solve(input, part),
+        23 => day23::Maze::solve(input, part),
+        24 => day24::Hailstones::solve(input, part),
+        25 => day25::Graph::solve(input, part),
+        _ => Err(SolveError::failed(format!("there is no day {day}"))),
+    }
+}
+
+#[test]
+fn test_solve() {
+    let races = "Time:      7  15   30\nDistance:  9  40  200\n";
+    assert_eq!(solve(6, 1, races), Ok("288".to_string()));
+    assert_eq!(solve(6, 2, races), Ok("71503".to_string()));
+    assert!(solve(6, 3, races).is_err());
+    assert!(solve(26, 1, races).is_err());
+}
@@ -0,0 +1,101 @@
+//! The solutions built for WebAssembly, for the page in `www/` where a puzzle input can be
+//! pasted in and solved in the browser. From the repository root:
+//!
+//! ```text
+//! wasm-pack build --target web --out-dir www/pkg web
+//! python3 -m http.server -d web/www
+//! ```
+
+use aoc_core::{Grid, SolveError, Visualize};
+use wasm_bindgen::prelude::*;
+
+/// Solves one part of one day's puzzle for the given input.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    Ok(aoc_solvers::solve(day, part, input)?)
+}
+
+/// A picture of a puzzle with one pixel per tile, as RGBA ready for a canvas's `ImageData`.
+#[wasm_bindgen]
+pub struct Image {
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Image {
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rgba(&self) -> Vec<u8> {
+        self.rgba.clone()
+    }
+}
+
+impl Image {
+    fn new(picture: &impl Visualize) -> Self {
+        let frame: Grid<char> = picture.frame();
+        let rgba = frame
+            .iter()
+            .flat_map(|&ch| {
+                let [r, g, b] = picture.color(ch);
+                [r, g, b, 255]
+            })
+            .collect();
+        Self {
+            width: frame.width(),
+            height: frame.height(),
+            rgba,
+        }
+    }
+}
+
+/// Draws part 1 of the days with a grid worth looking at, or None for the others.
+fn draw_day(day: u8, input: &str) -> Result<Option<Image>, SolveError> {
+    Ok(Some(match day {
+        14 => {
+            let mut platform: day14::Platform = input
+                .parse()
+                .map_err(|e| SolveError::failed(format!("{e:?}")))?;
+            platform.slide_cycle_with(|_, _| {});
+            Image::new(&platform)
+        }
+        16 => {
+            let contraption: day16::Contraption = input.parse().map_err(SolveError::failed)?;
+            Image::new(&contraption.trace_beam(day16::Contraption::PART1_START, |_| {}))
+        }
+        18 => Image::new(&day18::part1_trench(input)?),
+        21 => {
+            let garden = day21::Garden::infinite_from_str(input).map_err(SolveError::failed)?;
+            let reachable = garden.reachable_from_start_after_steps(64);
+            Image::new(&day21::Picture(garden.draw_positions(&reachable, 0)))
+        }
+        _ => return Ok(None),
+    }))
+}
+
+/// A picture of the puzzle for the days that have one: day 14's platform after a spin cycle,
+/// day 16's energized tiles, day 18's trench and day 21's reachable plots.
+#[wasm_bindgen]
+pub fn picture(day: u8, input: &str) -> Result<Option<Image>, JsError> {
+    Ok(draw_day(day, input)?)
+}
+
+#[test]
+fn test_draw_day() {
+    let image = draw_day(16, ".\\.\\\n.../").unwrap().unwrap();
+    assert_eq!((image.width, image.height), (4, 2));
+    assert_eq!(image.rgba.len(), 4 * 2 * 4);
+    assert!(draw_day(18, "R 2\nD 2\nL 2\nU 2\n").unwrap().is_some());
+    assert!(draw_day(18, "R 2\n").is_err());
+    assert!(draw_day(6, "").unwrap().is_none());
+}
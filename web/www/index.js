@@ -0,0 +1,60 @@
+// Built by `wasm-pack build --target web --out-dir www/pkg web`
+import init, { picture, solve } from "./pkg/aoc_web.js";
+
+/// How many screen pixels wide each tile of a picture is drawn, at most
+const MAX_SCALE = 8;
+
+const day = document.getElementById("day");
+const input = document.getElementById("input");
+const canvas = document.getElementById("picture");
+
+for (let d = 1; d <= 25; d++) {
+  day.add(new Option(d, d));
+}
+
+function message(e) {
+  // A panic in the solver traps instead of throwing an error with a message
+  return e instanceof WebAssembly.RuntimeError ? "the solver crashed" : e.message ?? String(e);
+}
+
+function draw(image) {
+  if (!image) {
+    canvas.hidden = true;
+    return;
+  }
+  canvas.width = image.width;
+  canvas.height = image.height;
+  const pixels = new Uint8ClampedArray(image.rgba);
+  canvas.getContext("2d").putImageData(new ImageData(pixels, image.width, image.height), 0, 0);
+  const scale = Math.max(1, Math.min(MAX_SCALE, Math.floor(800 / image.width)));
+  canvas.style.width = `${image.width * scale}px`;
+  canvas.hidden = false;
+  image.free();
+}
+
+async function run() {
+  const [d, text] = [Number(day.value), input.value];
+  for (const part of [1, 2]) {
+    const output = document.getElementById(`part${part}`);
+    output.value = "solving…";
+    // Let the page show that before the solver takes over the thread
+    await new Promise((resolve) => setTimeout(resolve));
+    try {
+      output.value = solve(d, part, text);
+    } catch (e) {
+      output.value = `error: ${message(e)}`;
+    }
+  }
+  try {
+    draw(picture(d, text));
+  } catch {
+    // A broken input has already been reported by the parts
+    draw(null);
+  }
+}
+
+await init();
+document.querySelector("form").addEventListener("submit", (event) => {
+  event.preventDefault();
+  run();
+});